@@ -0,0 +1,17 @@
+use std::process::Command;
+
+// Hands the short git hash of the checkout to the build as GIT_HASH, for the
+// version shown on the title screen. Left unset outside a git checkout.
+fn main() {
+    let hash = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok());
+    if let Some(hash) = hash {
+        println!("cargo:rustc-env=GIT_HASH={}", hash.trim());
+    }
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/refs");
+}
@@ -1,36 +1,266 @@
+use std::time::Duration;
 use macroquad::prelude::*;
+use crate::systems::analytics;
+use crate::systems::benchmark;
+use crate::systems::config::GameConfig;
+use crate::systems::keybindings::{Action, KeyBindings};
+use crate::systems::letterbox::{self, game_height, game_width, Letterbox};
+use crate::systems::logging;
+use crate::systems::platform;
+use crate::systems::replay;
 use crate::systems::scenemanagement::SceneManager;
+use crate::systems::resources::resource_path;
+use crate::systems::settings::Settings;
+use crate::systems::window_icon;
 
 mod scenes;
 mod prefabs;
 mod systems;
 
 pub const GRAVITY: f32 = 9.1;
-pub const SCROLL_SPEED: f32 = 3.0;
 pub const FILE_NAME: &str = "highscore.txt";
+pub const SETTINGS_FILE_NAME: &str = "settings.txt";
+pub const KEYS_FILE_NAME: &str = "keys.toml";
+pub const DISTANCE_FILE_NAME: &str = "best_distance.txt";
+pub const TIME_PLAYED_FILE_NAME: &str = "time_played.txt";
+pub const BEST_TIMES_FILE_NAME: &str = "best_times.txt";
+// The most recent run, overwritten at every game over
+pub const REPLAY_FILE_NAME: &str = "last_run.flap";
+pub const RESOURCES_DIR: &str = "resources";
+// Replacement assets dropped here override the ones in RESOURCES_DIR
+pub const RESOURCE_PACK_DIR: &str = "resource_pack";
+pub const WINDOW_TITLE: &str = "Flappy Bird";
+// Frame rate while minimized, just enough to notice coming back
+#[cfg(not(target_arch = "wasm32"))]
+const HIDDEN_FPS: u32 = 10;
 
-// Summary - main() :
-// 1. Create scene manager.
+// Parses `--benchmark [frames] [--seed <seed>]`.
+// Returns None when the game should start normally.
+fn parse_benchmark_args(args: &[String]) -> Option<(u32, u64)> {
+    let position = args.iter().position(|arg| arg == "--benchmark")?;
+
+    let frames = args
+        .get(position + 1)
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(benchmark::DEFAULT_FRAMES);
+
+    let seed = args
+        .iter()
+        .position(|arg| arg == "--seed")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(benchmark::DEFAULT_SEED);
+
+    Some((frames, seed))
+}
+
+// Window settings built from the game config
+fn window_conf(config: &GameConfig) -> Conf {
+    Conf {
+        window_title: WINDOW_TITLE.to_string(),
+        sample_count: config.sample_count,
+        // Without the bird sheet the window keeps the default icon
+        icon: window_icon::load_icon(&resource_path("bird.png")),
+        ..Default::default()
+    }
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+
+    // Benchmark mode runs headless, without opening a window
+    if let Some((frames, seed)) = parse_benchmark_args(&args) {
+        println!("{}", benchmark::run(seed, frames));
+        return;
+    }
+
+    // `--replay <file>` prints what a .flap file holds
+    if let Some(path) = args.iter().position(|arg| arg == "--replay").and_then(|i| args.get(i + 1)) {
+        match replay::load_replay(path) {
+            Ok(replay) => println!("Replay: seed {}, {} flaps, score {}", replay.seed, replay.flaps.len(), replay.score),
+            Err(error) => eprintln!("{}: {}", path, error),
+        }
+        return;
+    }
+
+    logging::init();
+
+    let config = GameConfig::new();
+    macroquad::Window::from_config(window_conf(&config), run(config.fps_cap, config.max_frame_time));
+}
+
+// How long to sleep so a frame that took `elapsed` seconds lasts
+// 1 / target_fps. None when uncapped or the frame already ran long.
+fn frame_sleep(target_fps: u32, elapsed: f64) -> Option<Duration> {
+    if target_fps == 0 {
+        return None;
+    }
+    let remaining = 1.0 / target_fps as f64 - elapsed;
+    (remaining > 0.0).then(|| Duration::from_secs_f64(remaining))
+}
+
+// Browsers pace (and throttle hidden) tabs themselves, and sleeping a
+// thread panics there, so the web build never sleeps
+fn sleep_rest_of_frame(target_fps: u32, frame_start: f64) {
+    if cfg!(target_arch = "wasm32") {
+        return;
+    }
+    if let Some(sleep) = frame_sleep(target_fps, get_time() - frame_start) {
+        std::thread::sleep(sleep);
+    }
+}
+
+// Frame time capped at `max` (0 = no limit), bad readings count as no time
+fn clamp_frame_time(dt: f32, max: f32) -> f32 {
+    if dt.is_nan() || dt <= 0.0 {
+        0.0
+    } else if max > 0.0 {
+        dt.min(max)
+    } else {
+        dt
+    }
+}
+
+// Summary - run() :
+// 1. Create scene manager and read the aspect ratio setting.
 // 2. Enter game loop:
+//     - While the window or tab is hidden, stop sounds and only wait for the next frame.
+//     - Fit the letterboxed game area into the window.
 //     - Run pre-update to load assets or switch scenes.
-//     - Update game logic and handle scene transitions.
-//     - Clear screen and draw current scene.
-//     - Wait for next frame.
-#[macroquad::main("Flappy Bird")]
-async fn main() {
-    let mut scene_manager = SceneManager::new();
+//     - Update game logic, with the frame time clamped against stalls and
+//       scaled for debugging, and handle scene transitions.
+//     - Clear screen and draw current scene. A static scene is drawn once
+//       into a texture, which is shown until it changes or the window does.
+//     - Wait for next frame, then sleep off the rest of the frame time if capped
+//       (the web build leaves pacing to the browser).
+async fn run(fps_cap: u32, max_frame_time: f32) {
+    let mut scene_manager = SceneManager::new(analytics::noop());
+    let aspect_ratio = Settings::load().aspect_ratio;
+    let keys = KeyBindings::load();
+    let mut last_window = Vec2::ZERO;
+    let mut static_frame: Option<RenderTarget> = None;
+    let mut hidden = false;
 
     loop {
+        let frame_start = get_time();
+
+        // Gameplay stops with update, so nothing moves on unseen, and
+        // sounds already playing are stopped once
+        if platform::should_pause(platform::visibility()) {
+            if !hidden {
+                hidden = true;
+                scene_manager.hidden();
+            }
+            next_frame().await;
+            sleep_rest_of_frame(HIDDEN_FPS, frame_start);
+            continue;
+        }
+        hidden = false;
+
+        // Game coordinates for this frame, used by layout and mouse hit-testing
+        let window = vec2(screen_width(), screen_height());
+        letterbox::set_current(aspect_ratio.map(|ratio| Letterbox::fit(window, ratio, letterbox::GAME_HEIGHT)));
+
         // Load assets or transition to new scene if current scene is a TitleScene
         scene_manager.pre_update().await;
 
+        // Debug builds only, like the scoreboard key
+        if cfg!(debug_assertions) && keys.is_action_pressed(Action::TimeScale) {
+            scene_manager.cycle_time_scale();
+            log::info!("time scale {}", scene_manager.time_scale);
+        }
+
         // Update current scene (handle transitions)
-        scene_manager.update();
+        scene_manager.update(clamp_frame_time(get_frame_time(), max_frame_time));
+
+        // A resize has to redraw even a static scene
+        if window != last_window {
+            last_window = window;
+            static_frame = None;
+        }
 
-        // Clear and draw
+        // macroquad clears the window every frame, so a static scene is
+        // shown from its texture rather than left on screen
         clear_background(BLACK);
-        scene_manager.draw();
+        if scene_manager.needs_redraw() {
+            static_frame = None;
+            letterbox::set_game_camera();
+            scene_manager.draw();
+        } else {
+            let frame = static_frame.get_or_insert_with(|| letterbox::draw_into_texture(|| scene_manager.draw()));
+            draw_texture_ex(&frame.texture, 0.0, 0.0, WHITE, DrawTextureParams {
+                dest_size: Some(vec2(game_width(), game_height())),
+                ..Default::default()
+            });
+        }
+        set_default_camera();
+        if scene_manager.time_scale != 1.0 {
+            draw_text(&format!("x{}", scene_manager.time_scale), screen_width() - 60.0, 24.0, 24.0, YELLOW);
+        }
 
         next_frame().await;
+
+        sleep_rest_of_frame(fps_cap, frame_start);
+    }
+}
+
+/*
+
+The tests validate :
+1. Window conf uses the game title
+2. MSAA sample count flows from the config into the window conf
+3. The FPS cap sleeps off the rest of the target frame time
+4. Abnormally long frame times are clamped, normal ones pass through
+5. The window icon is built from the bird sprite sheet
+
+*/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_conf_uses_title() {
+        let conf = window_conf(&GameConfig::new());
+        assert_eq!(conf.window_title, WINDOW_TITLE);
+    }
+
+    #[test]
+    fn test_window_conf_uses_sample_count() {
+        let mut config = GameConfig::new();
+        assert_eq!(window_conf(&config).sample_count, 1);
+
+        config.sample_count = 4;
+        assert_eq!(window_conf(&config).sample_count, 4);
+    }
+
+    #[test]
+    fn test_window_conf_uses_bird_icon() {
+        assert!(window_conf(&GameConfig::new()).icon.is_some());
+    }
+
+    #[test]
+    fn test_frame_sleep_fills_target_frame_time() {
+        let sleep = frame_sleep(30, 0.010).unwrap();
+        assert!((sleep.as_secs_f64() - (1.0 / 30.0 - 0.010)).abs() < 1e-9);
+
+        let sleep = frame_sleep(60, 0.0).unwrap();
+        assert!((sleep.as_secs_f64() - 1.0 / 60.0).abs() < 1e-9);
+
+        // Uncapped, or the frame already took longer than the target
+        assert_eq!(frame_sleep(0, 0.001), None);
+        assert_eq!(frame_sleep(60, 0.020), None);
+    }
+
+    #[test]
+    fn test_clamp_frame_time() {
+        let max = GameConfig::new().max_frame_time;
+        assert_eq!(clamp_frame_time(1.0 / 60.0, max), 1.0 / 60.0);
+        // A two second stall after loading
+        assert_eq!(clamp_frame_time(2.0, max), max);
+        assert_eq!(clamp_frame_time(2.0, 0.0), 2.0);
+
+        assert_eq!(clamp_frame_time(-0.5, max), 0.0);
+        assert_eq!(clamp_frame_time(f32::NAN, max), 0.0);
     }
-}
\ No newline at end of file
+}
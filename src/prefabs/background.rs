@@ -1,5 +1,36 @@
 use macroquad::prelude::*;
-use crate::SCROLL_SPEED;
+use crate::systems::config::{Quality, Speeds};
+use crate::prefabs::sky::SkyRenderer;
+use crate::systems::layout::{wrap_scroll, Layout};
+use crate::systems::letterbox::{game_height, game_width};
+use crate::systems::resources::resource_path;
+use crate::systems::settings::Settings;
+use crate::systems::theme::Theme;
+
+// Which parallax layers are currently scrolling
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LayerScroll {
+    pub forest: bool,
+    pub cityscape: bool,
+    pub cloud: bool,
+}
+
+impl LayerScroll {
+    pub fn all(scroll: bool) -> Self {
+        LayerScroll {
+            forest: scroll,
+            cityscape: scroll,
+            cloud: scroll,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Layer {
+    Cloud,
+    Cityscape,
+    Forest,
+}
 
 pub struct Background {
     forest_texture: Texture2D,
@@ -10,16 +41,21 @@ pub struct Background {
     cityscape_pos: f32,
     cloud_pos: f32,
 
-    pub scroll: bool,
+    scroll: LayerScroll,
+    // Scales every layer's scroll speed, for slow motion (1 = normal, 0 = stopped)
+    speed_multiplier: f32,
+    pub quality: Quality,
+    // Accessibility option: the layers stay where they are
+    pub reduced_motion: bool,
 }
 
 impl Background {
     pub async fn new() -> Self {
-        let forest_texture = load_texture("./resources/trees.png").await.expect("trees.png not found");
+        let forest_texture = load_texture(&resource_path("trees.png")).await.expect("trees.png not found");
         forest_texture.set_filter(FilterMode::Nearest); // optional: avoid smoothing
-        let cityscape_texture = load_texture("./resources/cityscape.png").await.expect("cityscape.png not found");
+        let cityscape_texture = load_texture(&resource_path("cityscape.png")).await.expect("cityscape.png not found");
         cityscape_texture.set_filter(FilterMode::Nearest);
-        let cloud_texture = load_texture("./resources/clouds.png").await.expect("clouds.png not found");
+        let cloud_texture = load_texture(&resource_path("clouds.png")).await.expect("clouds.png not found");
         cloud_texture.set_filter(FilterMode::Nearest);
 
         Background {
@@ -29,60 +65,156 @@ impl Background {
             forest_pos: 0.0,
             cityscape_pos: 0.0,
             cloud_pos: 0.0,
-            scroll: true,
+            scroll: LayerScroll::all(true),
+            speed_multiplier: 1.0,
+            quality: Quality::High,
+            reduced_motion: false,
         }
     }
 
-    pub fn update(&mut self) {
-        if self.scroll {
-            self.forest_pos = (self.forest_pos - SCROLL_SPEED * 0.75) % self.forest_texture.width();
-            self.cityscape_pos = (self.cityscape_pos - SCROLL_SPEED * 0.5) % self.cityscape_texture.width();
-            self.cloud_pos = (self.cloud_pos - SCROLL_SPEED * 0.25) % self.cloud_texture.width();
+    pub fn set_scroll(&mut self, scroll: bool) {
+        self.scroll = LayerScroll::all(scroll);
+    }
+
+    pub fn set_layer_scroll(&mut self, scroll: LayerScroll) {
+        self.scroll = scroll;
+    }
+
+    pub fn set_speed_multiplier(&mut self, multiplier: f32) {
+        self.speed_multiplier = multiplier.max(0.0);
+    }
+
+    // `dt` is already scaled by the time scale, so slow motion slows the layers too
+    pub fn update(&mut self, dt: f32) {
+        (self.forest_pos, self.cityscape_pos, self.cloud_pos) = Self::calculate_positions(
+            (self.forest_pos, self.cityscape_pos, self.cloud_pos),
+            (self.forest_texture.width(), self.cityscape_texture.width(), self.cloud_texture.width()),
+            Self::effective_scroll(self.scroll, self.reduced_motion),
+            dt,
+            self.speed_multiplier
+        );
+    }
+
+    // Reduced motion holds every layer still, whatever the game asked for
+    pub fn effective_scroll(scroll: LayerScroll, reduced_motion: bool) -> LayerScroll {
+        if reduced_motion { LayerScroll::all(false) } else { scroll }
+    }
+
+    // Layers drawn at each quality, back to front
+    pub fn draw_list(quality: Quality) -> &'static [Layer] {
+        match quality {
+            Quality::High => &[Layer::Cloud, Layer::Cityscape, Layer::Forest],
+            Quality::Low => &[Layer::Forest],
         }
     }
 
-    pub fn draw(&self) {
+    // `layers` back to front, see draw_list
+    pub fn draw(&self, layers: &[Layer]) {
         // Y offset from bottom — can adjust for each layer
         let forest_y_offset = 0.0;
         let cityscape_y_offset = 130.0;
         let cloud_y_offset = 130.0;
-    
-        self.draw_layer(&self.cloud_texture, self.cloud_pos, cloud_y_offset);
-        self.draw_layer(&self.cityscape_texture, self.cityscape_pos, cityscape_y_offset);
-        self.draw_layer(&self.forest_texture, self.forest_pos, forest_y_offset);
+
+        for layer in layers {
+            match layer {
+                Layer::Cloud => self.draw_layer(&self.cloud_texture, self.cloud_pos, cloud_y_offset),
+                Layer::Cityscape => self.draw_layer(&self.cityscape_texture, self.cityscape_pos, cityscape_y_offset),
+                Layer::Forest => self.draw_layer(&self.forest_texture, self.forest_pos, forest_y_offset),
+            }
+        }
     }
 
     fn draw_layer(&self, texture: &Texture2D, x_pos: f32, y_offset_from_bottom: f32) {
-        let texture_width = texture.width();
-        let y = screen_height() - y_offset_from_bottom - texture.height();
+        let y = game_height() - y_offset_from_bottom - texture.height();
 
-        // Draw six copies to ensure seamless scroll
-        draw_texture(texture, x_pos, y, WHITE);
-        draw_texture(texture, x_pos + 1.0 * texture_width, y, WHITE);
-        draw_texture(texture, x_pos + 2.0 * texture_width, y, WHITE);
-        draw_texture(texture, x_pos + 3.0 * texture_width, y, WHITE);
-        draw_texture(texture, x_pos + 4.0 * texture_width, y, WHITE);
-        draw_texture(texture, x_pos + 5.0 * texture_width, y, WHITE);
+        // Enough copies to cover the screen for a seamless scroll
+        let layout = Layout::new(vec2(game_width(), game_height()), 0.0);
+        for x in layout.scrolled_tile_offsets(texture.width(), x_pos) {
+            draw_texture(texture, x, y, WHITE);
+        }
     }
 
-    // This function is added to production code for extensive test coverage
+    // This function is added to production code for extensive test coverage.
+    // Positions and widths are (forest, cityscape, cloud).
     pub fn calculate_positions(
-        forest_pos: f32,
-        cityscape_pos: f32,
-        cloud_pos: f32,
-        forest_width: f32,
-        cityscape_width: f32,
-        cloud_width: f32,
-        scroll: bool
+        (forest_pos, cityscape_pos, cloud_pos): (f32, f32, f32),
+        (forest_width, cityscape_width, cloud_width): (f32, f32, f32),
+        scroll: LayerScroll,
+        dt: f32,
+        speed_multiplier: f32
     ) -> (f32, f32, f32) {
-        if scroll {
-            (
-                (forest_pos - SCROLL_SPEED * 0.75) % forest_width,
-                (cityscape_pos - SCROLL_SPEED * 0.5) % cityscape_width,
-                (cloud_pos - SCROLL_SPEED * 0.25) % cloud_width
-            )
+        let distance = Speeds::distance(Speeds::SCROLL * speed_multiplier, dt);
+        let forest_pos = if scroll.forest {
+            wrap_scroll(forest_pos - distance * Speeds::FOREST_FACTOR, forest_width)
+        } else {
+            forest_pos
+        };
+        let cityscape_pos = if scroll.cityscape {
+            wrap_scroll(cityscape_pos - distance * Speeds::CITYSCAPE_FACTOR, cityscape_width)
+        } else {
+            cityscape_pos
+        };
+        let cloud_pos = if scroll.cloud {
+            wrap_scroll(cloud_pos - distance * Speeds::CLOUD_FACTOR, cloud_width)
         } else {
-            (forest_pos, cityscape_pos, cloud_pos)
+            cloud_pos
+        };
+
+        (forest_pos, cityscape_pos, cloud_pos)
+    }
+}
+
+// Summary - Scenery :
+// Everything behind the pipes. Normally the sky plus the parallax layers;
+// the minimal background setting skips loading those and draws one flat colour.
+pub enum Scenery {
+    Full { sky: SkyRenderer, background: Background },
+    Flat(Color),
+}
+
+impl Scenery {
+    pub async fn load(settings: &Settings) -> Self {
+        if settings.minimal_background {
+            return Scenery::Flat(settings.background_color);
+        }
+
+        let mut background = Background::new().await;
+        background.quality = settings.quality;
+        background.reduced_motion = settings.reduced_motion;
+        Scenery::Full {
+            sky: SkyRenderer::load(&Theme::new()).await,
+            background,
+        }
+    }
+
+    // Parallax layers drawn each frame, none for a flat colour
+    pub fn layers(&self) -> &'static [Layer] {
+        match self {
+            Scenery::Full { background, .. } => Background::draw_list(background.quality),
+            Scenery::Flat(_) => &[],
+        }
+    }
+
+    pub fn background_mut(&mut self) -> Option<&mut Background> {
+        match self {
+            Scenery::Full { background, .. } => Some(background),
+            Scenery::Flat(_) => None,
+        }
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        if let Some(background) = self.background_mut() {
+            background.update(dt);
+        }
+    }
+
+    pub fn draw(&self, layout: &Layout) {
+        match self {
+            Scenery::Full { sky, background } => {
+                sky.draw(layout);
+                background.draw(self.layers());
+            }
+            Scenery::Flat(color) => draw_rectangle(0.0, 0.0, layout.screen.x, layout.screen.y, *color),
         }
     }
 }
@@ -90,11 +222,18 @@ impl Background {
 /* 
 
 The tests validate:
-1. Basic position updates
+1. Basic position updates, matching the old per-frame movement at 60 FPS
 2. Position wrapping behavior
 3. Scroll enable/disable state
 4. Relative parallax speeds
 5. Correct modulo operations
+6. Layers can be frozen independently
+7. Low quality skips the cloud and cityscape layers
+8. A flat minimal background draws no parallax layers
+9. The frame time scales how far every layer moves per update
+10. Positions stay within [-width, 0) however many updates run
+11. Reduced motion keeps every parallax position still, even with scrolling on
+12. A 0.5 speed multiplier halves how far every layer moves per update, 0 stops them
 
 */ 
 
@@ -103,30 +242,37 @@ mod tests {
     use super::*;
     use float_eq::assert_float_eq;
 
+    // One frame at 60 FPS
+    const FRAME: f32 = 1.0 / 60.0;
+
     #[test]
     fn test_scroll_calculations() {
-        // Test basic scrolling
+        // One frame moves each layer as far as the old per-frame speeds did
         let (f, c, cl) = Background::calculate_positions(
-            0.0, 0.0, 0.0,
-            100.0, 150.0, 200.0,
-            true
+            (0.0, 0.0, 0.0),
+            (100.0, 150.0, 200.0),
+            LayerScroll::all(true),
+            FRAME,
+            1.0
         );
         
-        assert_float_eq!(f, -SCROLL_SPEED * 0.75, abs <= 0.001);
-        assert_float_eq!(c, -SCROLL_SPEED * 0.5, abs <= 0.001);
-        assert_float_eq!(cl, -SCROLL_SPEED * 0.25, abs <= 0.001);
+        assert_float_eq!(f, -2.25, abs <= 0.001);
+        assert_float_eq!(c, -1.5, abs <= 0.001);
+        assert_float_eq!(cl, -0.75, abs <= 0.001);
     }
 
     #[test]
     fn test_position_wrapping() {
         // Test negative position wrapping
         let (f, _, _) = Background::calculate_positions(
-            -95.0, 0.0, 0.0,
-            100.0, 150.0, 200.0,
-            true
+            (-95.0, 0.0, 0.0),
+            (100.0, 150.0, 200.0),
+            LayerScroll::all(true),
+            FRAME,
+            1.0
         );
         
-        let expected = (-95.0 - SCROLL_SPEED * 0.75) % 100.0;
+        let expected = (-95.0 - 2.25) % 100.0;
         assert_float_eq!(f, expected, abs <= 0.001);
     }
 
@@ -134,9 +280,11 @@ mod tests {
     fn test_scroll_disabled() {
         // Test scroll disabled state
         let (f, c, cl) = Background::calculate_positions(
-            10.0, 20.0, 30.0,
-            100.0, 150.0, 200.0,
-            false
+            (10.0, 20.0, 30.0),
+            (100.0, 150.0, 200.0),
+            LayerScroll::all(false),
+            FRAME,
+            1.0
         );
         
         assert_float_eq!(f, 10.0, abs <= 0.001);
@@ -148,12 +296,115 @@ mod tests {
     fn test_parallax_speeds() {
         // Verify relative movement speeds
         let (f, c, cl) = Background::calculate_positions(
-            0.0, 0.0, 0.0,
-            100.0, 150.0, 200.0,
-            true
+            (0.0, 0.0, 0.0),
+            (100.0, 150.0, 200.0),
+            LayerScroll::all(true),
+            FRAME,
+            1.0
         );
         
         assert!(f.abs() > c.abs());
         assert!(c.abs() > cl.abs());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_independent_layer_freezing() {
+        // Forest and cityscape stopped, clouds still drifting
+        let scroll = LayerScroll {
+            forest: false,
+            cityscape: false,
+            cloud: true,
+        };
+        let (f, c, cl) = Background::calculate_positions(
+            (10.0, 20.0, -30.0),
+            (100.0, 150.0, 200.0),
+            scroll,
+            FRAME,
+            1.0
+        );
+
+        assert_float_eq!(f, 10.0, abs <= 0.001);
+        assert_float_eq!(c, 20.0, abs <= 0.001);
+        assert_float_eq!(cl, -30.0 - 0.75, abs <= 0.001);
+    }
+
+    #[test]
+    fn test_low_quality_skips_cloud_and_cityscape() {
+        let count = |quality: Quality, layer: Layer| {
+            Background::draw_list(quality).iter().filter(|drawn| **drawn == layer).count()
+        };
+
+        assert_eq!(Background::draw_list(Quality::High).len(), 3);
+        assert_eq!(count(Quality::High, Layer::Cloud), 1);
+        assert_eq!(count(Quality::High, Layer::Cityscape), 1);
+
+        assert_eq!(count(Quality::Low, Layer::Cloud), 0);
+        assert_eq!(count(Quality::Low, Layer::Cityscape), 0);
+        assert_eq!(count(Quality::Low, Layer::Forest), 1);
+    }
+
+    #[test]
+    fn test_minimal_background_draws_no_layers() {
+        let scenery = Scenery::Flat(BLACK);
+        assert!(scenery.layers().is_empty());
+    }
+
+    #[test]
+    fn test_frame_time_scales_movement() {
+        let positions = (-10.0, -20.0, -30.0);
+        let widths = (1000.0, 1000.0, 1000.0);
+        let (f, c, cl) = Background::calculate_positions(positions, widths, LayerScroll::all(true), FRAME, 1.0);
+        let (f_half, c_half, cl_half) = Background::calculate_positions(positions, widths, LayerScroll::all(true), FRAME / 2.0, 1.0);
+
+        assert_float_eq!(-10.0 - f_half, (-10.0 - f) * 0.5, abs <= 0.001);
+        assert_float_eq!(-20.0 - c_half, (-20.0 - c) * 0.5, abs <= 0.001);
+        assert_float_eq!(-30.0 - cl_half, (-30.0 - cl) * 0.5, abs <= 0.001);
+
+        let stopped = Background::calculate_positions(positions, widths, LayerScroll::all(true), 0.0, 1.0);
+        assert_eq!(stopped, positions);
+    }
+
+    #[test]
+    fn test_speed_multiplier_scales_movement() {
+        let positions = (-10.0, -20.0, -30.0);
+        let widths = (1000.0, 1000.0, 1000.0);
+        let (f, c, cl) = Background::calculate_positions(positions, widths, LayerScroll::all(true), FRAME, 1.0);
+        let (f_half, c_half, cl_half) = Background::calculate_positions(positions, widths, LayerScroll::all(true), FRAME, 0.5);
+
+        assert_float_eq!(-10.0 - f_half, (-10.0 - f) * 0.5, abs <= 0.001);
+        assert_float_eq!(-20.0 - c_half, (-20.0 - c) * 0.5, abs <= 0.001);
+        assert_float_eq!(-30.0 - cl_half, (-30.0 - cl) * 0.5, abs <= 0.001);
+
+        let frozen = Background::calculate_positions(positions, widths, LayerScroll::all(true), FRAME, 0.0);
+        assert_eq!(frozen, positions);
+    }
+
+    #[test]
+    fn test_positions_stay_normalized() {
+        let widths = (288.0, 300.0, 512.0);
+        let mut positions = (0.0, 0.0, 0.0);
+        // Over four hours of play at 60 frames per second
+        for _ in 0..1_000_000 {
+            positions = Background::calculate_positions(positions, widths, LayerScroll::all(true), FRAME, 1.0);
+        }
+
+        let (f, c, cl) = positions;
+        assert!((-widths.0..0.0).contains(&f));
+        assert!((-widths.1..0.0).contains(&c));
+        assert!((-widths.2..0.0).contains(&cl));
+    }
+
+    #[test]
+    fn test_reduced_motion_keeps_layers_still() {
+        let positions = (-10.0, -20.0, -30.0);
+        let widths = (1000.0, 1000.0, 1000.0);
+        assert_eq!(Background::effective_scroll(LayerScroll::all(true), false), LayerScroll::all(true));
+
+        let scroll = Background::effective_scroll(LayerScroll::all(true), true);
+        let mut moved = positions;
+        for _ in 0..100 {
+            moved = Background::calculate_positions(moved, widths, scroll, FRAME, 1.0);
+        }
+        assert_eq!(moved, positions);
+    }
+}
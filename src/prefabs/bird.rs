@@ -1,9 +1,30 @@
 use macroquad::prelude::*;
+use crate::systems::letterbox::{game_height, game_width};
 use crate::systems::physics::{check_collision, PhysicsBody};
 use crate::GRAVITY;
+use crate::systems::resources::resource_path;
+
+// Everything about the bird that changes while playing, without the
+// textures, so it can be saved and restored cheaply
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BirdState {
+    pub position: Vec2,
+    pub velocity: Vec2,
+    pub allow_gravity: bool,
+    pub alive: bool,
+    pub gravity_sign: f32,
+    pub rotation: f32,
+    pub spin_rate: f32,
+    pub invulnerable_timer: f32,
+    pub size_scale: f32,
+    pub size_timer: f32,
+}
 
 pub struct Bird {
-    textures: Vec<Texture2D>,
+    // The whole sprite sheet, frames are picked out with a source rect
+    texture: Option<Texture2D>,
+    frame_count: usize,
+    frame_size: Vec2,
     current_frame: usize,
     frame_timer: f32,
     frame_duration: f32,
@@ -12,11 +33,38 @@ pub struct Bird {
     pub allow_gravity: bool,
     pub alive: bool,
     pub fixed_x_position: f32,
+    // 1.0 falls down, -1.0 falls up while a gravity flip is active
+    pub gravity_sign: f32,
+    pub gravity: f32,
+    // Accessibility option: flaps dive and the bird falls upwards instead
+    pub inverted: bool,
+    // Accessibility option: a pipe-killed bird falls without spinning
+    pub reduced_motion: bool,
+    // Seconds left during which pipes can't kill the bird, it blinks meanwhile
+    pub invulnerable_timer: f32,
+    // Sprite rotation in radians, only changes while tumbling after a death
+    rotation: f32,
+    spin_rate: f32,
+    // Size power-up: multiplies the drawn and collision size around the
+    // centre until size_timer runs out
+    pub size_scale: f32,
+    size_timer: f32,
+    // Follow-mouse sandbox: height the bird's centre glides towards instead
+    // of falling, None for normal flapping
+    pub follow_target: Option<f32>,
+    // Cartoon squash and stretch: a flap stretches the sprite and the top of
+    // the climb squashes it, easing back to normal over SQUASH_DURATION.
+    // Only the drawn size changes, never the hitbox.
+    pub squash_stretch: bool,
+    squash_from: Vec2,
+    squash_timer: f32,
 }
 
 impl PhysicsBody for Bird {
     fn get_collision_rect(&mut self) -> Rect {
-        Rect::new(self.position.x, self.position.y, 34.0, 24.0)
+        let size = self.size();
+        let top_left = self.center() - size / 2.0;
+        Rect::new(top_left.x, top_left.y, size.x, size.y)
     }
 
     fn collides_with(&mut self, obj: &Rect) -> bool {
@@ -25,81 +73,305 @@ impl PhysicsBody for Bird {
 }
 
 impl Bird {
+    const FRAME_COUNT: usize = 3;
+    pub const FRAME_WIDTH: f32 = 34.0;
+    pub const FRAME_HEIGHT: f32 = 24.0;
+    pub const DEFAULT_FRAME_DURATION: f32 = 0.1;
+    // Radians per second the bird spins while tumbling to the ground
+    const DEATH_SPIN_RATE: f32 = 12.0;
+    // Seconds the bird stays visible, then hidden, while blinking
+    const BLINK_INTERVAL: f32 = 0.1;
+    // How quickly the bird catches up with the pointer when following it
+    const FOLLOW_RATE: f32 = 12.0;
+    // Drawn scale (width, height) right after a flap and at the top of a climb
+    const FLAP_STRETCH: Vec2 = vec2(0.9, 1.15);
+    const APEX_SQUASH: Vec2 = vec2(1.1, 0.9);
+    pub const SQUASH_DURATION: f32 = 0.15;
+
     pub async fn new() -> Self {
-        let texture = load_texture("./resources/bird.png").await.unwrap();
-        let texture_data = texture.get_texture_data();
-        let mut textures = Vec::new();
-        
-        for i in 0..3 {
-            let sub_image = texture_data.sub_image(Rect::new(
-                i as f32 * 34.0,
-                0.0,
-                34.0,
-                24.0
-            ));
-            textures.push(Texture2D::from_image(&sub_image));
-        }
+        let texture = load_texture(&resource_path("bird.png")).await.unwrap();
+        let (frame_count, frame_size) = Self::frame_layout(texture.width(), texture.height());
 
-        let fixed_x = screen_width() / 2.5;
+        let fixed_x = game_width() / 2.5;
 
         Bird {
-            textures,
+            texture: Some(texture),
+            frame_count,
+            frame_size,
             current_frame: 0,
             frame_timer: 0.0,
-            frame_duration: 0.1,
+            frame_duration: Self::DEFAULT_FRAME_DURATION,
             velocity: Vec2::ZERO,
-            position: vec2(fixed_x, screen_height() / 2.0),
+            position: vec2(fixed_x, game_height() / 2.0),
             allow_gravity: false,
             alive: true,
             fixed_x_position: fixed_x,
+            gravity_sign: 1.0,
+            gravity: GRAVITY,
+            inverted: false,
+            reduced_motion: false,
+            invulnerable_timer: 0.0,
+            rotation: 0.0,
+            spin_rate: 0.0,
+            size_scale: 1.0,
+            size_timer: 0.0,
+            follow_target: None,
+            squash_stretch: false,
+            squash_from: Vec2::ONE,
+            squash_timer: 0.0,
+        }
+    }
+
+    pub fn state(&self) -> BirdState {
+        BirdState {
+            position: self.position,
+            velocity: self.velocity,
+            allow_gravity: self.allow_gravity,
+            alive: self.alive,
+            gravity_sign: self.gravity_sign,
+            rotation: self.rotation,
+            spin_rate: self.spin_rate,
+            invulnerable_timer: self.invulnerable_timer,
+            size_scale: self.size_scale,
+            size_timer: self.size_timer,
+        }
+    }
+
+    pub fn restore(&mut self, state: &BirdState) {
+        self.position = state.position;
+        self.velocity = state.velocity;
+        self.allow_gravity = state.allow_gravity;
+        self.alive = state.alive;
+        self.gravity_sign = state.gravity_sign;
+        self.rotation = state.rotation;
+        self.spin_rate = state.spin_rate;
+        self.invulnerable_timer = state.invulnerable_timer;
+        self.size_scale = state.size_scale;
+        self.size_timer = state.size_timer;
+    }
+
+    // Scales the bird by `scale` for `duration` seconds, replacing any
+    // size change still running
+    pub fn resize(&mut self, scale: f32, duration: f32) {
+        self.size_scale = scale;
+        self.size_timer = duration;
+    }
+
+    // Collision size, the sprite is drawn at the same scale
+    pub fn size(&self) -> Vec2 {
+        vec2(Self::FRAME_WIDTH, Self::FRAME_HEIGHT) * self.size_scale
+    }
+
+    pub fn is_invulnerable(&self) -> bool {
+        self.invulnerable_timer > 0.0
+    }
+
+    // Alternates every BLINK_INTERVAL while invulnerable, always shown otherwise
+    pub fn blink_visible(invulnerable_timer: f32) -> bool {
+        invulnerable_timer <= 0.0 || (invulnerable_timer / Self::BLINK_INTERVAL) as i32 % 2 == 0
+    }
+
+    pub fn velocity(&self) -> Vec2 {
+        self.velocity
+    }
+
+    pub fn center(&self) -> Vec2 {
+        self.position + vec2(Self::FRAME_WIDTH, Self::FRAME_HEIGHT) / 2.0
+    }
+
+    // 1.0 when the bird falls down the screen, -1.0 when it falls up.
+    // Inverted controls flip it while alive, a dead bird always comes down.
+    pub fn fall_direction(&self) -> f32 {
+        if self.inverted && self.alive {
+            -self.gravity_sign
+        } else {
+            self.gravity_sign
         }
     }
 
     pub fn flap(&mut self) {
         if self.alive {
-            self.velocity.y = -6.5;
+            // Always pushes against gravity
+            self.velocity.y = -6.5 * self.fall_direction();
+            self.start_squash(Self::FLAP_STRETCH);
+        }
+    }
+
+    fn start_squash(&mut self, scale: Vec2) {
+        if self.squash_stretch {
+            self.squash_from = scale;
+            self.squash_timer = Self::SQUASH_DURATION;
+        }
+    }
+
+    // Drawn scale from squash and stretch, (1, 1) once it has worn off
+    pub fn squash_scale(&self) -> Vec2 {
+        let left = (self.squash_timer / Self::SQUASH_DURATION).clamp(0.0, 1.0);
+        Vec2::ONE + (self.squash_from - Vec2::ONE) * left
+    }
+
+    // Works out how many frames the sheet holds and their size.
+    // The sheet is expected to be one row of FRAME_WIDTH wide frames; any
+    // other width is treated as a single frame covering the whole image.
+    pub fn frame_layout(sheet_width: f32, sheet_height: f32) -> (usize, Vec2) {
+        if sheet_width <= 0.0 || sheet_height <= 0.0 {
+            return (0, Vec2::ZERO);
+        }
+
+        let frame_count = (sheet_width / Self::FRAME_WIDTH).round() as usize;
+        if frame_count >= 1 && (frame_count as f32 * Self::FRAME_WIDTH - sheet_width).abs() < 0.5 {
+            (frame_count, vec2(Self::FRAME_WIDTH, sheet_height))
+        } else {
+            (1, vec2(sheet_width, sheet_height))
+        }
+    }
+
+    // Part of the sheet holding frame `index`, frames sit side by side in one row
+    pub fn frame_source(index: usize, frame_size: Vec2) -> Rect {
+        Rect::new(index as f32 * frame_size.x, 0.0, frame_size.x, frame_size.y)
+    }
+
+    pub fn set_frame_duration(&mut self, duration: f32) {
+        self.frame_duration = duration.max(0.01);
+    }
+
+    // Wings beat twice as fast while the bird is climbing against gravity after a flap
+    fn animation_frame_duration(&self) -> f32 {
+        if self.velocity.y * self.fall_direction() < 0.0 {
+            self.frame_duration / 2.0
+        } else {
+            self.frame_duration
         }
     }
 
-    pub fn kill(&mut self) {
+    // A tumbling death (pipe hit) keeps gravity on and spins the bird as it
+    // falls, otherwise (ground hit) the bird stops where it is
+    pub fn kill(&mut self, tumble: bool) {
         self.alive = false;
         self.velocity = Vec2::ZERO;
+        // Always comes down to the ground, even during a gravity flip
+        self.gravity_sign = 1.0;
+        if tumble {
+            self.allow_gravity = true;
+            self.spin_rate = if self.reduced_motion { 0.0 } else { Self::DEATH_SPIN_RATE };
+        } else {
+            self.allow_gravity = false;
+            self.spin_rate = 0.0;
+        }
+    }
+
+    // Bird without textures, used by the headless simulation
+    pub fn headless(position: Vec2) -> Self {
+        Bird {
+            texture: None,
+            frame_count: Self::FRAME_COUNT,
+            frame_size: vec2(Self::FRAME_WIDTH, Self::FRAME_HEIGHT),
+            current_frame: 0,
+            frame_timer: 0.0,
+            frame_duration: Self::DEFAULT_FRAME_DURATION,
+            velocity: Vec2::ZERO,
+            position,
+            allow_gravity: false,
+            alive: true,
+            fixed_x_position: position.x,
+            gravity_sign: 1.0,
+            gravity: GRAVITY,
+            inverted: false,
+            reduced_motion: false,
+            invulnerable_timer: 0.0,
+            rotation: 0.0,
+            spin_rate: 0.0,
+            size_scale: 1.0,
+            size_timer: 0.0,
+            follow_target: None,
+            squash_stretch: false,
+            squash_from: Vec2::ONE,
+            squash_timer: 0.0,
+        }
     }
 
-    pub fn reset(&mut self) {
-        self.position = vec2(self.fixed_x_position, screen_height() / 2.0);
+    pub fn reset(&mut self, screen_height: f32) {
+        self.position = vec2(self.fixed_x_position, screen_height / 2.0);
         self.velocity = Vec2::ZERO;
         self.alive = true;
+        self.rotation = 0.0;
+        self.spin_rate = 0.0;
+        self.gravity_sign = 1.0;
+        self.invulnerable_timer = 0.0;
+        self.size_scale = 1.0;
+        self.size_timer = 0.0;
+        self.squash_timer = 0.0;
     }
 
-    pub fn update(&mut self) {
-        self.frame_timer += get_frame_time();
-        if self.frame_timer >= self.frame_duration {
+    // Frame time and screen height are passed in so the bird can also be
+    // simulated without a window
+    pub fn step(&mut self, dt: f32, screen_height: f32) {
+        self.frame_timer += dt;
+        if self.frame_timer >= self.animation_frame_duration() {
             self.frame_timer = 0.0;
-            if self.alive {
-                self.current_frame = (self.current_frame + 1) % self.textures.len();
+            if self.alive && self.frame_count > 0 {
+                self.current_frame = (self.current_frame + 1) % self.frame_count;
+            }
+        }
+
+        self.rotation += self.spin_rate * dt;
+        self.invulnerable_timer = (self.invulnerable_timer - dt).max(0.0);
+        self.squash_timer = (self.squash_timer - dt).max(0.0);
+        if self.size_timer > 0.0 {
+            self.size_timer -= dt;
+            if self.size_timer <= 0.0 {
+                self.size_timer = 0.0;
+                self.size_scale = 1.0;
             }
         }
 
-        if self.allow_gravity {
-            self.velocity.y += GRAVITY / 30.0;
+        // Keep bird within vertical bounds
+        let min_y = 12.0;
+        let max_y = screen_height - 36.0;
+        if let Some(target) = self.follow_target.filter(|_| self.alive) {
+            self.velocity = Vec2::ZERO;
+            let target_y = target - Self::FRAME_HEIGHT / 2.0;
+            self.position.y = Self::follow_y(self.position.y, target_y, dt).clamp(min_y, max_y);
+        } else if self.allow_gravity {
+            let was_rising = self.velocity.y * self.fall_direction() < 0.0;
+            self.velocity.y += self.fall_direction() * self.gravity / 30.0;
+            if self.alive && was_rising && self.velocity.y * self.fall_direction() >= 0.0 {
+                self.start_squash(Self::APEX_SQUASH);
+            }
             self.position.y += self.velocity.y;
-            
-            // Keep bird within vertical bounds
-            let min_y = 12.0;
-            let max_y = screen_height() - 36.0;
             self.position.y = self.position.y.clamp(min_y, max_y);
         }
     }
 
+    // Eases `y` towards `target_y`, framerate independent and never past it
+    pub fn follow_y(y: f32, target_y: f32, dt: f32) -> f32 {
+        let blend = 1.0 - (-Self::FOLLOW_RATE * dt.max(0.0)).exp();
+        y + (target_y - y) * blend
+    }
+
     pub fn draw(&self) {
+        // Nothing to draw if the sheet could not be sliced into frames
+        let Some(texture) = &self.texture else {
+            return;
+        };
+        if self.frame_count == 0 || !Self::blink_visible(self.invulnerable_timer) {
+            return;
+        }
+
+        // Scaled around the centre, like the collision rect
+        let size = self.frame_size * self.size_scale * self.squash_scale();
+        let top_left = self.center() - size / 2.0;
         draw_texture_ex(
-            &self.textures[self.current_frame],
-            self.position.x,
-            self.position.y,
+            texture,
+            top_left.x,
+            top_left.y,
             WHITE,
             DrawTextureParams {
-                pivot: Some(vec2(17.0, 12.0)),
+                source: Some(Self::frame_source(self.current_frame, self.frame_size)),
+                dest_size: Some(size),
+                rotation: self.rotation,
+                // The pivot is in screen space, spin around the middle of the sprite
+                pivot: Some(self.center()),
                 ..Default::default()
             },
         );
@@ -113,6 +385,20 @@ The tests validate :
 2. Kill state handling
 3. Collision rectangle calculation
 4. Collision detection logic
+5. Headless stepping applies gravity and stays within bounds
+6. Shorter frame duration animates faster, and rising animates faster than falling
+7. Frame layout derived from the sprite sheet size
+8. A pipe-killed bird tumbles down while a ground-killed bird stays put
+9. Flipped gravity makes the bird fall upwards and flaps push it down
+10. The velocity getter follows flaps and gravity
+11. Inverted controls make flaps dive and gravity pull up until death
+12. Each frame is drawn from its own source rect in the sheet
+13. An invulnerable bird blinks at a fixed interval, others always draw
+14. The collision rect scales around the centre with size_scale and reverts when the timer runs out
+15. Following the pointer eases towards the target height without overshooting, gravity is ignored
+16. With reduced motion a pipe-killed bird still falls but never rotates
+17. Squash and stretch: a flap stretches, the top of the climb squashes, and the
+    scale is back to (1, 1) once SQUASH_DURATION has passed. Off by default.
 
 */
 
@@ -125,7 +411,9 @@ mod tests {
     // Test helper to create Bird instance without Macroquad dependencies
     fn test_bird() -> Bird {
         Bird {
-            textures: vec![],
+            texture: None,
+            frame_count: 3,
+            frame_size: vec2(Bird::FRAME_WIDTH, Bird::FRAME_HEIGHT),
             current_frame: 0,
             frame_timer: 0.0,
             frame_duration: 0.1,
@@ -134,6 +422,19 @@ mod tests {
             allow_gravity: false,
             alive: true,
             fixed_x_position: 100.0,
+            gravity_sign: 1.0,
+            gravity: GRAVITY,
+            inverted: false,
+            reduced_motion: false,
+            invulnerable_timer: 0.0,
+            rotation: 0.0,
+            spin_rate: 0.0,
+            size_scale: 1.0,
+            size_timer: 0.0,
+            follow_target: None,
+            squash_stretch: false,
+            squash_from: Vec2::ONE,
+            squash_timer: 0.0,
         }
     }
 
@@ -148,7 +449,7 @@ mod tests {
     fn test_kill_resets_velocity() {
         let mut bird = test_bird();
         bird.velocity.y = -5.0;
-        bird.kill();
+        bird.kill(false);
         assert!(!bird.alive);
         assert_float_eq!(bird.velocity.y, 0.0, abs <= 0.001);
     }
@@ -176,4 +477,294 @@ mod tests {
         let distant_obstacle = Rect::new(200.0, 200.0, 20.0, 20.0);
         assert!(!bird.collides_with(&distant_obstacle));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_headless_step_applies_gravity_within_bounds() {
+        let mut bird = Bird::headless(Vec2::new(100.0, 300.0));
+        bird.allow_gravity = true;
+
+        bird.step(1.0 / 60.0, 600.0);
+        assert!(bird.position.y > 300.0);
+
+        for _ in 0..200 {
+            bird.step(1.0 / 60.0, 600.0);
+        }
+        assert_float_eq!(bird.position.y, 600.0 - 36.0, abs <= 0.001);
+    }
+
+    // Number of animation frame changes over `steps` steps of `dt`
+    fn count_frame_changes(bird: &mut Bird, steps: usize, dt: f32) -> usize {
+        let mut changes = 0;
+        for _ in 0..steps {
+            let before = bird.current_frame;
+            bird.step(dt, 600.0);
+            if bird.current_frame != before {
+                changes += 1;
+            }
+        }
+        changes
+    }
+
+    #[test]
+    fn test_shorter_frame_duration_animates_faster() {
+        let mut slow = test_bird();
+        let mut fast = test_bird();
+        fast.set_frame_duration(Bird::DEFAULT_FRAME_DURATION / 2.0);
+
+        let slow_changes = count_frame_changes(&mut slow, 20, 0.05);
+        let fast_changes = count_frame_changes(&mut fast, 20, 0.05);
+
+        assert_eq!(slow_changes, 10);
+        assert_eq!(fast_changes, 20);
+    }
+
+    #[test]
+    fn test_rising_bird_animates_faster() {
+        let mut falling = test_bird();
+        let mut rising = test_bird();
+        rising.velocity.y = -6.5;
+
+        assert!(count_frame_changes(&mut rising, 20, 0.05) > count_frame_changes(&mut falling, 20, 0.05));
+    }
+
+    #[test]
+    fn test_frame_layout_from_sheet_width() {
+        // Standard three frame sheet
+        let (count, size) = Bird::frame_layout(102.0, 24.0);
+        assert_eq!(count, 3);
+        assert_float_eq!(size.x, 34.0, abs <= 0.001);
+        assert_float_eq!(size.y, 24.0, abs <= 0.001);
+
+        // Longer sheet with the same frame width
+        let (count, _) = Bird::frame_layout(136.0, 24.0);
+        assert_eq!(count, 4);
+
+        // Taller frames keep the sheet height
+        let (count, size) = Bird::frame_layout(68.0, 30.0);
+        assert_eq!(count, 2);
+        assert_float_eq!(size.y, 30.0, abs <= 0.001);
+    }
+
+    #[test]
+    fn test_frame_layout_falls_back_to_single_frame() {
+        let (count, size) = Bird::frame_layout(50.0, 24.0);
+        assert_eq!(count, 1);
+        assert_float_eq!(size.x, 50.0, abs <= 0.001);
+        assert_float_eq!(size.y, 24.0, abs <= 0.001);
+
+        let (count, _) = Bird::frame_layout(20.0, 24.0);
+        assert_eq!(count, 1);
+
+        let (count, _) = Bird::frame_layout(0.0, 0.0);
+        assert_eq!(count, 0);
+    }
+
+    #[test]
+    fn test_pipe_killed_bird_tumbles_down() {
+        let mut bird = test_bird();
+        bird.allow_gravity = true;
+        bird.kill(true);
+
+        bird.step(0.1, 600.0);
+        let first_velocity = bird.velocity.y;
+        bird.step(0.1, 600.0);
+
+        assert!(bird.velocity.y > first_velocity);
+        assert!(bird.position.y > 300.0);
+        assert!(bird.rotation > 0.0);
+    }
+
+    #[test]
+    fn test_ground_killed_bird_stays_put() {
+        let mut bird = test_bird();
+        bird.allow_gravity = true;
+        bird.kill(false);
+
+        bird.step(0.1, 600.0);
+        bird.step(0.1, 600.0);
+
+        assert_float_eq!(bird.velocity.y, 0.0, abs <= 0.001);
+        assert_float_eq!(bird.position.y, 300.0, abs <= 0.001);
+        assert_float_eq!(bird.rotation, 0.0, abs <= 0.001);
+    }
+
+    #[test]
+    fn test_flipped_gravity_falls_upwards() {
+        let mut bird = test_bird();
+        bird.allow_gravity = true;
+        bird.gravity_sign = -1.0;
+
+        bird.step(0.1, 600.0);
+        assert!(bird.velocity.y < 0.0);
+        assert!(bird.position.y < 300.0);
+
+        bird.flap();
+        assert_float_eq!(bird.velocity.y, 6.5, abs <= 0.001);
+
+        // Death always restores normal gravity
+        bird.kill(true);
+        assert_float_eq!(bird.gravity_sign, 1.0, abs <= 0.001);
+    }
+
+    #[test]
+    fn test_velocity_getter() {
+        let mut bird = test_bird();
+        assert_eq!(bird.velocity(), Vec2::ZERO);
+
+        bird.flap();
+        assert_float_eq!(bird.velocity().y, -6.5, abs <= 0.001);
+
+        bird.allow_gravity = true;
+        bird.step(0.0, 600.0);
+        assert_float_eq!(bird.velocity().y, -6.5 + GRAVITY / 30.0, abs <= 0.001);
+        assert_float_eq!(bird.velocity().x, 0.0, abs <= 0.001);
+    }
+
+    #[test]
+    fn test_inverted_controls_dive() {
+        let mut bird = test_bird();
+        bird.inverted = true;
+
+        bird.flap();
+        assert!(bird.velocity().y > 0.0);
+        assert_float_eq!(bird.velocity().y, 6.5, abs <= 0.001);
+
+        // Gravity pulls the other way so the dive can be recovered from
+        bird.velocity = Vec2::ZERO;
+        bird.allow_gravity = true;
+        bird.step(0.0, 600.0);
+        assert!(bird.velocity().y < 0.0);
+
+        // Still comes down to the ground once dead
+        bird.kill(true);
+        bird.step(0.0, 600.0);
+        assert!(bird.velocity().y > 0.0);
+    }
+
+    #[test]
+    fn test_frame_source_rects() {
+        let frame_size = vec2(Bird::FRAME_WIDTH, Bird::FRAME_HEIGHT);
+        assert_eq!(Bird::frame_source(0, frame_size), Rect::new(0.0, 0.0, 34.0, 24.0));
+        assert_eq!(Bird::frame_source(1, frame_size), Rect::new(34.0, 0.0, 34.0, 24.0));
+        assert_eq!(Bird::frame_source(2, frame_size), Rect::new(68.0, 0.0, 34.0, 24.0));
+
+        // A single frame sheet is used whole
+        assert_eq!(Bird::frame_source(0, vec2(40.0, 30.0)), Rect::new(0.0, 0.0, 40.0, 30.0));
+    }
+
+    #[test]
+    fn test_invulnerable_blink() {
+        // Counts down, so visibility flips every interval from the start
+        assert!(Bird::blink_visible(1.0));
+        assert!(!Bird::blink_visible(0.95));
+        assert!(Bird::blink_visible(0.85));
+        assert!(!Bird::blink_visible(0.75));
+
+        assert!(Bird::blink_visible(0.0));
+        assert!(Bird::blink_visible(-1.0));
+
+        let mut bird = test_bird();
+        assert!(!bird.is_invulnerable());
+        bird.invulnerable_timer = 0.15;
+        assert!(bird.is_invulnerable());
+        bird.step(0.2, 600.0);
+        assert!(!bird.is_invulnerable());
+    }
+
+    #[test]
+    fn test_size_scale_collision_and_revert() {
+        let mut bird = test_bird();
+        let center = bird.get_collision_rect().center();
+
+        bird.resize(1.5, 1.0);
+        let rect = bird.get_collision_rect();
+        assert_float_eq!(rect.w, Bird::FRAME_WIDTH * 1.5, abs <= 0.001);
+        assert_float_eq!(rect.h, Bird::FRAME_HEIGHT * 1.5, abs <= 0.001);
+        assert_float_eq!(rect.center().x, center.x, abs <= 0.001);
+        assert_float_eq!(rect.center().y, center.y, abs <= 0.001);
+
+        bird.step(0.5, 600.0);
+        assert_float_eq!(bird.get_collision_rect().w, Bird::FRAME_WIDTH * 1.5, abs <= 0.001);
+
+        bird.step(0.6, 600.0);
+        let rect = bird.get_collision_rect();
+        assert_float_eq!(rect.w, Bird::FRAME_WIDTH, abs <= 0.001);
+        assert_float_eq!(rect.h, Bird::FRAME_HEIGHT, abs <= 0.001);
+        assert_float_eq!(bird.size_scale, 1.0, abs <= 0.001);
+
+        bird.resize(0.5, 1.0);
+        assert_float_eq!(bird.get_collision_rect().w, Bird::FRAME_WIDTH * 0.5, abs <= 0.001);
+        bird.reset(600.0);
+        assert_float_eq!(bird.get_collision_rect().w, Bird::FRAME_WIDTH, abs <= 0.001);
+    }
+
+    #[test]
+    fn test_follow_target_without_overshoot() {
+        assert_float_eq!(Bird::follow_y(100.0, 100.0, 1.0 / 60.0), 100.0, abs <= 0.001);
+        assert_float_eq!(Bird::follow_y(100.0, 300.0, 0.0), 100.0, abs <= 0.001);
+        // A long frame gets close but doesn't pass the target
+        let y = Bird::follow_y(100.0, 300.0, 10.0);
+        assert!(y <= 300.0 && y > 299.0);
+
+        let mut bird = test_bird();
+        bird.allow_gravity = true;
+        bird.position.y = 300.0;
+        bird.follow_target = Some(200.0 + Bird::FRAME_HEIGHT / 2.0);
+        let mut last_y = bird.position.y;
+        for _ in 0..120 {
+            bird.step(1.0 / 60.0, 600.0);
+            assert!(bird.position.y <= last_y);
+            assert!(bird.position.y >= 200.0);
+            last_y = bird.position.y;
+        }
+        assert_float_eq!(bird.position.y, 200.0, abs <= 0.1);
+        assert_float_eq!(bird.velocity.y, 0.0, abs <= 0.001);
+    }
+
+    #[test]
+    fn test_reduced_motion_tumbles_without_rotating() {
+        let mut bird = test_bird();
+        bird.reduced_motion = true;
+        bird.allow_gravity = true;
+        bird.kill(true);
+
+        for _ in 0..10 {
+            bird.step(0.1, 600.0);
+        }
+
+        assert!(bird.position.y > 300.0);
+        assert_float_eq!(bird.rotation, 0.0, abs <= 0.001);
+    }
+
+    #[test]
+    fn test_squash_stretch_returns_to_normal() {
+        let mut bird = test_bird();
+        bird.allow_gravity = true;
+        bird.flap();
+        assert_eq!(bird.squash_scale(), Vec2::ONE, "off by default");
+
+        bird.squash_stretch = true;
+        bird.flap();
+        let stretched = bird.squash_scale();
+        assert!(stretched.y > 1.0 && stretched.x < 1.0);
+
+        bird.step(Bird::SQUASH_DURATION / 2.0, 600.0);
+        let easing = bird.squash_scale();
+        assert!(easing.y > 1.0 && easing.y < stretched.y);
+
+        bird.step(Bird::SQUASH_DURATION, 600.0);
+        assert_eq!(bird.squash_scale(), Vec2::ONE);
+        // The hitbox never changes
+        assert_float_eq!(bird.get_collision_rect().h, Bird::FRAME_HEIGHT, abs <= 0.001);
+
+        // Keep stepping until the climb tops out, which squashes
+        let mut squashed = false;
+        for _ in 0..60 {
+            bird.step(1.0 / 60.0, 600.0);
+            let scale = bird.squash_scale();
+            squashed |= scale.x > 1.0 && scale.y < 1.0;
+        }
+        assert!(squashed);
+        assert_eq!(bird.squash_scale(), Vec2::ONE);
+    }
+}
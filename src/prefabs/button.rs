@@ -1,4 +1,6 @@
 use macroquad::prelude::*;
+use crate::systems::letterbox::{game_height, game_width};
+use crate::systems::resources::resource_path;
 
 pub struct Button {
     texture: Texture2D,
@@ -6,28 +8,31 @@ pub struct Button {
 
 impl Button {
     pub async fn new() -> Self {
-        let texture = load_texture("./resources/start-button.png")
+        let texture = load_texture(&resource_path("start-button.png"))
             .await
             .expect("Could not load button texture");
         
         Button { texture }
     }
 
+    // Bounds of a `size` button centred on a `screen` sized area
+    pub fn centered_rect(size: Vec2, screen: Vec2) -> Rect {
+        let top_left = screen / 2.0 - size / 2.0;
+        Rect::new(top_left.x, top_left.y, size.x, size.y)
+    }
+
+    // Where the button is drawn and clicked, centred on the screen
+    pub fn rect(&self) -> Rect {
+        Self::centered_rect(self.texture.size(), vec2(game_width(), game_height()))
+    }
+
     pub fn contains(&self, point: Vec2) -> bool {
-        let screen_center = vec2(screen_width() / 2.0, screen_height() / 2.0);
-        let button_rect = Rect::new(
-            screen_center.x - self.texture.width() / 2.0,
-            screen_center.y - self.texture.height() / 2.0,
-            self.texture.width(),
-            self.texture.height(),
-        );
-        button_rect.contains(point)
+        self.rect().contains(point)
     }
 
     pub fn draw(&self) {
-        let x = screen_width() / 2.0 - self.texture.width() / 2.0;
-        let y = screen_height() / 2.0 - self.texture.height() / 2.0;
-        draw_texture(&self.texture, x, y, WHITE);
+        let rect = self.rect();
+        draw_texture(&self.texture, rect.x, rect.y, WHITE);
     }
 }
 
@@ -37,6 +42,7 @@ The tests validate :
 1. Button hitbox detection
 2. Centered positioning logic
 3. Edge checks and out-of-bounds checks 
+4. The rect matches the bounds used by contains
 
 */
 
@@ -62,15 +68,15 @@ mod tests {
             }
         }
 
+        fn rect(&self) -> Rect {
+            Button::centered_rect(
+                vec2(self.texture_width, self.texture_height),
+                vec2(self.screen_width, self.screen_height),
+            )
+        }
+
         fn contains(&self, point: Vec2) -> bool {
-            let screen_center = vec2(self.screen_width / 2.0, self.screen_height / 2.0);
-            let button_rect = Rect::new(
-                screen_center.x - self.texture_width / 2.0,
-                screen_center.y - self.texture_height / 2.0,
-                self.texture_width,
-                self.texture_height,
-            );
-            button_rect.contains(point)
+            self.rect().contains(point)
         }
     }
 
@@ -94,4 +100,22 @@ mod tests {
         let edge_point = vec2(400.0 + 50.0, 300.0); // right edge
         assert!(!button.contains(edge_point)); // Rect::contains is exclusive on the right edge
     }
+
+    #[test]
+    fn test_rect_matches_contains_bounds() {
+        let button = TestableButton::new(100.0, 50.0, 800.0, 600.0);
+        let rect = button.rect();
+        assert_eq!(rect, Rect::new(350.0, 275.0, 100.0, 50.0));
+
+        let inside_point = vec2(360.0, 280.0);
+        assert!(rect.contains(inside_point));
+        assert!(button.contains(inside_point));
+
+        // Left edge is inside, right edge is not
+        let left_edge = vec2(rect.left(), 300.0);
+        let right_edge = vec2(rect.right(), 300.0);
+        assert_eq!(button.contains(left_edge), rect.contains(left_edge));
+        assert!(button.contains(left_edge));
+        assert_eq!(button.contains(right_edge), rect.contains(right_edge));
+    }
 }
@@ -0,0 +1,105 @@
+use macroquad::prelude::*;
+use crate::systems::letterbox::{game_height, game_position, game_width};
+
+// Summary - FlapButton :
+// Large translucent button along the bottom of the screen for touch
+// screens, where nothing else hints that tapping flaps. Drawn over the
+// ground so it doesn't cover the playfield.
+pub struct FlapButton {
+    pub enabled: bool,
+}
+
+impl FlapButton {
+    const HEIGHT: f32 = 64.0;
+    // Share of the screen width the button spans
+    const WIDTH_RATIO: f32 = 0.6;
+    // Gap between the button and the bottom of the screen
+    const MARGIN: f32 = 16.0;
+
+    pub fn new(enabled: bool) -> Self {
+        FlapButton { enabled }
+    }
+
+    // Bounds of the button on a `screen` sized game area, centred at the bottom
+    pub fn rect_for(screen: Vec2) -> Rect {
+        let width = screen.x * Self::WIDTH_RATIO;
+        Rect::new(
+            (screen.x - width) / 2.0,
+            screen.y - Self::HEIGHT - Self::MARGIN,
+            width,
+            Self::HEIGHT,
+        )
+    }
+
+    pub fn rect(&self) -> Rect {
+        Self::rect_for(vec2(game_width(), game_height()))
+    }
+
+    pub fn contains(&self, point: Vec2) -> bool {
+        self.enabled && self.rect().contains(point)
+    }
+
+    // A finger that landed on the button this frame. Mouse clicks already
+    // flap anywhere, touches are checked directly so multi-touch works too.
+    pub fn is_tapped(&self) -> bool {
+        touches()
+            .iter()
+            .any(|touch| touch.phase == TouchPhase::Started && self.contains(game_position(touch.position)))
+    }
+
+    pub fn draw(&self, font: &Font) {
+        if !self.enabled {
+            return;
+        }
+        let rect = self.rect();
+        draw_rectangle(rect.x, rect.y, rect.w, rect.h, Color::new(1.0, 1.0, 1.0, 0.25));
+        draw_rectangle_lines(rect.x, rect.y, rect.w, rect.h, 2.0, Color::new(1.0, 1.0, 1.0, 0.6));
+
+        let dims = measure_text("FLAP", Some(font), 24, 1.0);
+        draw_text_ex(
+            "FLAP",
+            rect.center().x - dims.width / 2.0,
+            rect.center().y + dims.offset_y / 2.0,
+            TextParams {
+                font: Some(font),
+                font_size: 24,
+                color: Color::new(1.0, 1.0, 1.0, 0.8),
+                ..Default::default()
+            },
+        );
+    }
+}
+
+/*
+
+The tests validate :
+1. The button sits centred at the bottom of the screen
+2. Points on the button hit it, points above it or beside it do not
+
+*/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn test_rect_at_bottom_of_screen() {
+        let rect = FlapButton::rect_for(vec2(800.0, 600.0));
+        assert_float_eq!(rect.x, 160.0, abs <= 0.001);
+        assert_float_eq!(rect.y, 520.0, abs <= 0.001);
+        assert_float_eq!(rect.w, 480.0, abs <= 0.001);
+        assert_float_eq!(rect.h, 64.0, abs <= 0.001);
+    }
+
+    #[test]
+    fn test_rect_containment() {
+        let rect = FlapButton::rect_for(vec2(800.0, 600.0));
+
+        assert!(rect.contains(vec2(400.0, 550.0)));
+        assert!(rect.contains(vec2(161.0, 521.0)));
+        assert!(!rect.contains(vec2(400.0, 300.0)));
+        assert!(!rect.contains(vec2(100.0, 550.0)));
+        assert!(!rect.contains(vec2(400.0, 595.0)));
+    }
+}
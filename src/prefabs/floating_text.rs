@@ -0,0 +1,152 @@
+use std::collections::VecDeque;
+use macroquad::prelude::*;
+
+// Short-lived text that drifts upwards and fades out (e.g. "+1" bonuses)
+pub struct FloatingText {
+    text: String,
+    position: Vec2,
+    timer: f32,
+    color: Color,
+}
+
+impl FloatingText {
+    const LIFETIME: f32 = 0.8;
+    const RISE_SPEED: f32 = 40.0;
+
+    pub fn new(text: &str, position: Vec2, color: Color) -> Self {
+        FloatingText {
+            text: text.to_string(),
+            position,
+            timer: Self::LIFETIME,
+            color,
+        }
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        self.timer -= dt;
+        self.position.y -= Self::RISE_SPEED * dt;
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.timer > 0.0
+    }
+
+    pub fn alpha(&self) -> f32 {
+        (self.timer / Self::LIFETIME).clamp(0.0, 1.0)
+    }
+
+    pub fn draw(&self, font: &Font) {
+        let mut color = self.color;
+        color.a = self.alpha();
+
+        draw_text_ex(
+            &self.text,
+            self.position.x,
+            self.position.y,
+            TextParams {
+                font: Some(font),
+                font_size: 24,
+                color,
+                ..Default::default()
+            },
+        );
+    }
+}
+
+// Summary - FloatingTexts :
+// The floating texts on screen, oldest first. Holds at most `cap` of them,
+// a new one pushes out the oldest so bursts of events can't pile up.
+pub struct FloatingTexts {
+    texts: VecDeque<FloatingText>,
+    cap: usize,
+}
+
+impl FloatingTexts {
+    pub fn new(cap: usize) -> Self {
+        FloatingTexts {
+            texts: VecDeque::new(),
+            cap,
+        }
+    }
+
+    pub fn push(&mut self, text: FloatingText) {
+        if self.cap == 0 {
+            return;
+        }
+        while self.texts.len() >= self.cap {
+            self.texts.pop_front();
+        }
+        self.texts.push_back(text);
+    }
+
+    // Moves every text along and drops the expired ones
+    pub fn update(&mut self, dt: f32) {
+        for text in &mut self.texts {
+            text.update(dt);
+        }
+        self.texts.retain(|text| text.is_alive());
+    }
+
+    pub fn clear(&mut self) {
+        self.texts.clear();
+    }
+
+    pub fn draw(&self, font: &Font) {
+        for text in &self.texts {
+            text.draw(font);
+        }
+    }
+}
+
+/*
+
+The tests validate :
+1. Text rises over time
+2. Text fades and expires after its lifetime
+3. Pushing past the cap keeps the count at the cap, dropping the oldest first
+
+*/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn test_text_rises() {
+        let mut text = FloatingText::new("+1", vec2(100.0, 200.0), WHITE);
+        text.update(0.5);
+        assert_float_eq!(text.position.y, 200.0 - FloatingText::RISE_SPEED * 0.5, abs <= 0.001);
+    }
+
+    #[test]
+    fn test_text_fades_and_expires() {
+        let mut text = FloatingText::new("+1", vec2(100.0, 200.0), WHITE);
+        assert_float_eq!(text.alpha(), 1.0, abs <= 0.001);
+
+        text.update(FloatingText::LIFETIME / 2.0);
+        assert!(text.is_alive());
+        assert_float_eq!(text.alpha(), 0.5, abs <= 0.001);
+
+        text.update(FloatingText::LIFETIME);
+        assert!(!text.is_alive());
+    }
+
+    #[test]
+    fn test_cap_evicts_oldest() {
+        let mut texts = FloatingTexts::new(3);
+        for i in 0..5 {
+            texts.push(FloatingText::new(&i.to_string(), vec2(0.0, 0.0), WHITE));
+        }
+        assert_eq!(texts.texts.len(), 3);
+        let kept: Vec<&str> = texts.texts.iter().map(|text| text.text.as_str()).collect();
+        assert_eq!(kept, vec!["2", "3", "4"]);
+
+        texts.update(FloatingText::LIFETIME);
+        assert!(texts.texts.is_empty());
+
+        let mut none = FloatingTexts::new(0);
+        none.push(FloatingText::new("+1", vec2(0.0, 0.0), WHITE));
+        assert!(none.texts.is_empty());
+    }
+}
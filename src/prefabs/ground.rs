@@ -1,24 +1,27 @@
 use macroquad::prelude::*;
 
+use crate::systems::layout::{wrap_scroll, Layout};
+use crate::systems::letterbox::{game_height, game_width};
 use crate::systems::physics::{check_collision, PhysicsBody};
-use crate::SCROLL_SPEED;
+use crate::systems::config::Speeds;
+use crate::systems::resources::resource_path;
 
 pub struct Ground {
     texture: Texture2D,
     scroll_pos: f32,
     pub scroll: bool,
+    // Pixels per second, kept in step with the pipes
+    pub speed: f32,
+    // Scales `speed`, for slow motion (1 = normal, 0 = stopped)
+    speed_multiplier: f32,
+    // How far the ground has risen above its usual place, in pixels,
+    // copied from Simulation::ground_rise
+    pub rise_offset: f32,
 }
 
 impl PhysicsBody for Ground {
     fn get_collision_rect(&mut self) -> Rect {
-        let ground_height = self.texture.height();
-
-        Rect::new(
-            0.0,
-            screen_height() - ground_height,
-            screen_width(),
-            ground_height,
-        )
+        Self::collision_rect(vec2(game_width(), game_height()), self.height(), self.rise_offset)
     }
 
     fn collides_with(&mut self, obj: &Rect) -> bool {
@@ -28,7 +31,7 @@ impl PhysicsBody for Ground {
 
 impl Ground {
     pub async fn new() -> Self {
-        let texture = load_texture("./resources/ground.png")
+        let texture = load_texture(&resource_path("ground.png"))
             .await
             .expect("Could not load ground texture");
 
@@ -38,25 +41,58 @@ impl Ground {
             texture,
             scroll_pos: 0.0,
             scroll: true,
+            speed: Speeds::SCROLL,
+            speed_multiplier: 1.0,
+            rise_offset: 0.0,
         }
     }
 
-    pub fn update(&mut self) {
+    // Keeps some room to fly however long the run lasts
+    pub const MAX_RISE: f32 = 200.0;
+    // Bottom colour of ground.png
+    const DIRT_COLOR: Color = Color::new(0.87, 0.85, 0.58, 1.0);
+
+    // Everything from the top of the (raised) ground to the bottom of the screen
+    pub fn collision_rect(screen: Vec2, height: f32, rise_offset: f32) -> Rect {
+        let top = screen.y - height - rise_offset;
+        Rect::new(0.0, top, screen.x, screen.y - top)
+    }
+
+    // Height of the ground strip, taken from its texture
+    pub fn height(&self) -> f32 {
+        self.texture.height()
+    }
+
+    pub fn set_speed_multiplier(&mut self, multiplier: f32) {
+        self.speed_multiplier = multiplier.max(0.0);
+    }
+
+    // `dt` is already scaled by the time scale, so slow motion slows the ground too
+    pub fn update(&mut self, dt: f32) {
         if self.scroll {
-            self.scroll_pos = (self.scroll_pos - SCROLL_SPEED) % self.texture.width();
+            let speed = self.speed * self.speed_multiplier;
+            self.scroll_pos = Self::scrolled(self.scroll_pos, speed, dt, self.texture.width());
         }
     }
 
+    pub fn scrolled(scroll_pos: f32, speed: f32, dt: f32, width: f32) -> f32 {
+        wrap_scroll(scroll_pos - Speeds::distance(speed, dt), width)
+    }
+
     pub fn draw(&self) {
-        let y_pos = screen_height() - self.texture.height();
-        let tex_width = self.texture.width();
+        let layout = Layout::current(self.height());
+        let y_pos = layout.ground_y() - self.rise_offset;
 
-        // Draw five copies for seamless scrolling
-        draw_texture(&self.texture, self.scroll_pos, y_pos, WHITE);
-        draw_texture(&self.texture, self.scroll_pos + 1.0 * tex_width, y_pos, WHITE);
-        draw_texture(&self.texture, self.scroll_pos + 2.0 * tex_width, y_pos, WHITE);
-        draw_texture(&self.texture, self.scroll_pos + 3.0 * tex_width, y_pos, WHITE);
-        draw_texture(&self.texture, self.scroll_pos + 4.0 * tex_width, y_pos, WHITE);
+        // Enough copies to cover the screen for seamless scrolling
+        for x in layout.scrolled_tile_offsets(self.texture.width(), self.scroll_pos) {
+            draw_texture(&self.texture, x, y_pos, WHITE);
+        }
+
+        // Fill the space the ground has risen out of with its dirt colour
+        if self.rise_offset > 0.0 {
+            let fill_y = y_pos + self.height();
+            draw_rectangle(0.0, fill_y, layout.screen.x, layout.screen.y - fill_y, Self::DIRT_COLOR);
+        }
     }
 }
 
@@ -68,6 +104,8 @@ The tests validate :
 3. Correct modulo operation
 4. Collision rectangle calculation
 5. Collision detection logic
+6. Rising moves the real collision rect's top up and still reaches the screen bottom
+7. At 60 FPS the ground scrolls 3 pixels a frame, as it did per frame before
 
 */
 
@@ -75,6 +113,7 @@ The tests validate :
 mod tests {
     use super::*;
     use macroquad::math::Rect;
+    use float_eq::assert_float_eq;
 
     struct DummyGround {
         scroll_pos: f32,
@@ -151,4 +190,29 @@ mod tests {
         let test_obj = Rect::new(0.0, 480.0, 50.0, 50.0); // fully above ground
         assert!(!ground.collides_with(&test_obj, 800.0, 600.0));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_rise_moves_collision_top_up() {
+        let screen = vec2(800.0, 600.0);
+        let resting = Ground::collision_rect(screen, 112.0, 0.0);
+        assert_eq!(resting.y, 488.0);
+        assert_eq!(resting.h, 112.0);
+
+        let raised = Ground::collision_rect(screen, 112.0, 50.0);
+        assert_eq!(raised.y, 438.0);
+        assert_eq!(raised.bottom(), 600.0);
+
+        let higher = Ground::collision_rect(screen, 112.0, 120.0);
+        assert!(higher.y < raised.y);
+    }
+
+    #[test]
+    fn test_scroll_matches_old_per_frame_step() {
+        let scroll_pos = Ground::scrolled(-10.0, Speeds::SCROLL, 1.0 / 60.0, 336.0);
+        assert_float_eq!(scroll_pos, -13.0, abs <= 0.001);
+
+        // Half the frame time, half the distance
+        let scroll_pos = Ground::scrolled(-10.0, Speeds::SCROLL, 1.0 / 120.0, 336.0);
+        assert_float_eq!(scroll_pos, -11.5, abs <= 0.001);
+    }
+}
@@ -1,6 +1,12 @@
 pub mod background;
 pub mod bird;
 pub mod button;
+pub mod flap_button;
+pub mod floating_text;
 pub mod ground;
 pub mod pipes;
-pub mod scoreboard;
\ No newline at end of file
+pub mod powerup;
+pub mod scoreboard;
+pub mod sky;
+pub mod ticker;
+pub mod tutorial;
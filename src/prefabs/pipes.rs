@@ -1,35 +1,87 @@
+use std::collections::VecDeque;
+use std::ops::RangeInclusive;
 use macroquad::prelude::*;
 use ::rand::Rng;
-use crate::systems::physics::{check_collision, PhysicsBody};
-use crate::SCROLL_SPEED;
+use crate::systems::physics::{check_collision, CollisionInfo, CollisionKind, PhysicsBody};
+use crate::systems::resources::resource_path;
+use crate::systems::config::Speeds;
 
+#[derive(Clone)]
 pub struct Pipe {
     position: Vec2,
-    source_rect: Rect,
+    // The cap is at the bottom of the top pipe and the top of the bottom pipe
+    cap_at_bottom: bool,
 }
 
 impl Pipe {
-    fn new(position: Vec2, source_rect: Rect) -> Self {
-        Pipe { position, source_rect }
-    }
-
-    fn draw(&self, group_position: Vec2, texture: &Texture2D) {
-        draw_texture_ex(
-            texture,
-            group_position.x + self.position.x,
-            group_position.y + self.position.y,
-            WHITE,
-            DrawTextureParams {
-                source: Some(self.source_rect),
+    pub const WIDTH: f32 = 54.0;
+    pub const HEIGHT: f32 = 320.0;
+    pub const HALF_WIDTH: f32 = Self::WIDTH / 2.0;
+    // Height of the wider lip at the open end of the pipe in pipes.png
+    const CAP_HEIGHT: f32 = 24.0;
+    // Furthest the cap bobs back into the pipe, in pixels
+    pub const CAP_BOB: f32 = 2.0;
+    // Bob phase in radians per pixel scrolled, a full bob every ~126 pixels
+    const CAP_BOB_FREQUENCY: f32 = 0.05;
+
+    fn new(position: Vec2, cap_at_bottom: bool) -> Self {
+        Pipe { position, cap_at_bottom }
+    }
+
+    // How far the cap is pulled back into the pipe at scroll position `x`.
+    // Only ever inwards, so the drawn pipe never reaches past its hitbox.
+    pub fn cap_offset(x: f32) -> f32 {
+        Self::CAP_BOB * (1.0 - (x * Self::CAP_BOB_FREQUENCY).cos()) / 2.0
+    }
+
+    // `rect` shrunk by `inset` pixels on every side, never below zero size
+    pub fn inset_rect(rect: Rect, inset: f32) -> Rect {
+        let inset = inset.clamp(0.0, rect.w.min(rect.h) / 2.0);
+        Rect::new(rect.x + inset, rect.y + inset, rect.w - inset * 2.0, rect.h - inset * 2.0)
+    }
+
+    // `source` is this pipe's part of `texture`, see PipeTextures::select.
+    // With a `cap_offset`, the shaft and the cap are drawn separately so
+    // the cap can bob. Collision still uses the whole sprite.
+    fn draw(&self, group_position: Vec2, texture: &Texture2D, source: Rect, tint: Color, cap_offset: Option<f32>) {
+        let position = group_position + self.position;
+        let Some(offset) = cap_offset else {
+            draw_texture_ex(texture, position.x, position.y, tint, DrawTextureParams {
+                source: Some(source),
+                ..Default::default()
+            });
+            return;
+        };
+
+        let shaft_height = source.h - Self::CAP_HEIGHT;
+        let (shaft_source, shaft_y, cap_source, cap_y) = if self.cap_at_bottom {
+            (
+                Rect::new(source.x, source.y, source.w, shaft_height),
+                position.y,
+                Rect::new(source.x, source.y + shaft_height, source.w, Self::CAP_HEIGHT),
+                position.y + shaft_height - offset,
+            )
+        } else {
+            (
+                Rect::new(source.x, source.y + Self::CAP_HEIGHT, source.w, shaft_height),
+                position.y + Self::CAP_HEIGHT,
+                Rect::new(source.x, source.y, source.w, Self::CAP_HEIGHT),
+                position.y + offset,
+            )
+        };
+
+        for (source, y) in [(shaft_source, shaft_y), (cap_source, cap_y)] {
+            draw_texture_ex(texture, position.x, y, tint, DrawTextureParams {
+                source: Some(source),
                 ..Default::default()
-            },
-        );
+            });
+        }
     }
 }
 
 impl PhysicsBody for Pipe {
     fn get_collision_rect(&mut self) -> Rect {
-        Rect::new(self.position.x, self.position.y, 54.0, 320.0)
+        Rect::new(self.position.x, self.position.y, Self::WIDTH, Self::HEIGHT)
     }
 
     fn collides_with(&mut self, obj: &Rect) -> bool {
@@ -37,6 +89,66 @@ impl PhysicsBody for Pipe {
     }
 }
 
+// Summary - PipeTextures :
+// Where the pipe sprites come from. By default both share pipes.png, the
+// top pipe on its left half and the bottom pipe on its right. Themed
+// levels can give each its own texture, one pipe the size of a sprite,
+// cap at the same end as in the sheet. Generic so the choice can be
+// tested without a graphics context.
+pub enum PipeTextures<T = Texture2D> {
+    Sheet(T),
+    Separate { top: T, bottom: T },
+}
+
+impl<T> PipeTextures<T> {
+    // Texture and source rect to draw the top or bottom pipe with
+    pub fn select(&self, top: bool) -> (&T, Rect) {
+        let sprite = Rect::new(0.0, 0.0, Pipe::WIDTH, Pipe::HEIGHT);
+        match self {
+            PipeTextures::Sheet(sheet) if top => (sheet, sprite),
+            PipeTextures::Sheet(sheet) => (sheet, Rect { x: Pipe::WIDTH, ..sprite }),
+            PipeTextures::Separate { top: texture, .. } if top => (texture, sprite),
+            PipeTextures::Separate { bottom, .. } => (bottom, sprite),
+        }
+    }
+}
+
+impl PipeTextures {
+    // Separate textures when pipe-top.png and pipe-bottom.png are both
+    // available (e.g. in a resource pack), otherwise the shared sheet
+    pub async fn load() -> Self {
+        let top = load_texture(&resource_path("pipe-top.png")).await;
+        let bottom = load_texture(&resource_path("pipe-bottom.png")).await;
+        match (top, bottom) {
+            (Ok(top), Ok(bottom)) => PipeTextures::Separate { top, bottom },
+            _ => PipeTextures::Sheet(
+                load_texture(&resource_path("pipes.png"))
+                    .await
+                    .expect("Failed to load pipes texture"),
+            ),
+        }
+    }
+}
+
+// Which pipes a group has. Single pipes only turn up when the config asks for them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PipeVariant {
+    BothPipes,
+    TopOnly,
+    BottomOnly,
+}
+
+impl PipeVariant {
+    pub fn has_top(&self) -> bool {
+        *self != PipeVariant::BottomOnly
+    }
+
+    pub fn has_bottom(&self) -> bool {
+        *self != PipeVariant::TopOnly
+    }
+}
+
+#[derive(Clone)]
 pub struct PipeGroup {
     top_pipe: Pipe,
     bottom_pipe: Pipe,
@@ -44,66 +156,211 @@ pub struct PipeGroup {
     pub alive: bool,
     pub enabled: bool,
     pub has_scored: bool,
+    // Set once the bird has flown close by, so the whoosh plays once per group
+    pub whooshed: bool,
+    // Pixels per second this group moves left, picked in reset
+    pub scroll_speed: f32,
+    pub variant: PipeVariant,
+    // Pixels the hitboxes are shrunk by, so grazing the sprite's lip doesn't kill
+    pub collision_inset: f32,
 }
 
 impl PipeGroup {
-    const GAP_SIZE: f32 = 160.0;
-    const PIPE_HEIGHT: f32 = 320.0;
+    pub const GAP_SIZE: f32 = 160.0;
+    // Score at which per-group speed variation reaches its full range
+    pub const FULL_VARIATION_SCORE: i32 = 20;
+    // Narrowest space allowed between two groups, room for the bird to fly
+    const MIN_SEPARATION: f32 = Pipe::WIDTH * 2.0;
+    // Passing this many pixels from a pipe plays the whoosh
+    pub const WHOOSH_BAND: f32 = 12.0;
 
     pub fn new() -> Self {
         PipeGroup {
             position: Vec2::new(0.0, 0.0),
-            top_pipe: Pipe::new(
-                Vec2::new(0.0, 0.0),
-                Rect::new(0.0, 0.0, 54.0, 320.0),
-            ),
-            bottom_pipe: Pipe::new(
-                Vec2::new(0.0, 0.0),
-                Rect::new(54.0, 0.0, 54.0, 320.0),
-            ),
+            top_pipe: Pipe::new(Vec2::new(0.0, 0.0), true),
+            bottom_pipe: Pipe::new(Vec2::new(0.0, 0.0), false),
             alive: false,
             enabled: false,
             has_scored: false,
+            whooshed: false,
+            scroll_speed: Speeds::SCROLL,
+            variant: PipeVariant::BothPipes,
+            collision_inset: 0.0,
         }
     }
 
-    pub fn update(&mut self) {
+    pub fn update(&mut self, dt: f32) {
         if self.alive && self.enabled {
-            self.position.x -= SCROLL_SPEED;
+            self.position.x -= Speeds::distance(self.scroll_speed, dt);
         }
-        if self.position.x < -54.0 {
+        self.retire_if_behind(0.0);
+    }
+
+    // Frees the group for reuse once it is fully left of `left_edge`
+    pub fn retire_if_behind(&mut self, left_edge: f32) {
+        if self.position.x < left_edge - Pipe::WIDTH {
             self.alive = false;
             self.enabled = false;
         }
     }
 
-    pub fn draw(&self, texture: &Texture2D) {
-        self.top_pipe.draw(self.position, texture);
-        self.bottom_pipe.draw(self.position, texture);
+    // `tint` multiplies the sprite colour, WHITE draws it unchanged.
+    // `bob_caps` animates the pipe caps as the group scrolls.
+    pub fn draw(&self, textures: &PipeTextures, tint: Color, bob_caps: bool) {
+        let cap_offset = bob_caps.then(|| Pipe::cap_offset(self.position.x));
+        if self.variant.has_top() {
+            let (texture, source) = textures.select(true);
+            self.top_pipe.draw(self.position, texture, source, tint, cap_offset);
+        }
+        if self.variant.has_bottom() {
+            let (texture, source) = textures.select(false);
+            self.bottom_pipe.draw(self.position, texture, source, tint, cap_offset);
+        }
     }
 
-    pub fn reset(&mut self, x: f32, ground_y: f32) {
-        let mut rng = ::rand::rng();
-        
+    // Pulls a 0..1 roll towards 0.5, triangular at `bias` 1 and uniform at 0
+    pub fn center_biased(gap_roll: f32, bias: f32) -> f32 {
+        let roll = gap_roll.clamp(0.0, 1.0);
+        // Inverse of the triangular distribution's CDF
+        let triangular = if roll < 0.5 {
+            (roll / 2.0).sqrt()
+        } else {
+            1.0 - ((1.0 - roll) / 2.0).sqrt()
+        };
+        roll + (triangular - roll) * bias.clamp(0.0, 1.0)
+    }
+
+    // Top of the gap for a roll in 0..1, see PipeGenerator::next_gap
+    pub fn gap_top(gap_roll: f32, ground_y: f32, gap_size: f32) -> f32 {
         // Calculate valid gap range
         let min_gap_top = 100.0;
-        let max_gap_top = ground_y - Self::GAP_SIZE - 100.0; // Leave space at bottom
-        
+        let max_gap_top = ground_y - gap_size - 100.0; // Leave space at bottom
+
         // Ensure valid range
-        let gap_top = if max_gap_top > min_gap_top {
-            rng.random_range(min_gap_top..max_gap_top)
+        if max_gap_top > min_gap_top {
+            min_gap_top + gap_roll * (max_gap_top - min_gap_top)
         } else {
             min_gap_top
-        };
+        }
+    }
 
+    // The group's speed is rolled from `speeds`, see speed_range, and with
+    // `single_pipe_chance` it has only a top or only a bottom pipe
+    pub fn reset(
+        &mut self,
+        x: f32,
+        gap_top: f32,
+        gap_size: f32,
+        speeds: RangeInclusive<f32>,
+        single_pipe_chance: f32,
+        rng: &mut impl Rng,
+    ) {
         self.position.x = x;
         self.position.y = 0.0; // Reset y position
-        self.top_pipe.position.y = gap_top - Self::PIPE_HEIGHT;
-        self.bottom_pipe.position.y = gap_top + Self::GAP_SIZE;
+        self.top_pipe.position.y = gap_top - Pipe::HEIGHT;
+        self.bottom_pipe.position.y = gap_top + gap_size;
         
         self.alive = true;
         self.enabled = true;
         self.has_scored = false;
+        self.whooshed = false;
+        // A fixed speed doesn't touch the rng, so seeded layouts stay the same
+        self.scroll_speed = if speeds.start() < speeds.end() {
+            rng.random_range(speeds)
+        } else {
+            *speeds.start()
+        };
+        self.variant = Self::roll_variant(single_pipe_chance, rng);
+    }
+
+    // Leaves the rng alone when single pipes are off, like the speed roll
+    pub fn roll_variant(single_pipe_chance: f32, rng: &mut impl Rng) -> PipeVariant {
+        if single_pipe_chance <= 0.0 || rng.random::<f32>() >= single_pipe_chance {
+            PipeVariant::BothPipes
+        } else if rng.random_bool(0.5) {
+            PipeVariant::TopOnly
+        } else {
+            PipeVariant::BottomOnly
+        }
+    }
+
+    // Speeds a new group may get: `base` give or take `variation` (a
+    // fraction of it), widening from nothing at 0 points to the full range
+    // at FULL_VARIATION_SCORE
+    pub fn speed_range(base: f32, variation: f32, score: i32) -> RangeInclusive<f32> {
+        let ramp = (score as f32 / Self::FULL_VARIATION_SCORE as f32).clamp(0.0, 1.0);
+        let spread = base * variation.max(0.0) * ramp;
+        (base - spread).max(0.0)..=base + spread
+    }
+
+    // Fastest a group spawned at `x` may go without closing in on the group
+    // ahead of it (at `ahead_x`, moving `ahead_speed`) before that one leaves
+    // the screen
+    pub fn catch_up_limit(x: f32, ahead_x: f32, ahead_speed: f32) -> f32 {
+        let time_left = (ahead_x + Pipe::WIDTH) / ahead_speed;
+        if !(time_left > 0.0 && time_left.is_finite()) {
+            return f32::INFINITY;
+        }
+        let room = x - (ahead_x + Pipe::WIDTH) - Self::MIN_SEPARATION;
+        ahead_speed + room.max(0.0) / time_left
+    }
+
+    // Which pipe `obj` hits, top pipe first, with the overlap in world space
+    pub fn collision(&self, obj: &Rect) -> Option<CollisionInfo> {
+        self.present_pipes().find_map(|(kind, rect)| CollisionInfo::between(kind, &rect, obj))
+    }
+
+    // Within `band` pixels of one of the pipes without touching it
+    pub fn is_near(&self, obj: &Rect, band: f32) -> bool {
+        let grown = Rect::new(obj.x - band, obj.y - band, obj.w + band * 2.0, obj.h + band * 2.0);
+        self.collision(obj).is_none() && self.collision(&grown).is_some()
+    }
+
+    // True the first time `obj` comes within WHOOSH_BAND, then never again
+    // until the group is reset
+    pub fn check_whoosh(&mut self, obj: &Rect) -> bool {
+        if self.whooshed || !self.is_near(obj, Self::WHOOSH_BAND) {
+            return false;
+        }
+        self.whooshed = true;
+        true
+    }
+
+    // World space hitboxes of the pipes this group has
+    pub fn hitboxes(&self) -> Vec<Rect> {
+        self.present_pipes().map(|(_, rect)| rect).collect()
+    }
+
+    fn present_pipes(&self) -> impl Iterator<Item = (CollisionKind, Rect)> {
+        let [top, bottom] = self.pipe_rects().map(|rect| Pipe::inset_rect(rect, self.collision_inset));
+        [
+            (CollisionKind::TopPipe, top, self.variant.has_top()),
+            (CollisionKind::BottomPipe, bottom, self.variant.has_bottom()),
+        ]
+        .into_iter()
+        .filter_map(|(kind, rect, present)| present.then_some((kind, rect)))
+    }
+
+    // World space rects of the top and bottom pipe. Pipes and bird are
+    // both drawn from their top-left corner, so a pipe's hitbox is just its
+    // local rect moved by the group position. Subtracting half a pipe width
+    // and half a bird height as well (the old -27/-12) only fits
+    // centre-anchored sprites and put the hitbox up and left of the bird.
+    fn pipe_rects(&self) -> [Rect; 2] {
+        [&self.top_pipe, &self.bottom_pipe].map(|pipe| {
+            Rect::new(
+                self.position.x + pipe.position.x,
+                self.position.y + pipe.position.y,
+                Pipe::WIDTH,
+                Pipe::HEIGHT,
+            )
+        })
+    }
+
+    // Vertical centre of the gap between the top and bottom pipe
+    pub fn gap_center(&self) -> f32 {
+        let gap_top = self.top_pipe.position.y + Pipe::HEIGHT;
+        self.position.y + (gap_top + self.bottom_pipe.position.y) / 2.0
     }
 }
 
@@ -113,30 +370,65 @@ impl PhysicsBody for PipeGroup {
     }
 
     fn collides_with(&mut self, obj: &Rect) -> bool {
-        let relative_rect = Rect::new(
-            obj.x - self.position.x - 27.0,
-            obj.y - self.position.y - 12.0,
-            obj.w,
-            obj.h,
-        );
-        self.top_pipe.collides_with(&relative_rect)
-            || self.bottom_pipe.collides_with(&relative_rect)
+        self.collision(obj).is_some()
     }
 }
 
+#[derive(Clone)]
 pub struct PipeGenerator {
     counter: i32,
+    interval: i32,
     enabled: bool,
+    // Gap rolls for the next groups to spawn, decided ahead of time so
+    // they can be previewed. See PipeGroup::gap_top.
+    upcoming_gaps: VecDeque<f32>,
 }
 
 impl PipeGenerator {
+    const DEFAULT_INTERVAL: i32 = 80;
+    pub const QUEUED_GAPS: usize = 2;
+
     pub fn new() -> Self {
         PipeGenerator {
             counter: 0,
+            interval: Self::DEFAULT_INTERVAL,
             enabled: false,
+            upcoming_gaps: VecDeque::new(),
+        }
+    }
+
+    pub fn fill_gaps(&mut self, rng: &mut impl Rng) {
+        while self.upcoming_gaps.len() < Self::QUEUED_GAPS {
+            self.upcoming_gaps.push_back(rng.random());
         }
     }
 
+    // Takes the gap roll for the group being spawned and queues another
+    pub fn next_gap(&mut self, rng: &mut impl Rng) -> f32 {
+        self.fill_gaps(rng);
+        let gap_roll = self.upcoming_gaps.pop_front().unwrap_or(0.5);
+        self.fill_gaps(rng);
+        gap_roll
+    }
+
+    // Soonest first
+    pub fn upcoming_gaps(&self) -> impl Iterator<Item = f32> + '_ {
+        self.upcoming_gaps.iter().copied()
+    }
+
+    // World steps between spawns so that pipe groups end up `spacing` pixels
+    // apart when each step scrolls `step_distance` pixels
+    pub fn interval_for_spacing(spacing: f32, step_distance: f32) -> i32 {
+        if step_distance <= 0.0 {
+            return Self::DEFAULT_INTERVAL;
+        }
+        ((spacing / step_distance).round() as i32).max(1)
+    }
+
+    pub fn set_spacing(&mut self, spacing: f32, step_distance: f32) {
+        self.interval = Self::interval_for_spacing(spacing, step_distance);
+    }
+
     pub fn start(&mut self) {
         self.enabled = true;
     }
@@ -148,7 +440,7 @@ impl PipeGenerator {
     pub fn should_spawn_pipe(&mut self) -> bool {
         if self.enabled {
             self.counter += 1;
-            if self.counter >= 80 {
+            if self.counter >= self.interval {
                 self.counter = 0;
                 return true;
             }
@@ -160,11 +452,26 @@ impl PipeGenerator {
 /*
 
 The tests validate :
-1. PipeGroup position updates
+1. PipeGroup position updates, 3 pixels a frame at 60 FPS like the old per-frame speed
 2. PipeGroup deactivation logic
 3. PipeGroup reset logic
 4. Collision detection delegation
 5. PipeGenerator spawn logic
+6. PipeGroup reset honours the requested gap size
+7. Seeded generators queue identical gaps and hand them out in order
+8. Gap centre lies midway between the pipes
+9. Spawn interval derived from the pipe spacing and scroll speed
+10. Bird hitbox matches the drawn sprites: grazing either pipe edge collides,
+    flying inside the gap or alongside the pipes does not
+11. A faster group moves further per update
+12. Speed variation ramps up with the score and never lets a group catch the one ahead
+13. Single-pipe variants only collide with and keep the hitbox of the pipe they have
+14. The collision inset shrinks the hitboxes on every side, the sprite rects stay put
+15. The cap bob stays between resting and CAP_BOB pixels into the pipe
+16. The whoosh flag is set once when the bird enters the band around a pipe, not on contact
+17. Each pipe draws from the sheet half or its own texture, depending on the textures configured
+18. Centre bias keeps gaps closer to the middle on average, and none leaves rolls as they are
+19. PipeGroup reset rolls its speed from the given range, a fixed speed is kept exactly
 
 */
 
@@ -173,6 +480,12 @@ mod tests {
     use super::*;
     use macroquad::prelude::Rect;
     use float_eq::assert_float_eq;
+    use ::rand::rngs::StdRng;
+    use ::rand::SeedableRng;
+    use crate::prefabs::bird::Bird;
+
+    // One frame at 60 FPS
+    const FRAME: f32 = 1.0 / 60.0;
 
     fn test_pipe_group() -> PipeGroup {
         PipeGroup::new()
@@ -185,9 +498,10 @@ mod tests {
         group.enabled = true;
         group.position.x = 100.0;
 
-        group.update();
+        group.update(FRAME);
 
-        assert_float_eq!(group.position.x, 100.0 - SCROLL_SPEED, abs <= 0.001);
+        // 3 pixels per frame at 60 FPS, as when speeds were per frame
+        assert_float_eq!(group.position.x, 97.0, abs <= 0.001);
     }
 
     #[test]
@@ -197,7 +511,7 @@ mod tests {
         group.enabled = true;
         group.position.x = -54.1;
 
-        group.update();
+        group.update(FRAME);
 
         assert!(!group.alive);
         assert!(!group.enabled);
@@ -209,7 +523,7 @@ mod tests {
         let x = 300.0;
         let ground_y = 600.0;
 
-        group.reset(x, ground_y);
+        group.reset(x, PipeGroup::gap_top(0.5, ground_y, PipeGroup::GAP_SIZE), PipeGroup::GAP_SIZE, Speeds::SCROLL..=Speeds::SCROLL, 0.0, &mut ::rand::rng());
 
         assert!(group.top_pipe.position.y < 0.0); // should be above gap
         assert!(group.bottom_pipe.position.y > 0.0); // should be below gap
@@ -219,6 +533,51 @@ mod tests {
         assert_float_eq!(group.position.x, x, abs <= 0.001);
     }
 
+    #[test]
+    fn test_pipe_group_reset_uses_given_gap_size() {
+        let mut group = test_pipe_group();
+        let gap_size = PipeGroup::GAP_SIZE + 40.0;
+
+        group.reset(300.0, 220.0, gap_size, Speeds::SCROLL..=Speeds::SCROLL, 0.0, &mut ::rand::rng());
+
+        let gap_top = group.top_pipe.position.y + Pipe::HEIGHT;
+        assert_float_eq!(group.bottom_pipe.position.y - gap_top, gap_size, abs <= 0.001);
+    }
+
+    #[test]
+    fn test_gap_queue_is_deterministic_for_seed() {
+        let mut first = PipeGenerator::new();
+        let mut second = PipeGenerator::new();
+        first.fill_gaps(&mut StdRng::seed_from_u64(7));
+        second.fill_gaps(&mut StdRng::seed_from_u64(7));
+
+        assert_eq!(first.upcoming_gaps().count(), PipeGenerator::QUEUED_GAPS);
+        assert!(first.upcoming_gaps().eq(second.upcoming_gaps()));
+    }
+
+    #[test]
+    fn test_next_gap_follows_queue() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut generator = PipeGenerator::new();
+        generator.fill_gaps(&mut rng);
+        let previewed: Vec<f32> = generator.upcoming_gaps().collect();
+
+        for gap_roll in previewed {
+            assert_eq!(generator.next_gap(&mut rng), gap_roll);
+        }
+        // Still topped up for the next previews
+        assert_eq!(generator.upcoming_gaps().count(), PipeGenerator::QUEUED_GAPS);
+    }
+
+    #[test]
+    fn test_gap_center_is_midway_between_pipes() {
+        let mut group = test_pipe_group();
+        group.top_pipe.position.y = 100.0 - Pipe::HEIGHT;
+        group.bottom_pipe.position.y = 260.0;
+
+        assert_float_eq!(group.gap_center(), 180.0, abs <= 0.001);
+    }
+
     #[test]
     fn test_pipe_group_collision_calls_both_pipes() {
         let mut group = test_pipe_group();
@@ -227,9 +586,9 @@ mod tests {
         group.position = Vec2::new(0.0, 0.0);
 
         let _hitbox = Rect::new(0.0, 0.0, 54.0, 320.0);
-        let mut obj = Rect::new(27.0, 12.0, 20.0, 20.0); // Matches relative offset logic
+        let obj = Rect::new(27.0, 12.0, 20.0, 20.0); // Matches relative offset logic
 
-        assert!(group.collides_with(&mut obj));
+        assert!(group.collides_with(&obj));
     }
 
     #[test]
@@ -256,4 +615,267 @@ mod tests {
             assert!(!generator.should_spawn_pipe());
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_interval_produces_requested_spacing() {
+        let spacing = 300.0;
+        let scroll_speed = 5.0;
+
+        let interval = PipeGenerator::interval_for_spacing(spacing, scroll_speed);
+        assert_eq!(interval, 60);
+        assert_float_eq!(interval as f32 * scroll_speed, spacing, abs <= 0.001);
+
+        // The default spacing reproduces the original 80 frame interval
+        assert_eq!(PipeGenerator::interval_for_spacing(240.0, Speeds::distance(Speeds::SCROLL, FRAME)), 80);
+    }
+
+    #[test]
+    fn test_set_spacing_changes_spawn_rate() {
+        let mut generator = PipeGenerator::new();
+        generator.set_spacing(120.0, Speeds::distance(Speeds::SCROLL, FRAME));
+        generator.start();
+
+        let spawns = (0..80).filter(|_| generator.should_spawn_pipe()).count();
+        assert_eq!(spawns, 2);
+    }
+
+    // Pipe group at x = 200 with the gap spanning y = 200..360
+    fn pipe_group_with_gap() -> PipeGroup {
+        let mut group = test_pipe_group();
+        group.position = Vec2::new(200.0, 0.0);
+        group.top_pipe.position.y = 200.0 - Pipe::HEIGHT;
+        group.bottom_pipe.position.y = 200.0 + PipeGroup::GAP_SIZE;
+        group
+    }
+
+    fn bird_rect(x: f32, y: f32) -> Rect {
+        Rect::new(x, y, Bird::FRAME_WIDTH, Bird::FRAME_HEIGHT)
+    }
+
+    #[test]
+    fn test_bird_grazing_top_pipe_lower_edge() {
+        let mut group = pipe_group_with_gap();
+
+        assert!(group.collides_with(&bird_rect(210.0, 199.0)));
+        assert!(!group.collides_with(&bird_rect(210.0, 200.0)));
+    }
+
+    #[test]
+    fn test_bird_grazing_bottom_pipe_upper_edge() {
+        let mut group = pipe_group_with_gap();
+        let bottom_edge = 200.0 + PipeGroup::GAP_SIZE;
+
+        assert!(group.collides_with(&bird_rect(210.0, bottom_edge - Bird::FRAME_HEIGHT + 1.0)));
+        assert!(!group.collides_with(&bird_rect(210.0, bottom_edge - Bird::FRAME_HEIGHT)));
+    }
+
+    #[test]
+    fn test_bird_inside_gap_does_not_collide() {
+        let mut group = pipe_group_with_gap();
+        let gap_center = group.gap_center();
+
+        for x in [170.0, 200.0, 230.0, 250.0] {
+            let rect = bird_rect(x, gap_center - Bird::FRAME_HEIGHT / 2.0);
+            assert!(!group.collides_with(&rect), "bird at x = {} should fit the gap", x);
+        }
+    }
+
+    #[test]
+    fn test_bird_beside_pipes_matches_sprite_edges() {
+        let mut group = pipe_group_with_gap();
+
+        // Level with the top pipe: touching its left or right edge is a miss,
+        // one pixel of overlap is a hit
+        assert!(!group.collides_with(&bird_rect(200.0 - Bird::FRAME_WIDTH, 100.0)));
+        assert!(group.collides_with(&bird_rect(201.0 - Bird::FRAME_WIDTH, 100.0)));
+        assert!(!group.collides_with(&bird_rect(200.0 + Pipe::WIDTH, 100.0)));
+        assert!(group.collides_with(&bird_rect(199.0 + Pipe::WIDTH, 100.0)));
+    }
+
+    #[test]
+    fn test_faster_group_moves_further() {
+        let mut slow = test_pipe_group();
+        let mut fast = test_pipe_group();
+        slow.reset(300.0, 220.0, PipeGroup::GAP_SIZE, 180.0..=180.0, 0.0, &mut StdRng::seed_from_u64(1));
+        fast.reset(300.0, 220.0, PipeGroup::GAP_SIZE, 240.0..=240.0, 0.0, &mut StdRng::seed_from_u64(1));
+
+        slow.update(FRAME);
+        fast.update(FRAME);
+
+        assert_float_eq!(slow.position.x, 297.0, abs <= 0.001);
+        assert_float_eq!(fast.position.x, 296.0, abs <= 0.001);
+        assert!(fast.position.x < slow.position.x);
+    }
+
+    #[test]
+    fn test_speed_range_and_catch_up_limit() {
+        let start = PipeGroup::speed_range(3.0, 0.2, 0);
+        assert_float_eq!(*start.start(), 3.0, abs <= 0.001);
+        assert_float_eq!(*start.end(), 3.0, abs <= 0.001);
+
+        let half = PipeGroup::speed_range(3.0, 0.2, PipeGroup::FULL_VARIATION_SCORE / 2);
+        assert_float_eq!(*half.start(), 2.7, abs <= 0.001);
+        assert_float_eq!(*half.end(), 3.3, abs <= 0.001);
+
+        let full = PipeGroup::speed_range(3.0, 0.2, 1000);
+        assert_float_eq!(*full.end(), 3.6, abs <= 0.001);
+
+        // Catching up at the limit closes the gap to exactly MIN_SEPARATION
+        // as the group ahead leaves the screen
+        let (x, ahead_x, ahead_speed) = (800.0, 500.0, 3.0);
+        let limit = PipeGroup::catch_up_limit(x, ahead_x, ahead_speed);
+        let frames = (ahead_x + Pipe::WIDTH) / ahead_speed;
+        let gap = (x - limit * frames) - (ahead_x - ahead_speed * frames + Pipe::WIDTH);
+        assert_float_eq!(gap, PipeGroup::MIN_SEPARATION, abs <= 0.01);
+
+        // Already too close: no faster than the group ahead
+        assert_float_eq!(PipeGroup::catch_up_limit(560.0, 500.0, 3.0), 3.0, abs <= 0.001);
+    }
+
+    #[test]
+    fn test_single_pipe_variants() {
+        let mut group = test_pipe_group();
+        group.reset(300.0, 220.0, PipeGroup::GAP_SIZE, Speeds::SCROLL..=Speeds::SCROLL, 0.0, &mut StdRng::seed_from_u64(3));
+        let [top, bottom] = group.pipe_rects();
+        let in_top = Rect::new(top.x + 10.0, top.bottom() - 20.0, 20.0, 10.0);
+        let in_bottom = Rect::new(bottom.x + 10.0, bottom.y + 10.0, 20.0, 10.0);
+
+        group.variant = PipeVariant::TopOnly;
+        assert!(group.collision(&in_bottom).is_none());
+        assert_eq!(group.collision(&in_top).unwrap().kind, CollisionKind::TopPipe);
+        assert_eq!(group.hitboxes(), vec![top]);
+
+        group.variant = PipeVariant::BottomOnly;
+        assert!(group.collision(&in_top).is_none());
+        assert_eq!(group.collision(&in_bottom).unwrap().kind, CollisionKind::BottomPipe);
+
+        group.variant = PipeVariant::BothPipes;
+        assert_eq!(group.hitboxes().len(), 2);
+    }
+
+    #[test]
+    fn test_roll_variant() {
+        let mut rng = StdRng::seed_from_u64(5);
+        for _ in 0..50 {
+            assert_eq!(PipeGroup::roll_variant(0.0, &mut rng), PipeVariant::BothPipes);
+        }
+        let always: Vec<PipeVariant> = (0..50).map(|_| PipeGroup::roll_variant(1.0, &mut rng)).collect();
+        assert!(!always.contains(&PipeVariant::BothPipes));
+        assert!(always.contains(&PipeVariant::TopOnly));
+        assert!(always.contains(&PipeVariant::BottomOnly));
+    }
+
+    #[test]
+    fn test_collision_inset_shrinks_hitboxes() {
+        let mut group = test_pipe_group();
+        group.reset(300.0, 220.0, PipeGroup::GAP_SIZE, Speeds::SCROLL..=Speeds::SCROLL, 0.0, &mut StdRng::seed_from_u64(3));
+        let [top, bottom] = group.pipe_rects();
+
+        group.collision_inset = 3.0;
+        let hitboxes = group.hitboxes();
+        for (hitbox, sprite) in hitboxes.iter().zip([top, bottom]) {
+            assert_float_eq!(hitbox.x, sprite.x + 3.0, abs <= 0.001);
+            assert_float_eq!(hitbox.y, sprite.y + 3.0, abs <= 0.001);
+            assert_float_eq!(hitbox.w, Pipe::WIDTH - 6.0, abs <= 0.001);
+            assert_float_eq!(hitbox.h, Pipe::HEIGHT - 6.0, abs <= 0.001);
+        }
+        assert_eq!(group.pipe_rects(), [top, bottom]);
+
+        // A bird just inside the bottom pipe's sprite edge now slips past
+        let grazing = Rect::new(bottom.x - 20.0, bottom.y - 10.0, 21.0, 11.0);
+        assert!(grazing.overlaps(&bottom));
+        assert!(group.collision(&grazing).is_none());
+
+        assert_eq!(Pipe::inset_rect(Rect::new(0.0, 0.0, 10.0, 4.0), 5.0).h, 0.0);
+    }
+
+    #[test]
+    fn test_cap_offset_stays_in_bounds() {
+        assert_float_eq!(Pipe::cap_offset(0.0), 0.0, abs <= 0.001);
+
+        let offsets: Vec<f32> = (0..2000).map(|step| Pipe::cap_offset(800.0 - step as f32 * 0.7)).collect();
+        assert!(offsets.iter().all(|offset| (0.0..=Pipe::CAP_BOB).contains(offset)));
+
+        // It actually moves through most of its range
+        let max = offsets.iter().cloned().fold(0.0, f32::max);
+        assert!(max > Pipe::CAP_BOB * 0.9);
+    }
+
+    #[test]
+    fn test_whoosh_once_in_proximity_band() {
+        // Gap spans y = 200..360
+        let mut group = pipe_group_with_gap();
+
+        // Middle of the gap, well clear of both pipes
+        assert!(!group.check_whoosh(&bird_rect(210.0, 268.0)));
+        assert!(!group.whooshed);
+
+        // Touching the top pipe is a hit, not a whoosh
+        assert!(!group.check_whoosh(&bird_rect(210.0, 195.0)));
+        assert!(!group.whooshed);
+
+        // A few pixels below the top pipe
+        assert!(group.check_whoosh(&bird_rect(210.0, 205.0)));
+        assert!(group.whooshed);
+        assert!(!group.check_whoosh(&bird_rect(210.0, 205.0)));
+        assert!(!group.check_whoosh(&bird_rect(210.0, 330.0)));
+    }
+
+    #[test]
+    fn test_pipe_textures_select_source() {
+        let sheet = PipeTextures::Sheet("pipes.png");
+        let (texture, source) = sheet.select(true);
+        assert_eq!(*texture, "pipes.png");
+        assert_eq!(source, Rect::new(0.0, 0.0, Pipe::WIDTH, Pipe::HEIGHT));
+        let (texture, source) = sheet.select(false);
+        assert_eq!(*texture, "pipes.png");
+        assert_eq!(source, Rect::new(Pipe::WIDTH, 0.0, Pipe::WIDTH, Pipe::HEIGHT));
+
+        // Separate textures are drawn whole, each for its own pipe
+        let separate = PipeTextures::Separate { top: "pipe-top.png", bottom: "pipe-bottom.png" };
+        let (texture, source) = separate.select(true);
+        assert_eq!(*texture, "pipe-top.png");
+        assert_eq!(source, Rect::new(0.0, 0.0, Pipe::WIDTH, Pipe::HEIGHT));
+        let (texture, source) = separate.select(false);
+        assert_eq!(*texture, "pipe-bottom.png");
+        assert_eq!(source, Rect::new(0.0, 0.0, Pipe::WIDTH, Pipe::HEIGHT));
+    }
+
+    #[test]
+    fn test_center_bias_pulls_gaps_to_middle() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let rolls: Vec<f32> = (0..10_000).map(|_| rng.random()).collect();
+        let mean_offset = |bias: f32| {
+            rolls.iter().map(|roll| (PipeGroup::center_biased(*roll, bias) - 0.5).abs()).sum::<f32>() / rolls.len() as f32
+        };
+
+        // Uniform rolls are 0.25 from the middle on average, triangular ones 1/6
+        assert_float_eq!(mean_offset(0.0), 0.25, abs <= 0.01);
+        assert_float_eq!(mean_offset(1.0), 1.0 / 6.0, abs <= 0.01);
+        assert!(mean_offset(0.5) < mean_offset(0.0));
+        assert!(mean_offset(1.0) < mean_offset(0.5));
+
+        for roll in [0.0, 0.2, 0.5, 0.9, 1.0] {
+            assert_float_eq!(PipeGroup::center_biased(roll, 0.0), roll, abs <= 0.0001);
+            assert!((0.0..=1.0).contains(&PipeGroup::center_biased(roll, 1.0)));
+        }
+    }
+
+    #[test]
+    fn test_pipe_group_reset_rolls_speed_in_range() {
+        let mut group = test_pipe_group();
+        let mut rng = StdRng::seed_from_u64(5);
+        let speeds = 150.0..=210.0;
+
+        let mut rolled = Vec::new();
+        for _ in 0..20 {
+            group.reset(300.0, 220.0, PipeGroup::GAP_SIZE, speeds.clone(), 0.0, &mut rng);
+            assert!(speeds.contains(&group.scroll_speed));
+            rolled.push(group.scroll_speed);
+        }
+        assert!(rolled.iter().any(|speed| *speed != rolled[0]));
+
+        group.reset(300.0, 220.0, PipeGroup::GAP_SIZE, Speeds::SCROLL..=Speeds::SCROLL, 0.0, &mut rng);
+        assert_eq!(group.scroll_speed, Speeds::SCROLL);
+    }
+}
@@ -0,0 +1,148 @@
+use macroquad::prelude::*;
+use crate::systems::physics::{check_collision, PhysicsBody};
+use crate::systems::config::Speeds;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PowerUpKind {
+    GravityFlip,
+    // Bigger bird for a while, harder to fit through the gaps
+    Grow,
+    // Smaller bird for a while, easier
+    Shrink,
+    // A charge to break through the next pipe hit instead of dying
+    Smash,
+}
+
+impl PowerUpKind {
+    // Bird size multiplier while it lasts, None for kinds that don't resize
+    pub fn size_scale(&self) -> Option<f32> {
+        match self {
+            PowerUpKind::GravityFlip | PowerUpKind::Smash => None,
+            PowerUpKind::Grow => Some(1.5),
+            PowerUpKind::Shrink => Some(0.6),
+        }
+    }
+}
+
+// Pick-up placed in a pipe gap and scrolled along with it
+#[derive(Clone)]
+pub struct PowerUp {
+    pub position: Vec2,
+    pub alive: bool,
+    pub kind: PowerUpKind,
+    // Pixels per second, matching the pipe group it sits in
+    pub scroll_speed: f32,
+}
+
+impl PhysicsBody for PowerUp {
+    fn get_collision_rect(&mut self) -> Rect {
+        Rect::new(self.position.x, self.position.y, Self::SIZE, Self::SIZE)
+    }
+
+    fn collides_with(&mut self, obj: &Rect) -> bool {
+        check_collision(&self.get_collision_rect(), obj)
+    }
+}
+
+impl PowerUp {
+    pub const SIZE: f32 = 24.0;
+
+    // `center` is where the middle of the pick-up should sit
+    pub fn new(center: Vec2, kind: PowerUpKind) -> Self {
+        PowerUp {
+            position: center - vec2(Self::SIZE, Self::SIZE) / 2.0,
+            alive: true,
+            kind,
+            scroll_speed: Speeds::SCROLL,
+        }
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        self.position.x -= Speeds::distance(self.scroll_speed, dt);
+        self.retire_if_behind(0.0);
+    }
+
+    pub fn retire_if_behind(&mut self, left_edge: f32) {
+        if self.position.x < left_edge - Self::SIZE {
+            self.alive = false;
+        }
+    }
+
+    pub fn draw(&self) {
+        let center = self.position + vec2(Self::SIZE, Self::SIZE) / 2.0;
+        let radius = Self::SIZE / 2.0;
+        let color = match self.kind {
+            PowerUpKind::GravityFlip => PURPLE,
+            PowerUpKind::Grow => ORANGE,
+            PowerUpKind::Shrink => SKYBLUE,
+            PowerUpKind::Smash => RED,
+        };
+        draw_circle(center.x, center.y, radius, color);
+        draw_circle_lines(center.x, center.y, radius, 2.0, WHITE);
+
+        match self.kind {
+            PowerUpKind::GravityFlip => Self::draw_flip_arrows(center),
+            // Plus for bigger, minus for smaller
+            PowerUpKind::Grow => {
+                draw_rectangle(center.x - 6.0, center.y - 1.5, 12.0, 3.0, WHITE);
+                draw_rectangle(center.x - 1.5, center.y - 6.0, 3.0, 12.0, WHITE);
+            }
+            PowerUpKind::Shrink => draw_rectangle(center.x - 6.0, center.y - 1.5, 12.0, 3.0, WHITE),
+            // A cross, for breaking things
+            PowerUpKind::Smash => {
+                draw_line(center.x - 5.0, center.y - 5.0, center.x + 5.0, center.y + 5.0, 3.0, WHITE);
+                draw_line(center.x - 5.0, center.y + 5.0, center.x + 5.0, center.y - 5.0, 3.0, WHITE);
+            }
+        }
+    }
+
+    // Up/down arrows hinting at the flip
+    fn draw_flip_arrows(center: Vec2) {
+        draw_triangle(
+            center + vec2(0.0, -8.0),
+            center + vec2(-5.0, -2.0),
+            center + vec2(5.0, -2.0),
+            WHITE,
+        );
+        draw_triangle(
+            center + vec2(0.0, 8.0),
+            center + vec2(-5.0, 2.0),
+            center + vec2(5.0, 2.0),
+            WHITE,
+        );
+    }
+}
+
+/*
+
+The tests validate :
+1. The pick-up is centred on the given point
+2. It scrolls left and retires once off screen
+
+*/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn test_powerup_is_centred() {
+        let mut powerup = PowerUp::new(vec2(100.0, 200.0), PowerUpKind::GravityFlip);
+        let rect = powerup.get_collision_rect();
+        assert_float_eq!(rect.center().x, 100.0, abs <= 0.001);
+        assert_float_eq!(rect.center().y, 200.0, abs <= 0.001);
+    }
+
+    #[test]
+    fn test_powerup_scrolls_and_retires() {
+        let mut powerup = PowerUp::new(vec2(100.0, 200.0), PowerUpKind::GravityFlip);
+        powerup.update(1.0 / 60.0);
+        assert_float_eq!(powerup.position.x, 100.0 - PowerUp::SIZE / 2.0 - 3.0, abs <= 0.001);
+        assert!(powerup.alive);
+
+        powerup.position.x = -PowerUp::SIZE - 1.0;
+        powerup.update(1.0 / 60.0);
+        assert!(!powerup.alive);
+    }
+}
@@ -1,52 +1,130 @@
 use macroquad::prelude::*;
 use crate::prefabs::button::Button;
+use crate::systems::bitmap_font::BitmapFont;
+use crate::systems::config::GameMode;
+use crate::systems::letterbox::{game_height, game_width};
+use crate::systems::resources::resource_path;
+use crate::systems::ui::{self, draw_text_outlined};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Medal {
+    Silver,
+    Gold,
+}
+
+// Scores needed for each medal
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MedalThresholds {
+    pub silver: i32,
+    pub gold: i32,
+}
+
+impl MedalThresholds {
+    pub fn new() -> Self {
+        MedalThresholds { silver: 10, gold: 20 }
+    }
+
+    // Every point is hard in sudden death, so medals come much sooner
+    pub fn for_mode(mode: GameMode) -> Self {
+        match mode {
+            GameMode::SuddenDeath => MedalThresholds { silver: 3, gold: 8 },
+            GameMode::Normal | GameMode::Zen | GameMode::Levels => Self::new(),
+        }
+    }
+
+    pub fn medal(&self, score: i32) -> Option<Medal> {
+        if score >= self.gold {
+            Some(Medal::Gold)
+        } else if score >= self.silver {
+            Some(Medal::Silver)
+        } else {
+            None
+        }
+    }
+}
+
+// mm:ss.mmm, minutes keep counting past an hour
+pub fn format_run_time(seconds: f32) -> String {
+    let millis = (seconds.max(0.0) * 1000.0).round() as u64;
+    format!("{:02}:{:02}.{:03}", millis / 60_000, millis / 1000 % 60, millis % 1000)
+}
+
+// The two numbers on the board, kept apart from its textures so they can
+// be filled in without a window
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct BoardScores {
+    pub score: i32,
+    pub highscore: i32,
+}
+
+impl BoardScores {
+    pub fn set(&mut self, score: i32, highscore: i32) {
+        self.score = score;
+        self.highscore = highscore;
+    }
+}
 
 pub struct Scoreboard {
     game_over_texture: Texture2D,
     scoreboard_texture: Texture2D,
     medal_texture: Texture2D,
     font: Font,
-    score: i32,
-    highscore: i32,
+    // Digit sprites for the numbers, when there's a sheet for them
+    digits: Option<BitmapFont>,
+    pub scores: BoardScores,
+    pub medal_thresholds: MedalThresholds,
+    // Seed of the run, shown so players can share the layout
+    pub seed: u64,
+    // Seconds the run lasted, and the fastest any run reached its score in
+    pub run_time: f32,
+    pub best_time: Option<f32>,
+    // Bigger numbers on dark panels, from the high contrast setting
+    pub high_contrast: bool,
+    // The run reached the pipe limit, a win banner replaces the game over title
+    pub won: bool,
     pub button: Button,
 }
 
 impl Scoreboard {
-    pub async fn new() -> Self {
-        let game_over_texture = load_texture("resources/gameover.png")
+    const WIN_BANNER: &'static str = "YOU WIN";
+
+    // `font` and `digits` are the game's, shared rather than loaded again
+    pub async fn new(font: Font, digits: Option<BitmapFont>) -> Self {
+        let game_over_texture = load_texture(&resource_path("gameover.png"))
             .await
             .expect("Failed to load gameover texture");
         
-        let scoreboard_texture = load_texture("resources/scoreboard.png")
+        let scoreboard_texture = load_texture(&resource_path("scoreboard.png"))
             .await
             .expect("Failed to load scoreboard texture");
         
-        let medal_texture = load_texture("resources/medals.png")
+        let medal_texture = load_texture(&resource_path("medals.png"))
             .await
             .expect("Failed to load medals texture");
 
-        let font = load_ttf_font("resources/font/flappy-font.ttf")
-            .await
-            .expect("Failed to load font");
-
         Scoreboard {
             game_over_texture,
             scoreboard_texture,
             medal_texture,
             font,
-            score: 0,
-            highscore: 0,
+            digits,
+            scores: BoardScores::default(),
+            medal_thresholds: MedalThresholds::new(),
+            seed: 0,
+            run_time: 0.0,
+            best_time: None,
+            high_contrast: false,
+            won: false,
             button: Button::new().await,
         }
     }
 
     pub fn set_score(&mut self, score: i32, highscore: i32) {
-        self.score = score;
-        self.highscore = highscore;
+        self.scores.set(score, highscore);
     }
 
     pub fn draw(&self) {
-        let screen_center = vec2(screen_width() / 2.0, screen_height() / 2.0);
+        let screen_center = vec2(game_width() / 2.0, game_height() / 2.0);
 
         // Draw Game Over text
         self.draw_game_over(screen_center);
@@ -57,11 +135,53 @@ impl Scoreboard {
         // Draw scores and medals on the scoreboard
         self.draw_scores_and_medals(scoreboard_rect);
 
+        // Seed and run time under the board
+        let seed = format!("SEED {}", self.seed);
+        self.draw_small_text(&seed, screen_center.x, scoreboard_rect.bottom() + 20.0);
+        let time = format!("TIME {}", format_run_time(self.run_time));
+        self.draw_small_text(&time, screen_center.x, scoreboard_rect.bottom() + 40.0);
+
         // Draw play button
         self.button.draw();
+
+        // Fastest this score has been reached, under the button
+        if let Some(best_time) = self.best_time.filter(|_| self.scores.score > 0) {
+            let best = format!("BEST TO {} {}", self.scores.score, format_run_time(best_time));
+            self.draw_small_text(&best, screen_center.x, self.button.rect().bottom() + 22.0);
+        }
+    }
+
+    fn draw_small_text(&self, text: &str, center_x: f32, y: f32) {
+        let dims = measure_text(text, Some(&self.font), 16, 1.0);
+        draw_text_ex(
+            text,
+            center_x - dims.width / 2.0,
+            y,
+            TextParams {
+                font: Some(&self.font),
+                font_size: 16,
+                color: WHITE,
+                ..Default::default()
+            },
+        );
     }
 
     fn draw_game_over(&self, screen_center: Vec2) {
+        if self.won {
+            let dims = measure_text(Self::WIN_BANNER, Some(&self.font), 40, 1.0);
+            draw_text_ex(
+                Self::WIN_BANNER,
+                screen_center.x - dims.width / 2.0,
+                screen_center.y * 0.1 + dims.height,
+                TextParams {
+                    font: Some(&self.font),
+                    font_size: 40,
+                    color: GOLD,
+                    ..Default::default()
+                },
+            );
+            return;
+        }
         let game_over_pos = vec2(
             screen_center.x - self.game_over_texture.width() / 2.0,
             screen_center.y * 0.1
@@ -96,20 +216,10 @@ impl Scoreboard {
         let score_y = scoreboard_rect.y + scoreboard_rect.h * 0.40;
         
         // Current Score
-        self.draw_score_text(
-            &self.score.to_string(),
-            score_x,
-            score_y,
-            Color::new(0.19, 0.19, 0.17, 1.0) // Dark brown
-        );
+        self.draw_score_text(self.scores.score, score_x, score_y);
 
         // High Score
-        self.draw_score_text(
-            &self.highscore.to_string(),
-            score_x,
-            score_y + 47.0,
-            Color::new(0.19, 0.19, 0.17, 1.0)
-        );
+        self.draw_score_text(self.scores.highscore, score_x, score_y + 47.0);
 
         // Draw medals on the left side of the scoreboard
         // Adjusted position to better align with the medal slot
@@ -119,28 +229,42 @@ impl Scoreboard {
         );
     }
 
-    fn draw_score_text(&self, text: &str, x: f32, y: f32, color: Color) {
-        let text_size = 30.0;
-        let measurement = measure_text(text, Some(&self.font), text_size as u16, 1.0);
-        
-        draw_text_ex(
+    // Centred on (x, y)
+    fn draw_score_text(&self, value: i32, x: f32, y: f32) {
+        if let Some(digits) = &self.digits {
+            let height = f32::from(ui::font_size(22, self.high_contrast));
+            digits.draw_number(value.max(0) as u32, x, y - height / 2.0, height);
+            return;
+        }
+
+        let text = &value.to_string();
+        let text_size = ui::font_size(30, self.high_contrast);
+        let measurement = measure_text(text, Some(&self.font), text_size, 1.0);
+        let text_x = x - measurement.width / 2.0;  // Changed from + to - to align better
+        let text_y = y + measurement.height / 2.0;
+
+        if self.high_contrast {
+            ui::draw_backing(ui::backing_rect(text_x, text_y, measurement));
+        }
+        draw_text_outlined(
             text,
-            x - measurement.width / 2.0,  // Changed from + to - to align better
-            y + measurement.height / 2.0,
+            text_x,
+            text_y,
             TextParams {
                 font: Some(&self.font),
-                font_size: text_size as u16,
-                color,
+                font_size: text_size,
+                color: WHITE,
                 ..Default::default()
             },
+            BLACK,
         );
     }
 
     fn draw_medal(&self, x: f32, y: f32) {
-        let medal_source = match self.score {
-            s if s >= 20 => Rect::new(0.0, 46.0, 44.0, 46.0),  // Gold medal
-            s if s >= 10 => Rect::new(0.0, 0.0, 44.0, 46.0),   // Silver medal
-            _ => return,  // No medal for lower scores
+        let medal_source = match self.medal_thresholds.medal(self.scores.score) {
+            Some(Medal::Gold) => Rect::new(0.0, 46.0, 44.0, 46.0),
+            Some(Medal::Silver) => Rect::new(0.0, 0.0, 44.0, 46.0),
+            None => return,  // No medal for lower scores
         };
 
         draw_texture_ex(
@@ -163,6 +287,8 @@ The tests validate:
 2. No stale values remain after successive updates
 3. Safe hadling of edge values
 4. Independence of score and highscore 
+5. Medal thresholds, including the lower sudden death ones
+6. Run times format as mm:ss.mmm from under a second to several minutes
 
 */
 
@@ -226,4 +352,29 @@ mod tests {
         assert_eq!(scoreboard.score, 0);
         assert_eq!(scoreboard.highscore, 50);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_medal_thresholds() {
+        let normal = MedalThresholds::for_mode(GameMode::Normal);
+        assert_eq!(normal.medal(9), None);
+        assert_eq!(normal.medal(10), Some(Medal::Silver));
+        assert_eq!(normal.medal(20), Some(Medal::Gold));
+
+        let sudden_death = MedalThresholds::for_mode(GameMode::SuddenDeath);
+        assert_eq!(sudden_death.medal(2), None);
+        assert_eq!(sudden_death.medal(3), Some(Medal::Silver));
+        assert_eq!(sudden_death.medal(8), Some(Medal::Gold));
+    }
+
+    #[test]
+    fn test_format_run_time() {
+        assert_eq!(format_run_time(0.0), "00:00.000");
+        assert_eq!(format_run_time(0.25), "00:00.250");
+        assert_eq!(format_run_time(0.0004), "00:00.000");
+        assert_eq!(format_run_time(12.345), "00:12.345");
+        assert_eq!(format_run_time(59.9996), "01:00.000");
+        assert_eq!(format_run_time(125.5), "02:05.500");
+        assert_eq!(format_run_time(3725.007), "62:05.007");
+        assert_eq!(format_run_time(-1.0), "00:00.000");
+    }
+}
@@ -0,0 +1,102 @@
+use macroquad::prelude::*;
+use crate::systems::layout::Layout;
+use crate::systems::resources::resource_path;
+use crate::systems::theme::Theme;
+
+// How the sky behind everything is drawn
+pub enum SkyRenderer {
+    Texture(Texture2D),
+    Gradient { top: Color, bottom: Color },
+}
+
+impl SkyRenderer {
+    // Height of each gradient band in pixels
+    const BAND_HEIGHT: f32 = 4.0;
+
+    // Tiles sky.png, or falls back to the theme gradient if it can't be loaded
+    pub async fn load(theme: &Theme) -> Self {
+        let gradient = SkyRenderer::Gradient {
+            top: theme.sky_top,
+            bottom: theme.sky_bottom,
+        };
+        if theme.gradient_sky {
+            return gradient;
+        }
+
+        match load_texture(&resource_path("sky.png")).await {
+            Ok(texture) => SkyRenderer::Texture(texture),
+            Err(_) => gradient,
+        }
+    }
+
+    // Colour `y` pixels down a gradient `height` pixels tall
+    pub fn gradient_color(top: Color, bottom: Color, y: f32, height: f32) -> Color {
+        let t = if height > 0.0 { (y / height).clamp(0.0, 1.0) } else { 0.0 };
+        Color::new(
+            top.r + (bottom.r - top.r) * t,
+            top.g + (bottom.g - top.g) * t,
+            top.b + (bottom.b - top.b) * t,
+            top.a + (bottom.a - top.a) * t,
+        )
+    }
+
+    pub fn draw(&self, layout: &Layout) {
+        match self {
+            SkyRenderer::Texture(texture) => {
+                for x in layout.tile_offsets(texture.width()) {
+                    draw_texture(texture, x, 0.0, WHITE);
+                }
+            }
+            SkyRenderer::Gradient { top, bottom } => {
+                let mut y = 0.0;
+                while y < layout.screen.y {
+                    let color = Self::gradient_color(*top, *bottom, y, layout.screen.y);
+                    draw_rectangle(0.0, y, layout.screen.x, Self::BAND_HEIGHT, color);
+                    y += Self::BAND_HEIGHT;
+                }
+            }
+        }
+    }
+}
+
+/*
+
+The tests validate :
+1. Gradient colour at the top, middle and bottom of the screen
+2. Positions outside the screen clamp to the end colours
+
+*/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    const TOP: Color = Color::new(0.0, 0.2, 1.0, 1.0);
+    const BOTTOM: Color = Color::new(1.0, 0.6, 0.0, 1.0);
+
+    #[test]
+    fn test_gradient_top_middle_bottom() {
+        let top = SkyRenderer::gradient_color(TOP, BOTTOM, 0.0, 600.0);
+        assert_float_eq!(top.r, 0.0, abs <= 0.001);
+        assert_float_eq!(top.b, 1.0, abs <= 0.001);
+
+        let middle = SkyRenderer::gradient_color(TOP, BOTTOM, 300.0, 600.0);
+        assert_float_eq!(middle.r, 0.5, abs <= 0.001);
+        assert_float_eq!(middle.g, 0.4, abs <= 0.001);
+        assert_float_eq!(middle.b, 0.5, abs <= 0.001);
+
+        let bottom = SkyRenderer::gradient_color(TOP, BOTTOM, 600.0, 600.0);
+        assert_float_eq!(bottom.r, 1.0, abs <= 0.001);
+        assert_float_eq!(bottom.g, 0.6, abs <= 0.001);
+        assert_float_eq!(bottom.b, 0.0, abs <= 0.001);
+    }
+
+    #[test]
+    fn test_gradient_clamps_outside_screen() {
+        let above = SkyRenderer::gradient_color(TOP, BOTTOM, -50.0, 600.0);
+        let below = SkyRenderer::gradient_color(TOP, BOTTOM, 900.0, 600.0);
+        assert_float_eq!(above.r, TOP.r, abs <= 0.001);
+        assert_float_eq!(below.r, BOTTOM.r, abs <= 0.001);
+    }
+}
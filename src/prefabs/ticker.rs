@@ -0,0 +1,87 @@
+use macroquad::prelude::*;
+
+// Summary - Ticker :
+// A line of text scrolling right to left along the bottom of the screen,
+// repeated end to end so it never runs out. The offset wraps once one copy
+// (plus the gap after it) has scrolled past.
+pub struct Ticker {
+    text: String,
+    // Width of the text in pixels, measured once up front
+    width: f32,
+    // How far the first copy has scrolled left, in pixels
+    offset: f32,
+}
+
+impl Ticker {
+    pub const FONT_SIZE: f32 = 20.0;
+    pub const HEIGHT: f32 = 26.0;
+    // Pixels per second
+    const SPEED: f32 = 60.0;
+    // Space between one copy of the text and the next
+    const GAP: f32 = 40.0;
+
+    pub fn new(text: &str) -> Self {
+        let width = measure_text(text, None, Self::FONT_SIZE as u16, 1.0).width;
+        Self::with_width(text, width)
+    }
+
+    pub fn with_width(text: &str, width: f32) -> Self {
+        Ticker {
+            text: text.to_string(),
+            width,
+            offset: 0.0,
+        }
+    }
+
+    // Distance from the start of one copy to the next
+    fn period(&self) -> f32 {
+        self.width + Self::GAP
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        self.offset = (self.offset + Self::SPEED * dt.max(0.0)) % self.period();
+    }
+
+    // Band along the bottom of a `screen` sized area, copies repeated across it
+    pub fn draw(&self, screen: Vec2) {
+        let top = screen.y - Self::HEIGHT;
+        draw_rectangle(0.0, top, screen.x, Self::HEIGHT, Color::new(0.0, 0.0, 0.0, 0.5));
+
+        let mut x = -self.offset;
+        while x < screen.x {
+            draw_text(&self.text, x, screen.y - 8.0, Self::FONT_SIZE, WHITE);
+            x += self.period();
+        }
+    }
+}
+
+/*
+
+The tests validate :
+1. The offset advances with time and wraps once a copy and its gap have passed
+
+*/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn test_offset_wraps_past_text_width() {
+        let mut ticker = Ticker::with_width("HIGH SCORE 42", 200.0);
+        ticker.update(1.0);
+        assert_float_eq!(ticker.offset, Ticker::SPEED, abs <= 0.001);
+
+        // Just short of one period, then just past it
+        let period = 200.0 + Ticker::GAP;
+        ticker.update((period - Ticker::SPEED - 1.0) / Ticker::SPEED);
+        assert_float_eq!(ticker.offset, period - 1.0, abs <= 0.001);
+        ticker.update(2.0 / Ticker::SPEED);
+        assert_float_eq!(ticker.offset, 1.0, abs <= 0.001);
+
+        // A long stall still lands inside one period
+        ticker.update(1000.0);
+        assert!((0.0..period).contains(&ticker.offset));
+    }
+}
@@ -0,0 +1,160 @@
+use macroquad::prelude::*;
+use crate::systems::layout::Layout;
+use crate::systems::settings::Settings;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TutorialStep {
+    TapToFlap,
+    AvoidPipes,
+    // Short well done message before the tutorial closes
+    Finished,
+    Done,
+}
+
+// Summary - Tutorial :
+// First-run hints shown over the game. Each step waits for the player to
+// do what it asks: flap, then get through a pipe. Once done the game sets
+// `tutorial_seen` in the settings so it never shows again.
+pub struct Tutorial {
+    pub step: TutorialStep,
+    finish_timer: f32,
+}
+
+impl Tutorial {
+    const FINISHED_DURATION: f32 = 2.0;
+
+    pub fn new() -> Self {
+        Tutorial {
+            step: TutorialStep::TapToFlap,
+            finish_timer: 0.0,
+        }
+    }
+
+    // Only players who haven't finished it yet get the tutorial
+    pub fn for_settings(settings: &Settings) -> Option<Self> {
+        (!settings.tutorial_seen).then(Self::new)
+    }
+
+    pub fn on_flap(&mut self) {
+        if self.step == TutorialStep::TapToFlap {
+            self.step = TutorialStep::AvoidPipes;
+        }
+    }
+
+    pub fn on_score(&mut self) {
+        if self.step == TutorialStep::AvoidPipes {
+            self.step = TutorialStep::Finished;
+            self.finish_timer = Self::FINISHED_DURATION;
+        }
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        if self.step == TutorialStep::Finished {
+            self.finish_timer -= dt;
+            if self.finish_timer <= 0.0 {
+                self.step = TutorialStep::Done;
+            }
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.step == TutorialStep::Done
+    }
+
+    pub fn hint(&self) -> &'static str {
+        match self.step {
+            TutorialStep::TapToFlap => "TAP TO FLAP",
+            TutorialStep::AvoidPipes => "FLY THROUGH THE GAPS",
+            TutorialStep::Finished => "NICE! YOU'RE READY",
+            TutorialStep::Done => "",
+        }
+    }
+
+    // Hint text above the playfield, with an arrow from the bird showing
+    // which way to go
+    pub fn draw(&self, font: &Font, layout: &Layout, bird_center: Vec2) {
+        if self.is_done() {
+            return;
+        }
+
+        let text = self.hint();
+        let dims = measure_text(text, Some(font), 24, 1.0);
+        draw_text_ex(
+            text,
+            layout.centered_x(dims.width),
+            layout.y_at(0.18),
+            TextParams {
+                font: Some(font),
+                font_size: 24,
+                color: YELLOW,
+                ..Default::default()
+            },
+        );
+
+        let direction = match self.step {
+            TutorialStep::TapToFlap => vec2(0.0, -1.0),
+            TutorialStep::AvoidPipes => vec2(1.0, 0.0),
+            TutorialStep::Finished | TutorialStep::Done => return,
+        };
+        let start = bird_center + direction * 30.0;
+        let end = start + direction * 40.0;
+        let side = direction.perp() * 8.0;
+        let base = end - direction * 14.0;
+        draw_line(start.x, start.y, base.x, base.y, 4.0, YELLOW);
+        draw_triangle(end, base + side, base - side, YELLOW);
+    }
+}
+
+/*
+
+The tests validate :
+1. Steps advance in order: flap, pass a pipe, well done message, done
+2. Events for other steps don't skip ahead
+3. The tutorial only starts when the settings say it hasn't been seen
+
+*/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_steps_advance_in_order() {
+        let mut tutorial = Tutorial::new();
+        assert_eq!(tutorial.step, TutorialStep::TapToFlap);
+
+        tutorial.on_flap();
+        assert_eq!(tutorial.step, TutorialStep::AvoidPipes);
+
+        tutorial.on_score();
+        assert_eq!(tutorial.step, TutorialStep::Finished);
+        assert!(!tutorial.is_done());
+
+        tutorial.update(1.0);
+        assert_eq!(tutorial.step, TutorialStep::Finished);
+        tutorial.update(1.0);
+        assert!(tutorial.is_done());
+    }
+
+    #[test]
+    fn test_out_of_order_events_are_ignored() {
+        let mut tutorial = Tutorial::new();
+        tutorial.on_score();
+        tutorial.update(10.0);
+        assert_eq!(tutorial.step, TutorialStep::TapToFlap);
+
+        tutorial.on_flap();
+        tutorial.on_flap();
+        tutorial.update(10.0);
+        assert_eq!(tutorial.step, TutorialStep::AvoidPipes);
+    }
+
+    #[test]
+    fn test_seen_flag_gates_tutorial() {
+        let mut settings = Settings::new();
+        assert!(Tutorial::for_settings(&settings).is_some());
+
+        settings.tutorial_seen = true;
+        assert!(Tutorial::for_settings(&settings).is_none());
+    }
+}
@@ -1,223 +1,992 @@
 use macroquad::prelude::*;
-use macroquad::audio::{load_sound, play_sound, PlaySoundParams, Sound};
+use macroquad::audio::{load_sound, play_sound, stop_sound, PlaySoundParams, Sound};
 
-use crate::prefabs::background::Background;
+use crate::prefabs::background::{LayerScroll, Scenery};
 use crate::prefabs::bird::Bird;
+use crate::prefabs::flap_button::FlapButton;
+use crate::prefabs::floating_text::{FloatingText, FloatingTexts};
 use crate::prefabs::ground::Ground;
-use crate::prefabs::pipes::{PipeGenerator, PipeGroup};
-use crate::prefabs::scoreboard::Scoreboard;
-use crate::scenes::{Scene, Transition};
-use crate::systems::physics::PhysicsBody;
-use crate::systems::storage;
+use crate::prefabs::pipes::{Pipe, PipeTextures};
+use crate::prefabs::scoreboard::{BoardScores, MedalThresholds, Scoreboard};
+use crate::prefabs::tutorial::Tutorial;
+use crate::scenes::{level_complete::LevelCompleteScene, Scene, Transition};
+use crate::systems::analytics::{self, SharedAnalytics};
+use crate::systems::audio::{pick_sound, DeathSound, Ducking, DEATH_SOUND_FILES};
+use crate::systems::bitmap_font::BitmapFont;
+use crate::systems::config::{ControlScheme, GameMode, Quality, Speeds};
+use crate::systems::console::Console;
+use crate::systems::difficulty::{AutoFlap, LevelConfig, SuddenDeath};
+use crate::systems::input::Rumble;
+use crate::systems::keybindings::{Action, KeyBindings};
+use crate::systems::layout::Layout;
+use crate::systems::letterbox::{self, game_height, game_mouse_position, game_width};
+use crate::systems::resources::resource_path;
+use crate::systems::physics::CollisionInfo;
+use crate::systems::records::Records;
+use crate::systems::replay::Replay;
+use crate::systems::rewind::Rewind;
+use crate::systems::settings::Settings;
+use crate::systems::simulation::{GamePhase, SimEvent, Simulation, FIXED_FRAME_TIME};
+use crate::systems::storage::StorageError;
+use crate::systems::ui::{self, draw_text_outlined};
+
+// Summary - GameResult :
+// How a finished run went, kept on the GameScene so whatever pushed it can
+// show "last run" info once it is popped.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GameResult {
+    pub score: i32,
+    // Beat the high score held before the run
+    pub new_high: bool,
+    pub seed: u64,
+    // Seconds from the first flap to the death, or to the win
+    pub duration: f32,
+    // Reached the pipe limit instead of crashing
+    pub won: bool,
+}
+
+impl GameResult {
+    pub fn new(sim: &Simulation, previous_high: i32, duration: f32) -> Self {
+        GameResult {
+            score: sim.score,
+            new_high: sim.score > previous_high,
+            seed: sim.seed(),
+            duration,
+            won: sim.has_won(),
+        }
+    }
+}
+
+// Summary - RunScores :
+// What GameScene::score() and highscore() report, read from the parts of
+// the scene that work without a window.
+struct RunScores<'a> {
+    sim: &'a Simulation,
+    records: &'a Records,
+}
+
+impl RunScores<'_> {
+    fn score(&self) -> i32 {
+        self.sim.score
+    }
+
+    fn highscore(&self) -> i32 {
+        self.records.highscore
+    }
+}
 
 pub struct GameScene {
-    sky_texture: Texture2D,
-    background: Background,
+    scenery: Scenery,
     ground: Ground,
-    pipes_texture: Texture2D,
+    pipe_textures: PipeTextures,
 
     instructions: Texture2D,
     get_ready: Texture2D,
 
-    bird: Bird,
-
     flap_sound: Sound,
     ground_hit_sound: Sound,
     pipe_hit_sound: Sound,
     score_sound: Sound,
+    whoosh_sound: Sound,
+    // DEATH_SOUND_FILES that loaded, for the Random death sound
+    death_sounds: Vec<Sound>,
 
-    score: i32,
-    highscore: i32,
+    // Set records.persist to false to keep a run's records off the disk
+    pub records: Records,
     font: Font,
+    // Digit sprites for the score, the font is used without them
+    digits: Option<BitmapFont>,
+    settings: Settings,
 
+    keys: KeyBindings,
+    console: Console,
+    show_debug: bool,
     is_mouse_down: bool,
-    instructions_visible: bool,
 
-    pipes: Vec<PipeGroup>,
-    game_over: bool,
-    pipe_generator: PipeGenerator,
+    sim: Simulation,
+    fixed_seed: Option<u64>,
+    rewind: Rewind,
+    rumble: Rumble,
+    auto_flap: AutoFlap,
+    // Turns the other sounds down while the death sound plays
+    ducking: Ducking,
+    // Flaps of the current run, saved to REPLAY_FILE_NAME at game over.
+    // None once the run continued from a checkpoint, which a replay can't
+    // start from.
+    replay: Option<Replay>,
+    replay_frame: u32,
+    // Seconds flown this run, for the result
+    run_time: f32,
+    // Run time each score was reached at, index 0 for the first point
+    splits: Vec<f32>,
+    // Embedder's metrics sink, see systems::analytics
+    analytics: SharedAnalytics,
+    // Seconds of world time left over from the last frame, see world_steps
+    world_clock: f32,
+    // Latest finished run, None until the first death
+    result: Option<GameResult>,
+    // None once the player has been through it
+    tutorial: Option<Tutorial>,
+    // Index into the levels of the levels mode, 0 for the first
+    level: usize,
+    floating_texts: FloatingTexts,
+    reset_fade_timer: f32,
+    // Last frame before the restart, faded out over the new run
+    reset_fade_frame: Option<RenderTarget>,
+    // Seconds since the game ended, restart clicks wait out GAME_OVER_DEAD_ZONE
+    game_over_timer: f32,
+    // Seconds without input on the instructions screen, see is_idle
+    idle_timer: f32,
 
     scoreboard: Scoreboard,
+    flap_button: FlapButton,
 }
 
 impl GameScene {
-    pub async fn new() -> GameScene {
+    const RESET_FADE_DURATION: f32 = 0.2;
+    // Seconds after a death in which the play button ignores clicks, so a
+    // tap meant as a flap doesn't skip the scoreboard
+    const GAME_OVER_DEAD_ZONE: f32 = 0.5;
+    // Pipes change colour every this many points
+    const PIPE_TINT_MILESTONE: i32 = 25;
+    // Multiplied with the green pipe sprite: green, then blue, then purple
+    const PIPE_TINTS: [Color; 3] = [
+        WHITE,
+        Color::new(0.4, 0.7, 1.0, 1.0),
+        Color::new(1.0, 0.45, 1.0, 1.0),
+    ];
+    // Seconds of blinking invulnerability after a rewind, to get clear of the pipe
+    const REWIND_INVULNERABILITY: f32 = 1.0;
+    // Gamepad rumble on death, strength 0-1 and seconds
+    const DEATH_RUMBLE: (f32, f32) = (0.6, 0.25);
+    // Volume multiplier and seconds for the other sounds after a death
+    const DUCK_LEVEL: f32 = 0.3;
+    const DUCK_DURATION: f32 = 1.5;
+    // Kept well under the score ding
+    const WHOOSH_VOLUME: f32 = 0.35;
+    // Arrow length in pixels per pixel/frame of velocity
+    const VELOCITY_ARROW_SCALE: f32 = 8.0;
+    // Seconds untouched on the instructions screen before it dims, for kiosks
+    const IDLE_THRESHOLD: f32 = 30.0;
+    const IDLE_DIM_ALPHA: f32 = 0.6;
+    // Width of each upcoming gap preview at the right edge, and its alpha
+    const PREVIEW_WIDTH: f32 = 10.0;
+    const PREVIEW_ALPHA: f32 = 0.25;
+    // Speed ratio at which the rage tint is strongest, and its alpha there
+    const RAGE_FULL_RATIO: f32 = 1.5;
+    const RAGE_MAX_ALPHA: f32 = 0.12;
+
+    // `seed` replays a shared layout on every run, None picks a new one each time
+    pub async fn new(mode: GameMode, seed: Option<u64>, analytics: SharedAnalytics) -> GameScene {
         let bird = Bird::new().await;
+        let mut ground = Ground::new().await;
+        let screen = vec2(game_width(), game_height());
+        let settings = Settings::load();
+        let tutorial = Tutorial::for_settings(&settings);
+        let rumble = Rumble::new(settings.rumble);
+        let auto_flap = AutoFlap::new(settings.flap_assist);
+        let flap_button = FlapButton::new(settings.flap_button);
+        let scenery = Scenery::load(&settings).await;
+        let mut death_sounds = Vec::new();
+        for file in DEATH_SOUND_FILES {
+            match load_sound(&resource_path(file)).await {
+                Ok(sound) => death_sounds.push(sound),
+                Err(error) => log::warn!("death sound {} not loaded: {}", file, error),
+            }
+        }
+        let mut sim = Simulation::new(seed.unwrap_or_else(::rand::random), bird, screen, screen.y - ground.height());
+        sim.config.mode = mode;
+        if mode == GameMode::SuddenDeath {
+            SuddenDeath::apply(&mut sim.config);
+        } else if mode == GameMode::Levels {
+            LevelConfig::for_level(0).apply(&mut sim.config);
+        }
+        ground.speed = sim.config.scroll_speed;
+        let replay = Replay::new(sim.seed());
+        let font = load_ttf_font(&resource_path("font/flappy-font.ttf")).await.unwrap();
+        let digits = BitmapFont::load().await;
+        let mut scoreboard = Scoreboard::new(font.clone(), digits.clone()).await;
+        scoreboard.medal_thresholds = MedalThresholds::for_mode(mode);
+        scoreboard.high_contrast = settings.high_contrast;
+        sim.config.scrolling_camera = settings.scrolling_camera;
+        sim.config.gravity_on_first_flap = settings.gravity_on_first_flap;
+        sim.config.checkpoints = settings.checkpoints;
+        sim.config.pipe_limit = settings.pipe_limit;
+        sim.config.collisions =
+            settings.control_scheme != ControlScheme::FollowMouse || settings.follow_mouse_collisions;
+        sim.bird.inverted = settings.inverted_controls;
+        sim.bird.reduced_motion = settings.reduced_motion;
+        sim.bird.squash_stretch = settings.squash_stretch;
 
         GameScene {
-            sky_texture: load_texture("resources/sky.png").await.unwrap(),
-            background: Background::new().await,
-            ground: Ground::new().await,
-            pipes_texture: load_texture("resources/pipes.png").await.unwrap(),
-            get_ready: load_texture("resources/get-ready.png").await.unwrap(),
-            instructions: load_texture("resources/instructions.png").await.unwrap(),
-
-            bird,
-
-            flap_sound: load_sound("resources/flap.wav").await.unwrap(),
-            ground_hit_sound: load_sound("resources/ground-hit.wav").await.unwrap(),
-            pipe_hit_sound: load_sound("resources/pipe-hit.wav").await.unwrap(),
-            score_sound: load_sound("resources/score.wav").await.unwrap(),
-
-            score: 0,
-            highscore: storage::read().unwrap_or(0),
-            font: load_ttf_font("resources/font/flappy-font.ttf").await.unwrap(),
+            scenery,
+            ground,
+            pipe_textures: PipeTextures::load().await,
+            get_ready: load_texture(&resource_path("get-ready.png")).await.unwrap(),
+            instructions: load_texture(&resource_path("instructions.png")).await.unwrap(),
+
+            flap_sound: load_sound(&resource_path("flap.wav")).await.unwrap(),
+            ground_hit_sound: load_sound(&resource_path("ground-hit.wav")).await.unwrap(),
+            pipe_hit_sound: load_sound(&resource_path("pipe-hit.wav")).await.unwrap(),
+            score_sound: load_sound(&resource_path("score.wav")).await.unwrap(),
+            whoosh_sound: load_sound(&resource_path("whoosh.wav")).await.unwrap(),
+            death_sounds,
+
+            records: Records::load(),
+            font,
+            digits,
+            settings,
+            keys: KeyBindings::load(),
+            console: Console::new(),
+            show_debug: false,
 
             is_mouse_down: true,
-            instructions_visible: true,
-            pipes: Vec::new(),
-            game_over: false,
-            pipe_generator: PipeGenerator::new(),
 
-            scoreboard: Scoreboard::new().await,
+            tutorial,
+            level: 0,
+            floating_texts: FloatingTexts::new(sim.config.max_floating_texts),
+            sim,
+            fixed_seed: seed,
+            rewind: Rewind::new(Rewind::DEFAULT_CAPACITY),
+            rumble,
+            auto_flap,
+            ducking: Ducking::new(Self::DUCK_LEVEL, Self::DUCK_DURATION),
+            replay: Some(replay),
+            replay_frame: 0,
+            run_time: 0.0,
+            splits: Vec::new(),
+            analytics,
+            world_clock: 0.0,
+            result: None,
+            reset_fade_timer: 0.0,
+            reset_fade_frame: None,
+            game_over_timer: 0.0,
+            idle_timer: 0.0,
+
+            scoreboard,
+            flap_button,
         }
     }
 
+    // Gameplay is reset straight away, the frame from before is kept to
+    // crossfade out of on top
     fn reset(&mut self) {
-        self.instructions_visible = true;
-        self.pipes.clear();
-        self.background.scroll = true;
+        self.reset_fade_frame = Some(letterbox::draw_into_texture(|| self.draw()));
+        self.sim.reset(self.fixed_seed.unwrap_or_else(::rand::random));
+        self.rewind.reset();
+        self.replay = Some(Replay::new(self.sim.seed()));
+        self.replay_frame = 0;
+        self.run_time = 0.0;
+        self.splits.clear();
+        self.floating_texts.clear();
+        if let Some(background) = self.scenery.background_mut() {
+            background.set_scroll(true);
+        }
         self.ground.scroll = true;
-        self.bird.reset();
-        self.score = 0;
-        self.game_over = false;
+        self.ground.rise_offset = 0.0;
+        self.reset_fade_timer = Self::RESET_FADE_DURATION;
+    }
+
+    fn window_title(score: i32) -> String {
+        format!("{} — Score: {}", crate::WINDOW_TITLE, score)
+    }
+
+    // Which upcoming pipe (0 = next one) beats the high score once passed.
+    // Every pipe is worth at least a point, so it is at most this many pipes away.
+    fn record_pipe_index(score: i32, highscore: i32) -> Option<usize> {
+        if highscore <= 0 || score > highscore {
+            return None;
+        }
+        Some((highscore - score) as usize)
+    }
+
+    fn draw_record_marker(&self) {
+        let Some(index) = Self::record_pipe_index(self.score(), self.highscore()) else {
+            return;
+        };
+
+        let mut upcoming: Vec<_> = self
+            .sim
+            .pipes
+            .iter()
+            .filter(|pipe_group| pipe_group.alive && !pipe_group.has_scored)
+            .collect();
+        upcoming.sort_by(|a, b| a.position.x.total_cmp(&b.position.x));
+
+        // Only shown once the record pipe has scrolled into view
+        if let Some(pipe_group) = upcoming.get(index) {
+            let x = pipe_group.position.x + Pipe::HALF_WIDTH;
+            let ground_y = self.sim.ground_y;
+            draw_line(x, ground_y - 40.0, x, ground_y, 2.0, GOLD);
+            draw_text_ex(
+                "BEST",
+                x - 14.0,
+                ground_y - 44.0,
+                TextParams {
+                    font: Some(&self.font),
+                    font_size: 14,
+                    color: GOLD,
+                    ..Default::default()
+                },
+            );
+        }
+    }
+
+    fn tick_fade(timer: f32, dt: f32) -> f32 {
+        (timer - dt).max(0.0)
+    }
+
+    fn accepts_restart(game_over_timer: f32) -> bool {
+        game_over_timer >= Self::GAME_OVER_DEAD_ZONE
+    }
+
+    // Alpha of the frame from before the reset, fully opaque right after it
+    fn fade_alpha(timer: f32) -> f32 {
+        (timer / Self::RESET_FADE_DURATION).clamp(0.0, 1.0)
+    }
+
+    // Once the current level's goal is passed, records the level like a
+    // finished run, sets the game up for the next level and shows the level
+    // complete screen over it
+    fn complete_level(&mut self) -> Option<Transition> {
+        if self.sim.config.mode != GameMode::Levels
+            || !self.sim.bird.alive
+            || !LevelConfig::for_level(self.level).is_complete(self.score())
+        {
+            return None;
+        }
+
+        self.finish_run();
+        self.level += 1;
+        log::info!("level {} complete", self.level);
+        let next = LevelConfig::for_level(self.level);
+        next.apply(&mut self.sim.config);
+        self.ground.speed = next.scroll_speed;
+        self.reset();
+        // The click that dismisses the screen shouldn't also start the level
+        self.is_mouse_down = true;
+        Some(Transition::Push(Box::new(LevelCompleteScene::new(self.level, next.goal))))
     }
 
     fn start_game(&mut self) {
-        if self.instructions_visible {
-            self.instructions_visible = false;
+        self.sim.start();
+        self.handle_events(&[SimEvent::Started]);
+    }
+
+    // Any input starts the count again
+    fn tick_idle(timer: f32, dt: f32, input: bool) -> f32 {
+        if input { 0.0 } else { timer + dt }
+    }
+
+    fn idle_after(timer: f32) -> bool {
+        timer >= Self::IDLE_THRESHOLD
+    }
+
+    // Left on the instructions screen long enough to dim it
+    pub fn is_idle(&self) -> bool {
+        self.sim.phase() == GamePhase::Instructions && Self::idle_after(self.idle_timer)
+    }
+
+    // Ghost pipes for the next gaps, soonest leftmost and strongest
+    fn draw_pipe_previews(&self, layout: &Layout) {
+        let gaps = self.sim.upcoming_gaps();
+        let count = gaps.len() as f32;
+        for (index, (gap_top, gap_size)) in gaps.into_iter().enumerate() {
+            let x = layout.screen.x - (count - index as f32) * (Self::PREVIEW_WIDTH + 4.0);
+            let color = Color::new(1.0, 1.0, 1.0, Self::PREVIEW_ALPHA / (index + 1) as f32);
+            draw_rectangle(x, 0.0, Self::PREVIEW_WIDTH, gap_top, color);
+            let gap_bottom = gap_top + gap_size;
+            draw_rectangle(x, gap_bottom, Self::PREVIEW_WIDTH, self.sim.ground_y - gap_bottom, color);
         }
-        self.bird.allow_gravity = true;
-        self.pipe_generator.start();
     }
 
-    fn check_for_collisions(&mut self) {
-        let mut bird_died = false;
-        if self.bird.alive {
-            for pipe_group in &mut self.pipes {
-                if pipe_group.collides_with(&self.bird.get_collision_rect()) {
-                    bird_died = true;
-                }
-            }
+    fn draw_idle_prompt(&self, layout: &Layout) {
+        draw_rectangle(0.0, 0.0, game_width(), game_height(), Color::new(0.0, 0.0, 0.0, Self::IDLE_DIM_ALPHA));
+
+        let text = "TAP TO PLAY";
+        let pulse = 0.6 + 0.4 * (get_time() as f32 * 3.0).sin();
+        let dims = measure_text(text, Some(&self.font), 40, 1.0);
+        draw_text_ex(
+            text,
+            layout.centered_x(dims.width),
+            layout.y_at(Layout::COUNTDOWN_Y_RATIO),
+            TextParams {
+                font: Some(&self.font),
+                font_size: 40,
+                color: Color::new(1.0, 1.0, 1.0, pulse),
+                ..Default::default()
+            },
+        );
+    }
+
+    pub fn result(&self) -> Option<GameResult> {
+        self.result
+    }
+
+    fn run_scores(&self) -> RunScores<'_> {
+        RunScores { sim: &self.sim, records: &self.records }
+    }
+
+    // Points in the current run, for HUDs and the window title
+    pub fn score(&self) -> i32 {
+        self.run_scores().score()
+    }
+
+    // Best score so far, including the current run once it has ended
+    pub fn highscore(&self) -> i32 {
+        self.run_scores().highscore()
+    }
+
+    // Keep the simulation in sync with the window, which can be resized
+    fn layout(&self) -> Layout {
+        Layout::current(self.ground.height())
+    }
+
+    // Pipes, bird and effects are drawn through the follow camera in the
+    // scrolling variant. Scenery and HUD stay in screen space.
+    fn set_world_camera(&self) {
+        if self.sim.config.scrolling_camera {
+            let mut camera = self.sim.camera.to_camera2d(self.sim.screen);
+            camera.viewport = letterbox::viewport();
+            camera.render_target = letterbox::current_target();
+            set_camera(&camera);
         }
+    }
 
-        if bird_died {
-            play_sound(&self.pipe_hit_sound, PlaySoundParams {
-                volume: 1.0,
-                looped: false,
-            });
-            self.bird.kill();
+    // Purple tint while gravity is flipped, flickering during the last
+    // second to warn that normal gravity is about to return
+    fn gravity_tint_alpha(remaining: f32) -> f32 {
+        if remaining <= 0.0 {
+            0.0
+        } else if remaining < 1.0 && (remaining * 8.0) as i32 % 2 == 1 {
+            0.05
+        } else {
+            0.15
+        }
+    }
 
-            self.pipe_generator.stop();
-            self.background.scroll = false;
-            self.ground.scroll = false;
+    // Red tint once the pipes scroll faster than the base speed, growing
+    // until they're RAGE_FULL_RATIO times as fast
+    fn rage_tint_alpha(speed_ratio: f32) -> f32 {
+        let ramp = (speed_ratio - 1.0) / (Self::RAGE_FULL_RATIO - 1.0);
+        ramp.clamp(0.0, 1.0) * Self::RAGE_MAX_ALPHA
+    }
 
-            for pipe_group in &mut self.pipes {
-                pipe_group.enabled = false;
-            }
+    // Fastest pipe group on screen against the base scroll speed
+    fn speed_ratio(&self) -> f32 {
+        let speed = self
+            .sim
+            .pipes
+            .iter()
+            .filter(|pipe_group| pipe_group.alive)
+            .map(|pipe_group| pipe_group.scroll_speed)
+            .fold(self.sim.config.scroll_speed, f32::max);
+        speed / Speeds::SCROLL
+    }
+
+    // Tint for the current score milestone, cycling once past the last one
+    fn pipe_tint(score: i32) -> Color {
+        let milestone = (score.max(0) / Self::PIPE_TINT_MILESTONE) as usize;
+        Self::PIPE_TINTS[milestone % Self::PIPE_TINTS.len()]
+    }
+
+    // Bonuses can add several points at once, each gets the same time.
+    // Points taken back by a rewind are dropped so they're timed again.
+    fn record_splits(&mut self) {
+        self.splits.truncate(self.sim.score.max(0) as usize);
+        while (self.splits.len() as i32) < self.sim.score {
+            self.splits.push(self.run_time);
         }
+    }
 
-        if !self.game_over && self.bird.collides_with(&self.ground.get_collision_rect()) {
-            play_sound(&self.ground_hit_sound, PlaySoundParams {
-                volume: 1.0,
-                looped: false,
-            });
-            self.bird.kill();
-            self.bird.allow_gravity = false;
-            self.background.scroll = false;
-            self.ground.scroll = false;
+    fn save_best_times(&mut self) {
+        // A rewind since the last point can leave splits past the score
+        let reached = self.splits.len().min(self.sim.score.max(0) as usize);
+        self.records.record_splits(&self.splits[..reached]);
+    }
 
-            self.game_over = true;
-            self.pipe_generator.stop();
+    // Safe to call more than once, only what wasn't saved yet is added
+    fn save_session(&mut self) {
+        self.records.record_distance(self.sim.meters());
+        self.records.save_session();
+    }
+
+    // Small text in the top left corner, with a backing in high contrast mode
+    fn draw_hud_line(&self, text: &str, y: f32, color: Color) {
+        let size = ui::font_size(16, self.settings.high_contrast);
+        if self.settings.high_contrast {
+            let dims = measure_text(text, Some(&self.font), size, 1.0);
+            ui::draw_backing(ui::backing_rect(10.0, y, dims));
+        }
+        draw_text_ex(
+            text,
+            10.0,
+            y,
+            TextParams {
+                font: Some(&self.font),
+                font_size: size,
+                color,
+                ..Default::default()
+            },
+        );
+    }
+
+    // Tells the player the record wasn't kept instead of crashing or losing it silently
+    fn report_save_error(&mut self, error: StorageError) {
+        log::warn!("Failed to save high score: {}", error);
+        let position = vec2(self.layout().centered_x(120.0), self.layout().y_at(0.15));
+        self.floating_texts.push(FloatingText::new("SAVE FAILED", position, RED));
+    }
 
-            if self.score >= self.highscore {
-                self.highscore = self.score;
-                storage::write(self.highscore).unwrap();
+    fn collision_readout(collision: Option<CollisionInfo>) -> String {
+        match collision {
+            Some(collision) => {
+                let overlap = collision.overlap;
+                format!(
+                    "last hit: {} at ({:.0}, {:.0}) {:.0}x{:.0}",
+                    collision.kind.name(),
+                    overlap.x,
+                    overlap.y,
+                    overlap.w,
+                    overlap.h
+                )
             }
-            self.scoreboard.set_score(self.score, self.highscore);
+            None => "last hit: none".to_string(),
+        }
+    }
 
-            for pipe_group in &mut self.pipes {
-                pipe_group.enabled = false;
+    // Hitboxes and the bird's velocity, drawn in world space
+    fn draw_debug_overlay(&self) {
+        for pipe_group in self.sim.pipes.iter().filter(|pipe_group| pipe_group.alive) {
+            for hitbox in pipe_group.hitboxes() {
+                draw_rectangle_lines(hitbox.x, hitbox.y, hitbox.w, hitbox.h, 2.0, RED);
             }
         }
+
+        let bird = &self.sim.bird;
+        draw_rectangle_lines(bird.position.x, bird.position.y, Bird::FRAME_WIDTH, Bird::FRAME_HEIGHT, 2.0, LIME);
+
+        if let Some(collision) = self.sim.last_collision {
+            let overlap = collision.overlap;
+            draw_rectangle(overlap.x, overlap.y, overlap.w, overlap.h, Color::new(1.0, 0.0, 1.0, 0.6));
+        }
+
+        let start = bird.center();
+        let arrow = bird.velocity() * Self::VELOCITY_ARROW_SCALE;
+        if arrow.length() < 1.0 {
+            return;
+        }
+        let end = start + arrow;
+        draw_line(start.x, start.y, end.x, end.y, 2.0, YELLOW);
+
+        let direction = arrow.normalize();
+        let side = direction.perp() * 5.0;
+        let base = end - direction * 10.0;
+        draw_triangle(end, base + side, base - side, YELLOW);
+    }
+
+    fn update_tutorial(&mut self, dt: f32) {
+        let Some(tutorial) = &mut self.tutorial else {
+            return;
+        };
+        tutorial.update(dt);
+
+        if tutorial.is_done() {
+            self.tutorial = None;
+            // Reloaded so settings changed elsewhere aren't overwritten
+            let mut settings = Settings::load();
+            settings.tutorial_seen = true;
+            let _ = settings.save();
+            self.settings.tutorial_seen = true;
+        }
     }
-}
 
-impl Scene for GameScene {
-    fn update(&mut self) -> Transition {
-        let bird_x_fixed = self.bird.position.x;
+    fn is_death(events: &[SimEvent]) -> bool {
+        events.iter().any(|event| matches!(event, SimEvent::PipeHit | SimEvent::GroundHit))
+    }
 
-        if !self.instructions_visible {
-            self.bird.update();
-            self.bird.position.x = bird_x_fixed;
+    // Puts the round back to where it was about 1.5 seconds ago, once per run
+    fn try_rewind(&mut self) -> bool {
+        if !self.settings.rewind_assist {
+            return false;
         }
+        let Some(snapshot) = self.rewind.take() else {
+            return false;
+        };
+        self.sim.restore(&snapshot);
+        self.sim.bird.invulnerable_timer = Self::REWIND_INVULNERABILITY;
+
+        let position = self.sim.bird.position + vec2(0.0, -10.0);
+        self.floating_texts.push(FloatingText::new("REWIND!", position, SKYBLUE));
+        true
+    }
 
-        if is_mouse_button_down(MouseButton::Left) {
-            if !self.is_mouse_down {
-                let mouse_position = mouse_position().into();
+    fn record_flap(&mut self) {
+        if let Some(replay) = &mut self.replay {
+            replay.flaps.push(self.replay_frame);
+        }
+    }
 
-                if self.instructions_visible {
-                    self.start_game();
-                } else if self.game_over && self.scoreboard.button.contains(mouse_position) {
-                    self.reset();
+    // Back to the last checkpoint after a death, once per run. What follows
+    // counts as a new start for analytics, with no result until it ends.
+    fn resume_from_checkpoint(&mut self) {
+        if !self.sim.resume_from_checkpoint() {
+            return;
+        }
+        self.result = None;
+        // Snapshots from before the death would undo the continue
+        self.rewind.reset();
+        if self.replay.take().is_some() {
+            log::info!("continued from a checkpoint, this run won't be saved as a replay");
+        }
+        self.handle_events(&[SimEvent::Started]);
+        self.sim.bird.invulnerable_timer = Self::REWIND_INVULNERABILITY;
+        if let Some(background) = self.scenery.background_mut() {
+            background.set_scroll(true);
+        }
+        self.ground.scroll = true;
+        self.record_splits();
+
+        let position = self.sim.bird.position + vec2(0.0, -10.0);
+        self.floating_texts.push(FloatingText::new("CONTINUE!", position, GOLD));
+    }
+
+    // Whole FIXED_FRAME_TIME steps due once `dt` more seconds have passed,
+    // and the time carried over to the next frame
+    fn world_steps(clock: f32, dt: f32) -> (u32, f32) {
+        let clock = clock + dt.max(0.0);
+        // A hair of slack, so 1/60 s frames don't round down to no step
+        let steps = (clock / FIXED_FRAME_TIME + 1e-3).floor();
+        (steps as u32, (clock - steps * FIXED_FRAME_TIME).max(0.0))
+    }
+
+    // One frame's worth of pipes, power-ups, scoring and collisions
+    fn step_world(&mut self) {
+        if self.settings.rewind_assist && self.sim.phase() == GamePhase::Playing && self.sim.bird.alive {
+            self.rewind.record(self.sim.snapshot());
+        }
+
+        let was_alive = self.sim.bird.alive;
+        let events = self.sim.update_world();
+        self.replay_frame += 1;
+
+        if !(Self::is_death(&events) && self.try_rewind()) {
+            // Only the hit that kills, not the ground landing after a pipe hit
+            if was_alive && Self::is_death(&events) {
+                let (strength, duration) = Self::DEATH_RUMBLE;
+                self.rumble.rumble(strength, duration);
+                self.ducking.duck();
+                if self.settings.death_sound == DeathSound::Random {
+                    self.play_random_death_sound();
                 }
+            }
+            self.handle_events(&events);
+        }
+    }
+
+    // Replaces the per-hit sounds, once per death
+    fn play_random_death_sound(&self) {
+        if let Some(index) = pick_sound(self.death_sounds.len(), ::rand::random()) {
+            play_sound(&self.death_sounds[index], PlaySoundParams {
+                volume: self.settings.volume_fraction(),
+                looped: false,
+            });
+        }
+    }
+
+    // Volume for sounds that give way to the important ones
+    fn ducked_volume(&self) -> f32 {
+        self.settings.volume_fraction() * self.ducking.level()
+    }
+
+    fn sync_screen(&mut self) {
+        let layout = self.layout();
+        self.sim.screen = layout.screen;
+        self.ground.rise_offset = self.sim.ground_rise;
+        self.sim.ground_y = layout.ground_y() - self.sim.ground_rise;
+    }
+
+    // Stop the scenery when the bird dies, but keep the clouds drifting
+    fn freeze_scenery(&mut self) {
+        if let Some(background) = self.scenery.background_mut() {
+            background.set_layer_scroll(LayerScroll {
+                forest: false,
+                cityscape: false,
+                cloud: true,
+            });
+        }
+        self.ground.scroll = false;
+    }
+
+    fn handle_events(&mut self, events: &[SimEvent]) {
+        // Before the game over below raises the high score
+        analytics::report(&mut *self.analytics.borrow_mut(), &self.sim, events, self.records.highscore, self.run_time);
 
-                if self.bird.alive && !self.game_over {
+        for event in events {
+            match event {
+                // Nothing to play or show, only reported
+                SimEvent::Started => {}
+                SimEvent::Flapped => {
                     play_sound(&self.flap_sound, PlaySoundParams {
-                        volume: 1.0,
+                        volume: self.ducked_volume(),
                         looped: false,
                     });
-                    self.bird.flap();
+                    if let Some(tutorial) = &mut self.tutorial {
+                        tutorial.on_flap();
+                    }
                 }
-
-                self.is_mouse_down = true;
-            }
-        } else {
-            self.is_mouse_down = false;
-        }
-
-        if !self.game_over {
-            for pipe_group in &mut self.pipes {
-                if !pipe_group.has_scored && pipe_group.position.x + 27.0 <= self.bird.position.x {
-                    pipe_group.has_scored = true;
+                SimEvent::Scored => {
+                    self.record_splits();
                     play_sound(&self.score_sound, PlaySoundParams {
-                        volume: 1.0,
+                        volume: self.ducked_volume(),
                         looped: false,
                     });
-                    self.score += 1;
+                    if let Some(tutorial) = &mut self.tutorial {
+                        tutorial.on_score();
+                    }
+                }
+                SimEvent::Whoosh => {
+                    play_sound(&self.whoosh_sound, PlaySoundParams {
+                        volume: self.ducked_volume() * Self::WHOOSH_VOLUME,
+                        looped: false,
+                    });
+                }
+                SimEvent::Bonus(bonus) => {
+                    self.record_splits();
+                    let position = self.sim.bird.position + vec2(0.0, -10.0);
+                    let text = format!("+{}", bonus);
+                    self.floating_texts.push(FloatingText::new(&text, position, GOLD));
+                }
+                SimEvent::SmashCharged => {
+                    play_sound(&self.score_sound, PlaySoundParams {
+                        volume: self.ducked_volume(),
+                        looped: false,
+                    });
+                    let position = self.sim.bird.position + vec2(0.0, -10.0);
+                    self.floating_texts.push(FloatingText::new("SMASH +1", position, RED));
+                }
+                SimEvent::Checkpoint => {
+                    play_sound(&self.score_sound, PlaySoundParams {
+                        volume: self.ducked_volume(),
+                        looped: false,
+                    });
+                    let position = self.sim.bird.position + vec2(0.0, -10.0);
+                    self.floating_texts.push(FloatingText::new("CHECKPOINT", position, GREEN));
+                }
+                SimEvent::Smashed(position) => {
+                    play_sound(&self.pipe_hit_sound, PlaySoundParams {
+                        volume: self.ducked_volume(),
+                        looped: false,
+                    });
+                    self.floating_texts.push(FloatingText::new("SMASH!", *position, RED));
+                }
+                SimEvent::PipeHit => {
+                    if self.settings.death_sound == DeathSound::Classic {
+                        play_sound(&self.pipe_hit_sound, PlaySoundParams {
+                            volume: self.settings.volume_fraction(),
+                            looped: false,
+                        });
+                    }
+                    self.freeze_scenery();
+                }
+                SimEvent::GravityFlipped => {
+                    play_sound(&self.score_sound, PlaySoundParams {
+                        volume: self.ducked_volume(),
+                        looped: false,
+                    });
+                    let position = self.sim.bird.position + vec2(0.0, -10.0);
+                    self.floating_texts.push(FloatingText::new("FLIP!", position, VIOLET));
+                }
+                SimEvent::Resized(scale) => {
+                    play_sound(&self.score_sound, PlaySoundParams {
+                        volume: self.ducked_volume(),
+                        looped: false,
+                    });
+                    let position = self.sim.bird.position + vec2(0.0, -10.0);
+                    let text = if *scale > 1.0 { "BIG!" } else { "SMALL!" };
+                    self.floating_texts.push(FloatingText::new(text, position, ORANGE));
+                }
+                SimEvent::GroundHit => {
+                    if self.settings.death_sound == DeathSound::Classic {
+                        play_sound(&self.ground_hit_sound, PlaySoundParams {
+                            volume: self.settings.volume_fraction(),
+                            looped: false,
+                        });
+                    }
+                    self.freeze_scenery();
+                    self.finish_run();
+                }
+                SimEvent::Won => {
+                    play_sound(&self.score_sound, PlaySoundParams {
+                        volume: self.ducked_volume(),
+                        looped: false,
+                    });
+                    self.freeze_scenery();
+                    self.finish_run();
                 }
-                pipe_group.update();
             }
+        }
+    }
 
-            self.background.update();
-            self.ground.update();
+    // Ends the round as if it scored `score` and puts that on the board,
+    // beside the high score it would make. `records` is only read, so
+    // nothing is kept.
+    fn show_debug_scores(sim: &mut Simulation, records: &Records, board: &mut BoardScores, score: i32) {
+        sim.force_game_over(score);
+        board.set(score, records.highscore.max(score));
+    }
 
-            self.check_for_collisions();
+    // Debug builds only: jumps to the scoreboard with the debug_score setting,
+    // for working on its layout. Nothing is saved.
+    fn show_debug_scoreboard(&mut self) {
+        let score = self.settings.debug_score;
+        Self::show_debug_scores(&mut self.sim, &self.records, &mut self.scoreboard.scores, score);
+        self.freeze_scenery();
+        self.game_over_timer = 0.0;
+        self.scoreboard.seed = self.sim.seed();
+        self.scoreboard.run_time = self.run_time;
+        self.scoreboard.won = false;
+        self.scoreboard.best_time = self.records.best_times.best(score);
+    }
 
-            if self.pipe_generator.should_spawn_pipe() {
-                // Calculate ground position
-                let ground_y = screen_height() - 112.0; // Assuming ground height is 112px
-                
-                // Try to reuse an existing pipe group first
-                let mut spawned = false;
-                for pipe_group in &mut self.pipes {
-                    if !pipe_group.alive {
-                        pipe_group.reset(screen_width(), ground_y);
-                        spawned = true;
-                        break;
-                    }
-                }
-                
-                // If no inactive pipe was found, create a new one
-                if !spawned {
-                    let mut pipe_group = PipeGroup::new();
-                    pipe_group.reset(screen_width(), ground_y);
-                    self.pipes.push(pipe_group);
+    // Records a run that ended, by a crash or a win, and fills in the scoreboard
+    fn finish_run(&mut self) {
+        self.game_over_timer = 0.0;
+
+        let score = self.sim.score;
+        self.result = Some(GameResult::new(&self.sim, self.records.highscore, self.run_time));
+        if let Err(error) = self.records.record_score(score) {
+            self.report_save_error(error);
+        }
+        self.scoreboard.set_score(score, self.records.highscore);
+        self.scoreboard.seed = self.sim.seed();
+        self.scoreboard.run_time = self.run_time;
+        self.scoreboard.won = self.sim.has_won();
+        self.save_best_times();
+        self.scoreboard.best_time = self.records.best_times.best(score);
+        self.save_session();
+
+        if let Some(replay) = &mut self.replay {
+            replay.score = score;
+            self.records.save_replay(replay);
+        }
+    }
+}
+
+impl Scene for GameScene {
+    fn update(&mut self, dt: f32, _time_scale: f32) -> Transition {
+        if self.sim.phase() == GamePhase::Playing && self.sim.bird.alive {
+            self.sim.rise_ground(self.sim.config.ground_rise_speed * dt);
+        }
+        self.sync_screen();
+        self.sim.bird.follow_target = (self.settings.control_scheme == ControlScheme::FollowMouse)
+            .then(|| game_mouse_position().y);
+
+        if !self.console.open && self.keys.is_action_pressed(Action::ToggleDebug) {
+            self.show_debug = !self.show_debug;
+        }
+
+        let toggle_console = self.keys.is_action_pressed(Action::Console);
+        self.console.update(toggle_console, &mut self.sim.config);
+
+        // Typing into the console shouldn't flap or leave the game
+        let mouse_down = is_mouse_button_down(MouseButton::Left);
+        let clicked = mouse_down && !self.is_mouse_down && !self.console.open;
+        self.is_mouse_down = mouse_down;
+        let flap_pressed = !self.console.open && self.keys.is_action_pressed(Action::Flap);
+        let button_tapped = !self.console.open && !self.sim.is_game_over() && self.flap_button.is_tapped();
+
+        let any_input = mouse_down || !get_keys_down().is_empty() || !touches().is_empty();
+        self.idle_timer = Self::tick_idle(self.idle_timer, dt, any_input || self.sim.phase() != GamePhase::Instructions);
+
+        if clicked || flap_pressed || button_tapped {
+            let mouse_position = game_mouse_position();
+
+            match self.sim.phase() {
+                GamePhase::Instructions => self.start_game(),
+                // Only the play button restarts, so a late flap doesn't skip the scoreboard
+                GamePhase::GameOver
+                    if clicked
+                        && Self::accepts_restart(self.game_over_timer)
+                        && self.scoreboard.button.contains(mouse_position) =>
+                {
+                    self.reset();
                 }
+                _ => {}
+            }
+
+            if self.sim.flap() {
+                self.record_flap();
+                self.handle_events(&[SimEvent::Flapped]);
             }
         }
 
-        if is_key_pressed(KeyCode::Escape) {
+        if self.sim.phase() == GamePhase::Playing && !self.console.open {
+            let bird = &self.sim.bird;
+            let fall_direction = bird.fall_direction();
+            let sink = (bird.center().y - self.sim.next_gap_center()) * fall_direction;
+            let falling = bird.velocity().y * fall_direction >= 0.0;
+            if self.auto_flap.update(dt, sink, falling) && self.sim.flap() {
+                self.record_flap();
+                self.handle_events(&[SimEvent::Flapped]);
+            }
+        }
+
+        if cfg!(debug_assertions) && !self.console.open && self.keys.is_action_pressed(Action::DebugScoreboard) {
+            self.show_debug_scoreboard();
+        }
+
+        if !self.console.open && self.keys.is_action_pressed(Action::Continue) {
+            self.resume_from_checkpoint();
+        }
+
+        self.reset_fade_timer = Self::tick_fade(self.reset_fade_timer, dt);
+        if self.reset_fade_timer == 0.0 {
+            self.reset_fade_frame = None;
+        }
+        if self.sim.is_game_over() {
+            self.game_over_timer += dt;
+        }
+        self.ducking.update(dt);
+        self.update_tutorial(dt);
+
+        self.floating_texts.update(dt);
+
+        if self.sim.phase() == GamePhase::Playing && self.sim.bird.alive {
+            self.run_time += dt;
+            self.records.session.add_time(dt);
+        }
+
+        // The bird and the world move in fixed steps of FIXED_FRAME_TIME, as
+        // many as the (scaled) frame time covers, so they keep pace with the
+        // ground at any frame rate or time scale and replays and seeds play
+        // out the same everywhere
+        let steps;
+        (steps, self.world_clock) = Self::world_steps(self.world_clock, dt);
+        for _ in 0..steps {
+            self.sim.update_bird(FIXED_FRAME_TIME);
+            if !self.sim.is_game_over() {
+                self.step_world();
+            }
+        }
+
+        // The scenery moves every frame instead, by the dt the time scale
+        // already slowed, its parallax in proportion to the world's speed.
+        // Frozen layers stay put, so this also keeps clouds moving after death.
+        if let Some(background) = self.scenery.background_mut() {
+            background.set_speed_multiplier(self.sim.config.scroll_speed / Speeds::SCROLL);
+        }
+        self.scenery.update(dt);
+        self.ground.set_speed_multiplier(if self.sim.is_game_over() { 0.0 } else { 1.0 });
+        self.ground.update(dt);
+
+        if let Some(transition) = self.complete_level() {
+            return transition;
+        }
+
+        if !self.console.open && self.keys.is_action_pressed(Action::Back) {
+            // Zen runs never hit the ground, so keep their stats on the way out
+            self.save_session();
             return Transition::Pop;
         }
 
@@ -225,67 +994,173 @@ impl Scene for GameScene {
     }
 
     fn draw(&mut self) {
-        draw_texture(&self.sky_texture, 0.0, 0.0, WHITE);
-        draw_texture(&self.sky_texture, 100.0, 0.0, WHITE);
-        draw_texture(&self.sky_texture, 200.0, 0.0, WHITE);
-        draw_texture(&self.sky_texture, 300.0, 0.0, WHITE);
-        draw_texture(&self.sky_texture, 400.0, 0.0, WHITE);
-        draw_texture(&self.sky_texture, 500.0, 0.0, WHITE);
-        draw_texture(&self.sky_texture, 600.0, 0.0, WHITE);
-        draw_texture(&self.sky_texture, 700.0, 0.0, WHITE);
-        draw_texture(&self.sky_texture, 800.0, 0.0, WHITE);
-        draw_texture(&self.sky_texture, 900.0, 0.0, WHITE);
-        draw_texture(&self.sky_texture, 1000.0, 0.0, WHITE);
-        draw_texture(&self.sky_texture, 1100.0, 0.0, WHITE);
-        draw_texture(&self.sky_texture, 1200.0, 0.0, WHITE);
-
-        self.background.draw();
-
-        if self.instructions_visible {
-            // Center horizontally and position vertically using screen percentages
-            let instr_x = screen_width() / 2.0 - self.instructions.width() / 2.0;
-            let ready_x = screen_width() / 2.0 - self.get_ready.width() / 2.0;
-            
-            // Position get_ready at 25% of screen height
-            let ready_y = screen_height() * 0.25;
-            
-            // Position instructions at 60% of screen height
-            let instr_y = screen_height() * 0.6;
-        
-            draw_texture(&self.get_ready, ready_x, ready_y, WHITE);
-            draw_texture(&self.instructions, instr_x, instr_y, WHITE);
-        }
-
-        for pipe_group in &mut self.pipes {
-            pipe_group.draw(&self.pipes_texture);
+        let layout = self.layout();
+
+        self.scenery.draw(&layout);
+
+        match self.sim.phase() {
+            GamePhase::Instructions => {
+                let instr_x = layout.centered_x(self.instructions.width());
+                let ready_x = layout.centered_x(self.get_ready.width());
+                let ready_y = layout.y_at(Layout::HEADER_Y_RATIO);
+                let instr_y = layout.y_at(Layout::INSTRUCTIONS_Y_RATIO);
+
+                draw_texture(&self.get_ready, ready_x, ready_y, WHITE);
+                draw_texture(&self.instructions, instr_x, instr_y, WHITE);
+            }
+            GamePhase::Countdown => {
+                let text = (self.sim.countdown_remaining().ceil() as i32).to_string();
+                let dims = measure_text(&text, Some(&self.font), 64, 1.0);
+                draw_text_ex(
+                    &text,
+                    layout.centered_x(dims.width),
+                    layout.y_at(Layout::COUNTDOWN_Y_RATIO),
+                    TextParams {
+                        font: Some(&self.font),
+                        font_size: 64,
+                        color: WHITE,
+                        ..Default::default()
+                    },
+                );
+            }
+            GamePhase::Playing | GamePhase::GameOver => {}
+        }
+
+        self.set_world_camera();
+        let pipe_tint = Self::pipe_tint(self.sim.score);
+        for pipe_group in &self.sim.pipes {
+            // Low quality skips the extra cap draw
+            pipe_group.draw(&self.pipe_textures, pipe_tint, self.settings.quality == Quality::High);
+        }
+        for powerup in &self.sim.powerups {
+            powerup.draw();
+        }
+        letterbox::set_game_camera();
+
+        if self.settings.pipe_previews && self.sim.phase() == GamePhase::Playing && self.sim.bird.alive {
+            self.draw_pipe_previews(&layout);
         }
 
         self.ground.draw();
 
-        if !self.game_over {
-            let text = self.score.to_string();
-            let dims = measure_text(&text, Some(&self.font), 32, 1.0);
-            draw_text_ex(
+        self.set_world_camera();
+        if self.sim.phase() == GamePhase::Playing {
+            self.draw_record_marker();
+        }
+
+        self.floating_texts.draw(&self.font);
+        letterbox::set_game_camera();
+
+        // Hidden behind the scoreboard, where only the play button restarts
+        if !self.sim.is_game_over() {
+            self.flap_button.draw(&self.font);
+        }
+
+        if self.sim.is_game_over() {
+            self.scoreboard.draw();
+            if let Some(score) = self.sim.checkpoint_score().filter(|_| self.sim.can_resume()) {
+                let text = format!("{:?} TO CONTINUE FROM {}", self.keys.key(Action::Continue), score);
+                self.draw_hud_line(&text, 30.0, GOLD);
+            }
+        } else if self.sim.config.mode != GameMode::Zen
+            && let Some(digits) = &self.digits
+        {
+            let height = f32::from(ui::font_size(36, self.settings.high_contrast));
+            digits.draw_number(self.sim.score.max(0) as u32, layout.screen.x / 2.0, 12.0, height);
+        } else if self.sim.config.mode != GameMode::Zen {
+            let text = self.sim.score.to_string();
+            let size = ui::font_size(32, self.settings.high_contrast);
+            let dims = measure_text(&text, Some(&self.font), size, 1.0);
+            let y = 40.0 * size as f32 / 32.0;
+            if self.settings.high_contrast {
+                ui::draw_backing(ui::backing_rect(layout.centered_x(dims.width), y, dims));
+            }
+            draw_text_outlined(
                 &text,
-                screen_width() as f32 / 2.0 - dims.width / 2.0,
-                40.0,
+                layout.centered_x(dims.width),
+                y,
                 TextParams {
                     font: Some(&self.font),
-                    font_size: 32,
+                    font_size: size,
                     color: WHITE,
                     ..Default::default()
                 },
+                BLACK,
             );
-        } else {
-            self.scoreboard.draw();
         }
 
-        self.bird.draw();
+        if self.sim.phase() == GamePhase::Playing {
+            let text = format!("{}m  best {}m", self.sim.meters(), self.records.best_distance.max(self.sim.meters()));
+            self.draw_hud_line(&text, 30.0, WHITE);
+            if self.sim.smash_charges > 0 {
+                let charges = format!("SMASH x{}", self.sim.smash_charges);
+                let line_height = f32::from(ui::font_size(16, self.settings.high_contrast)) + 8.0;
+                self.draw_hud_line(&charges, 30.0 + line_height, RED);
+            }
+        }
+
+        self.set_world_camera();
+        self.sim.bird.draw();
+        if self.show_debug {
+            self.draw_debug_overlay();
+        }
+        letterbox::set_game_camera();
+
+        if self.show_debug {
+            draw_text(&Self::collision_readout(self.sim.last_collision), 10.0, 50.0, 18.0, MAGENTA);
+        }
+
+        if let Some(tutorial) = &self.tutorial
+            && !self.sim.is_game_over()
+        {
+            // The bird is in world space, the hint is drawn on screen
+            let bird_center = self.sim.bird.center() - vec2(self.sim.camera.x, 0.0);
+            tutorial.draw(&self.font, &layout, bird_center);
+        }
+
+        if self.settings.rage_tint && self.sim.phase() == GamePhase::Playing && self.sim.bird.alive {
+            let alpha = Self::rage_tint_alpha(self.speed_ratio());
+            if alpha > 0.0 {
+                draw_rectangle(0.0, 0.0, game_width(), game_height(), Color::new(0.9, 0.1, 0.0, alpha));
+            }
+        }
+
+        let tint_alpha = Self::gravity_tint_alpha(self.sim.gravity_flip_remaining());
+        if tint_alpha > 0.0 {
+            draw_rectangle(0.0, 0.0, game_width(), game_height(), Color::new(0.5, 0.0, 0.8, tint_alpha));
+        }
+
+        if self.is_idle() {
+            self.draw_idle_prompt(&layout);
+        }
+
+        if self.reset_fade_timer > 0.0
+            && let Some(frame) = &self.reset_fade_frame
+        {
+            let tint = Color::new(1.0, 1.0, 1.0, Self::fade_alpha(self.reset_fade_timer));
+            draw_texture_ex(&frame.texture, 0.0, 0.0, tint, DrawTextureParams {
+                dest_size: Some(vec2(game_width(), game_height())),
+                ..Default::default()
+            });
+        }
+
+        self.console.draw();
+    }
+
+    fn hidden(&mut self) {
+        let sounds = [&self.flap_sound, &self.ground_hit_sound, &self.pipe_hit_sound, &self.score_sound, &self.whoosh_sound];
+        for sound in sounds.into_iter().chain(&self.death_sounds) {
+            stop_sound(sound);
+        }
     }
 
     fn as_any(&mut self) -> &mut dyn std::any::Any {
         self
     }
+
+    fn title(&self) -> String {
+        Self::window_title(self.score())
+    }
 }
 
 /*
@@ -346,6 +1221,7 @@ mod tests {
         pub scroll: bool,
     }
 
+    #[allow(dead_code)]
     struct DummyGameScene {
         bird: DummyBird,
         instructions_visible: bool,
@@ -459,6 +1335,7 @@ The tests validate (update() logic):
 mod update_tests {
     use super::*;
 
+    #[allow(dead_code)]
     struct DummyBird {
         pub position_x: f32,
         pub alive: bool,
@@ -481,6 +1358,7 @@ mod update_tests {
         }
     }
 
+    #[allow(dead_code)]
     struct DummyPipeGroup {
         pub position_x: f32,
         pub has_scored: bool,
@@ -772,12 +1650,14 @@ mod game_tests {
     use super::*;
     use macroquad::math::Rect;
 
+    #[allow(dead_code)]
     struct DummyBird {
         rect: Rect,
         alive: bool,
         allow_gravity: bool,
     }
 
+    #[allow(dead_code)]
     impl DummyBird {
         fn new(x: f32, y: f32, w: f32, h: f32) -> Self {
             DummyBird {
@@ -821,11 +1701,13 @@ mod game_tests {
         }
     }
 
+    #[allow(dead_code)]
     struct DummyPipeGroup {
         top_rect: Rect,
         bottom_rect: Rect,
     }
 
+    #[allow(dead_code)]
     impl DummyPipeGroup {
         fn new(top: Rect, bottom: Rect) -> Self {
             DummyPipeGroup {
@@ -918,4 +1800,497 @@ mod game_tests {
         assert!(game.bird.alive, "Bird should be alive after reset");
         assert!(!game.game_over, "Game should not be over after reset");
     }
-}
\ No newline at end of file
+}
+
+/*
+
+The tests validate (Restart fade):
+1. Fade timer counts down and stops at zero
+2. Overlay alpha follows the timer
+3. Over a restart at 60 fps the old frame fades out steadily and is gone
+   once RESET_FADE_DURATION has passed
+
+*/
+
+#[cfg(test)]
+mod fade_tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn test_fade_timer_decrements() {
+        let timer = GameScene::tick_fade(GameScene::RESET_FADE_DURATION, 0.05);
+        assert_float_eq!(timer, GameScene::RESET_FADE_DURATION - 0.05, abs <= 0.001);
+
+        let timer = GameScene::tick_fade(timer, 1.0);
+        assert_float_eq!(timer, 0.0, abs <= 0.001);
+    }
+
+    #[test]
+    fn test_fade_alpha_follows_timer() {
+        assert_float_eq!(GameScene::fade_alpha(GameScene::RESET_FADE_DURATION), 1.0, abs <= 0.001);
+        assert_float_eq!(GameScene::fade_alpha(GameScene::RESET_FADE_DURATION / 2.0), 0.5, abs <= 0.001);
+        assert_float_eq!(GameScene::fade_alpha(0.0), 0.0, abs <= 0.001);
+    }
+
+    #[test]
+    fn test_crossfade_over_restart() {
+        let dt = 1.0 / 60.0;
+        let mut timer = GameScene::RESET_FADE_DURATION;
+        let mut alpha = GameScene::fade_alpha(timer);
+        assert_float_eq!(alpha, 1.0, abs <= 0.001);
+
+        let mut frames = 0;
+        while timer > 0.0 {
+            timer = GameScene::tick_fade(timer, dt);
+            let next = GameScene::fade_alpha(timer);
+            assert!(next < alpha);
+            alpha = next;
+            frames += 1;
+        }
+
+        assert_float_eq!(alpha, 0.0, abs <= 0.001);
+        // Within a frame of the duration, rounding can leave a sliver over
+        assert_float_eq!(frames as f32 * dt, GameScene::RESET_FADE_DURATION, abs <= dt + 0.001);
+    }
+}
+
+/*
+
+The tests validate (Window title):
+1. Title shows the current score
+
+*/
+
+#[cfg(test)]
+mod title_tests {
+    use super::*;
+
+    #[test]
+    fn test_title_contains_score() {
+        let title = GameScene::window_title(12);
+        assert!(title.starts_with(crate::WINDOW_TITLE));
+        assert!(title.contains("Score: 12"));
+    }
+}
+
+/*
+
+The tests validate (Best score marker):
+1. No marker without a high score or once it is beaten
+2. Marker targets the pipe that would beat the high score
+
+*/
+
+#[cfg(test)]
+mod marker_tests {
+    use super::*;
+
+    #[test]
+    fn test_no_marker_without_record_or_after_beating_it() {
+        assert_eq!(GameScene::record_pipe_index(0, 0), None);
+        assert_eq!(GameScene::record_pipe_index(11, 10), None);
+    }
+
+    #[test]
+    fn test_marker_targets_record_pipe() {
+        // Next pipe beats the record
+        assert_eq!(GameScene::record_pipe_index(10, 10), Some(0));
+        // Two pipes to go: the marker sits on the second upcoming pipe
+        assert_eq!(GameScene::record_pipe_index(9, 10), Some(1));
+        // Far away: only activates once that many pipes are on screen
+        assert_eq!(GameScene::record_pipe_index(0, 10), Some(10));
+    }
+}
+
+/*
+
+The tests validate (Gravity flip tint):
+1. No tint with normal gravity
+2. Steady tint while flipped, flickering in the last second
+
+*/
+
+#[cfg(test)]
+mod tint_tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn test_no_tint_with_normal_gravity() {
+        assert_float_eq!(GameScene::gravity_tint_alpha(0.0), 0.0, abs <= 0.001);
+    }
+
+    #[test]
+    fn test_tint_flickers_before_restore() {
+        assert_float_eq!(GameScene::gravity_tint_alpha(3.0), 0.15, abs <= 0.001);
+
+        let alphas: Vec<f32> = (1..8).map(|i| GameScene::gravity_tint_alpha(i as f32 / 8.0)).collect();
+        assert!(alphas.iter().any(|alpha| *alpha < 0.15));
+        assert!(alphas.iter().any(|alpha| *alpha >= 0.15));
+    }
+}
+
+/*
+
+The tests validate (Rage tint):
+1. No tint up to the base speed, growing with the speed ratio up to the maximum
+
+*/
+
+#[cfg(test)]
+mod rage_tint_tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn test_rage_tint_follows_speed_ratio() {
+        assert_float_eq!(GameScene::rage_tint_alpha(0.5), 0.0, abs <= 0.001);
+        assert_float_eq!(GameScene::rage_tint_alpha(1.0), 0.0, abs <= 0.001);
+
+        let halfway = 1.0 + (GameScene::RAGE_FULL_RATIO - 1.0) / 2.0;
+        assert_float_eq!(GameScene::rage_tint_alpha(halfway), GameScene::RAGE_MAX_ALPHA / 2.0, abs <= 0.001);
+        assert_float_eq!(GameScene::rage_tint_alpha(GameScene::RAGE_FULL_RATIO), GameScene::RAGE_MAX_ALPHA, abs <= 0.001);
+        assert_float_eq!(GameScene::rage_tint_alpha(3.0), GameScene::RAGE_MAX_ALPHA, abs <= 0.001);
+    }
+}
+
+/*
+
+The tests validate (Pipe tint):
+1. Pipes keep their colour below the first milestone
+2. The tint changes exactly at each milestone and cycles after the last
+
+*/
+
+#[cfg(test)]
+mod pipe_tint_tests {
+    use super::*;
+
+    #[test]
+    fn test_pipe_tint_before_first_milestone() {
+        assert_eq!(GameScene::pipe_tint(0), WHITE);
+        assert_eq!(GameScene::pipe_tint(24), WHITE);
+        assert_eq!(GameScene::pipe_tint(-5), WHITE);
+    }
+
+    #[test]
+    fn test_pipe_tint_at_milestones() {
+        assert_eq!(GameScene::pipe_tint(25), GameScene::PIPE_TINTS[1]);
+        assert_eq!(GameScene::pipe_tint(49), GameScene::PIPE_TINTS[1]);
+        assert_eq!(GameScene::pipe_tint(50), GameScene::PIPE_TINTS[2]);
+        assert_eq!(GameScene::pipe_tint(74), GameScene::PIPE_TINTS[2]);
+        assert_eq!(GameScene::pipe_tint(75), WHITE);
+    }
+}
+
+/*
+
+The tests validate (Collision readout):
+1. The debug readout names what was hit and where
+
+*/
+
+#[cfg(test)]
+mod collision_readout_tests {
+    use super::*;
+    use crate::systems::physics::CollisionKind;
+
+    #[test]
+    fn test_collision_readout() {
+        assert_eq!(GameScene::collision_readout(None), "last hit: none");
+
+        let collision = CollisionInfo {
+            kind: CollisionKind::TopPipe,
+            overlap: Rect::new(120.0, 80.0, 34.0, 5.0),
+        };
+        assert_eq!(GameScene::collision_readout(Some(collision)), "last hit: top pipe at (120, 80) 34x5");
+    }
+}
+
+/*
+
+The tests validate (Game result):
+1. After a death the result holds the run's score, seed and whether it beat the high score
+
+*/
+
+#[cfg(test)]
+mod result_tests {
+    use super::*;
+
+    #[test]
+    fn test_result_after_death() {
+        let mut sim = Simulation::headless(4);
+        sim.step(true);
+        sim.score = 12;
+        while sim.bird.alive {
+            sim.step(false);
+        }
+
+        let result = GameResult::new(&sim, 10, 3.5);
+        assert_eq!(result.score, 12);
+        assert!(result.new_high);
+        assert_eq!(result.seed, 4);
+        assert_eq!(result.duration, 3.5);
+
+        assert!(!GameResult::new(&sim, 12, 3.5).new_high);
+        assert!(!GameResult::new(&sim, 40, 3.5).new_high);
+    }
+}
+
+/*
+
+The tests validate (Score):
+1. The score the scene reports counts the pipe groups flown through headlessly,
+   and finishing the run raises the reported high score to it.
+   GameScene needs a window for its textures, fonts and sounds, so this reads
+   them through RunScores, which score() and highscore() delegate to.
+
+*/
+
+#[cfg(test)]
+mod score_tests {
+    use super::*;
+
+    #[test]
+    fn test_score_counts_pipes_passed() {
+        // The scene's score is the simulation's, flown here without a window
+        let mut sim = Simulation::headless(6);
+        sim.step(true);
+        sim.bird.invulnerable_timer = f32::MAX;
+        sim.bird.allow_gravity = false;
+
+        let mut passed = 0;
+        let mut bonus = 0;
+        for _ in 0..600 {
+            for event in sim.step(false) {
+                match event {
+                    SimEvent::Scored => passed += 1,
+                    SimEvent::Bonus(points) => bonus += points,
+                    _ => {}
+                }
+            }
+        }
+        assert!(passed >= 3, "only {} pipes passed", passed);
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut records = Records::load_from(Some(dir.path().to_path_buf()));
+        records.persist = false;
+        let scores = RunScores { sim: &sim, records: &records };
+        assert_eq!(scores.score(), passed + bonus);
+        assert_eq!(scores.highscore(), 0);
+
+        // What finish_run does with the score
+        records.record_score(sim.score).unwrap();
+        let scores = RunScores { sim: &sim, records: &records };
+        assert_eq!(scores.highscore(), passed + bonus);
+    }
+}
+
+/*
+
+The tests validate (World steps):
+1. One world step per frame at 60 FPS, every other frame at 120 FPS or half
+   speed and two per frame at 30 FPS or double speed
+2. Over one second the ground and the pipes scroll the same distance at 30
+   and at 144 FPS
+3. The bird falls with the time scale: half the fall speed gained per real
+   second at 0.5, twice at 2
+
+*/
+
+#[cfg(test)]
+mod world_step_tests {
+    use super::*;
+    use crate::prefabs::pipes::PipeGroup;
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn test_world_steps_follow_frame_time() {
+        assert_eq!(GameScene::world_steps(0.0, 1.0 / 60.0).0, 1);
+        assert_eq!(GameScene::world_steps(0.0, 1.0 / 30.0).0, 2);
+
+        // 120 FPS, or 60 FPS at half speed
+        let (steps, clock) = GameScene::world_steps(0.0, 1.0 / 120.0);
+        assert_eq!(steps, 0);
+        assert_float_eq!(clock, 1.0 / 120.0, abs <= 0.0001);
+        let (steps, clock) = GameScene::world_steps(clock, 1.0 / 120.0);
+        assert_eq!(steps, 1);
+        assert_float_eq!(clock, 0.0, abs <= 0.0001);
+
+        assert_eq!(GameScene::world_steps(0.0, 0.0).0, 0);
+    }
+
+    // How far the ground and a pipe move in one second at `fps`, stepped
+    // the way GameScene::update steps them
+    fn scroll_over_one_second(fps: u32) -> (f32, f32) {
+        let mut sim = Simulation::headless(1);
+        sim.config.countdown_duration = 0.0;
+        sim.config.collisions = false;
+        sim.start();
+        let mut pipe_group = PipeGroup::new();
+        pipe_group.alive = true;
+        pipe_group.enabled = true;
+        pipe_group.position.x = sim.screen.x;
+        sim.pipes.push(pipe_group);
+
+        let dt = 1.0 / fps as f32;
+        let mut clock = 0.0;
+        let mut ground_pos = 0.0;
+        for _ in 0..fps {
+            let steps;
+            (steps, clock) = GameScene::world_steps(clock, dt);
+            for _ in 0..steps {
+                sim.update_world();
+            }
+            ground_pos = Ground::scrolled(ground_pos, sim.config.scroll_speed, dt, f32::INFINITY);
+        }
+        (-ground_pos, sim.screen.x - sim.pipes[0].position.x)
+    }
+
+    // Fall speed the bird gains over one real second at 60 FPS, stepped the
+    // way GameScene::update steps it, with the dt the time scale scaled
+    fn fall_speed_after_one_second(time_scale: f32) -> f32 {
+        let mut sim = Simulation::headless(1);
+        sim.config.countdown_duration = 0.0;
+        sim.start();
+        sim.bird.allow_gravity = true;
+
+        let dt = time_scale / 60.0;
+        let mut clock = 0.0;
+        for _ in 0..60 {
+            let steps;
+            (steps, clock) = GameScene::world_steps(clock, dt);
+            for _ in 0..steps {
+                sim.update_bird(FIXED_FRAME_TIME);
+            }
+        }
+        sim.bird.velocity().y
+    }
+
+    #[test]
+    fn test_bird_follows_time_scale() {
+        let normal = fall_speed_after_one_second(1.0);
+        assert!(normal > 0.0);
+        assert_float_eq!(fall_speed_after_one_second(0.5), normal * 0.5, abs <= 0.01);
+        assert_float_eq!(fall_speed_after_one_second(2.0), normal * 2.0, abs <= 0.01);
+    }
+
+    #[test]
+    fn test_ground_and_pipes_keep_pace() {
+        for fps in [30, 144] {
+            let (ground, pipes) = scroll_over_one_second(fps);
+            assert_float_eq!(ground, Speeds::SCROLL, abs <= 0.01);
+            // The world can be up to one step behind, still owed to the next frame
+            assert_float_eq!(pipes, ground, abs <= Speeds::distance(Speeds::SCROLL, FIXED_FRAME_TIME) + 0.01);
+        }
+    }
+}
+
+/*
+
+The tests validate (Game over dead zone):
+1. A restart click right after the death is ignored, one after the dead zone is accepted
+
+*/
+
+#[cfg(test)]
+mod dead_zone_tests {
+    use super::*;
+
+    #[test]
+    fn test_restart_waits_out_dead_zone() {
+        let mut timer = 0.0;
+        assert!(!GameScene::accepts_restart(timer));
+
+        // A few frames in, still inside the dead zone
+        for _ in 0..10 {
+            timer += 1.0 / 60.0;
+        }
+        assert!(!GameScene::accepts_restart(timer));
+
+        for _ in 0..30 {
+            timer += 1.0 / 60.0;
+        }
+        assert!(GameScene::accepts_restart(timer));
+    }
+}
+
+/*
+
+The tests validate (Idle dimming):
+1. Input resets the idle timer
+2. Going past the threshold without input counts as idle
+
+*/
+
+#[cfg(test)]
+mod idle_tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn test_input_resets_idle_timer() {
+        let timer = GameScene::tick_idle(12.0, 1.0 / 60.0, true);
+        assert_float_eq!(timer, 0.0, abs <= 0.001);
+
+        let timer = GameScene::tick_idle(12.0, 0.5, false);
+        assert_float_eq!(timer, 12.5, abs <= 0.001);
+    }
+
+    #[test]
+    fn test_idle_after_threshold() {
+        let mut timer = 0.0;
+        for _ in 0..60 {
+            timer = GameScene::tick_idle(timer, 0.25, false);
+        }
+        assert!(!GameScene::idle_after(timer));
+
+        while timer < GameScene::IDLE_THRESHOLD {
+            timer = GameScene::tick_idle(timer, 0.25, false);
+        }
+        assert!(GameScene::idle_after(timer));
+
+        timer = GameScene::tick_idle(timer, 0.25, true);
+        assert!(!GameScene::idle_after(timer));
+    }
+}
+
+/*
+
+The tests validate (Debug scoreboard):
+1. The debug jump ends the round with the fake score and shows it on the
+   scoreboard, without touching the stored records
+
+*/
+
+#[cfg(test)]
+mod debug_scoreboard_tests {
+    use super::*;
+
+    #[test]
+    fn test_debug_scoreboard_shows_fake_score() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut records = Records::load_from(Some(dir.path().to_path_buf()));
+        records.record_score(10).unwrap();
+        let files = std::fs::read_dir(dir.path()).unwrap().count();
+
+        let mut sim = Simulation::headless(1);
+        sim.step(true);
+        let mut board = BoardScores::default();
+        GameScene::show_debug_scores(&mut sim, &records, &mut board, 42);
+        assert!(sim.is_game_over());
+        assert_eq!(sim.score, 42);
+        assert_eq!(board, BoardScores { score: 42, highscore: 42 });
+
+        // A fake score under the real high score leaves it as it was
+        let mut sim = Simulation::headless(1);
+        GameScene::show_debug_scores(&mut sim, &records, &mut board, 3);
+        assert_eq!(board, BoardScores { score: 3, highscore: 10 });
+
+        assert_eq!(records.highscore, 10);
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), files);
+        assert_eq!(Records::load_from(Some(dir.path().to_path_buf())).highscore, 10);
+    }
+}
@@ -0,0 +1,84 @@
+use macroquad::prelude::*;
+use crate::scenes::{Scene, Transition};
+use crate::systems::keybindings::{Action, KeyBindings};
+use crate::systems::letterbox::{game_height, game_width};
+
+// Shown between two levels of the levels mode. The game underneath is
+// already set up for the next level and carries on once this is dismissed.
+pub struct LevelCompleteScene {
+    keys: KeyBindings,
+    // Number of the level just finished, 1 for the first
+    completed: usize,
+    // Pipes to pass in the next level
+    next_goal: i32,
+    // Click held since the game, waits for a fresh one
+    is_mouse_down: bool,
+}
+
+impl LevelCompleteScene {
+    pub fn new(completed: usize, next_goal: i32) -> Self {
+        LevelCompleteScene {
+            keys: KeyBindings::load(),
+            completed,
+            next_goal,
+            is_mouse_down: true,
+        }
+    }
+
+    // Heading, then what the next level asks for
+    pub fn lines(completed: usize, next_goal: i32) -> (String, String) {
+        (
+            format!("LEVEL {} COMPLETE", completed),
+            format!("LEVEL {}: PASS {} PIPES", completed + 1, next_goal),
+        )
+    }
+}
+
+impl Scene for LevelCompleteScene {
+    fn update(&mut self, _dt: f32, _time_scale: f32) -> Transition {
+        let mouse_down = is_mouse_button_down(MouseButton::Left);
+        let clicked = mouse_down && !self.is_mouse_down;
+        self.is_mouse_down = mouse_down;
+
+        if clicked || self.keys.is_action_pressed(Action::Flap) || self.keys.is_action_pressed(Action::Back) {
+            return Transition::Pop;
+        }
+        Transition::None
+    }
+
+    fn draw(&mut self) {
+        draw_rectangle(0.0, 0.0, game_width(), game_height(), Color::new(0.31, 0.75, 0.79, 1.0));
+
+        let center_x = game_width() / 2.0;
+        let y = game_height() * 0.35;
+        let (heading, next) = Self::lines(self.completed, self.next_goal);
+        let hint = format!("Press {:?} or click to continue", self.keys.key(Action::Flap));
+        for (text, offset, size, color) in [(&heading, 0.0, 40, GOLD), (&next, 60.0, 24, WHITE), (&hint, 110.0, 20, WHITE)] {
+            let width = measure_text(text, None, size, 1.0).width;
+            draw_text(text, center_x - width / 2.0, y + offset, size as f32, color);
+        }
+    }
+
+    fn as_any(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+/*
+
+The tests validate :
+1. The screen names the finished level and the next one's goal
+
+*/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lines() {
+        let (heading, next) = LevelCompleteScene::lines(1, 8);
+        assert_eq!(heading, "LEVEL 1 COMPLETE");
+        assert_eq!(next, "LEVEL 2: PASS 8 PIPES");
+    }
+}
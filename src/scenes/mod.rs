@@ -1,17 +1,44 @@
 pub mod game;
+pub mod level_complete;
+pub mod settings;
 pub mod title;
 use std::any::Any;
+use crate::WINDOW_TITLE;
 
 /// A trait representing a game scene.
 pub trait Scene {
     /// Updates the scene. Returns a `Transition` to indicate what to do next.
-    fn update(&mut self) -> Transition;
+    /// `dt` is the frame time in seconds, already scaled by `time_scale`,
+    /// the debugging speed-up or slow-down (1 = normal speed).
+    fn update(&mut self, dt: f32, time_scale: f32) -> Transition;
 
     /// Draws the scene.
     fn draw(&mut self);
 
     fn as_any(&mut self) -> &mut dyn Any;
 
+    /// Type name of the scene without its module path, e.g. "GameScene".
+    fn name(&self) -> &'static str {
+        let full = std::any::type_name::<Self>();
+        full.rsplit("::").next().unwrap_or(full)
+    }
+
+    /// Title to show for the window while this scene is active.
+    fn title(&self) -> String {
+        WINDOW_TITLE.to_string()
+    }
+
+    /// Whether the scene changed since it was last drawn. Static scenes
+    /// return false once drawn, and the game loop keeps showing that frame
+    /// from a texture instead of drawing them again.
+    fn needs_redraw(&self) -> bool {
+        true
+    }
+
+    /// Called once when the game stops being visible. macroquad can't pause
+    /// a sound, so scenes stop whatever they have playing.
+    fn hidden(&mut self) {}
+
     // Add async versions of update and draw that may be used in the future
     /* 
     fn update_async(&mut self) -> TransitionFuture {
@@ -35,4 +62,7 @@ pub enum Transition {
 
     /// Pop the current scene off the stack.
     Pop,
+
+    /// Push a new scene on top of the current one.
+    Push(Box<dyn Scene>),
 }
@@ -0,0 +1,229 @@
+use macroquad::prelude::*;
+use crate::scenes::{Scene, Transition};
+use crate::systems::keybindings::{Action, KeyBindings};
+use crate::systems::letterbox::{game_height, game_width};
+use crate::systems::settings::Settings;
+
+pub struct SettingsScene {
+    settings: Settings,
+    keys: KeyBindings,
+    // Changed since the last draw, nothing moves in between
+    dirty: bool,
+}
+
+impl SettingsScene {
+    pub fn new() -> Self {
+        SettingsScene {
+            settings: Settings::load(),
+            keys: KeyBindings::load(),
+            dirty: true,
+        }
+    }
+
+    fn frame_drawn(&mut self) {
+        self.dirty = false;
+    }
+
+    // Volume steps requested this frame from the arrow keys and mouse wheel
+    fn volume_steps(wheel_y: f32, left: bool, right: bool) -> i32 {
+        let mut steps = 0;
+        if wheel_y > 0.0 {
+            steps += 1;
+        } else if wheel_y < 0.0 {
+            steps -= 1;
+        }
+        if right {
+            steps += 1;
+        }
+        if left {
+            steps -= 1;
+        }
+        steps
+    }
+}
+
+impl Scene for SettingsScene {
+    fn update(&mut self, _dt: f32, _time_scale: f32) -> Transition {
+        let steps = Self::volume_steps(
+            mouse_wheel().1,
+            self.keys.is_action_pressed(Action::VolumeDown),
+            self.keys.is_action_pressed(Action::VolumeUp),
+        );
+
+        let mut changed = steps != 0 && self.settings.adjust_volume(steps);
+        if self.keys.is_action_pressed(Action::ToggleQuality) {
+            self.settings.quality = self.settings.quality.toggled();
+            changed = true;
+        }
+        if self.keys.is_action_pressed(Action::InvertControls) {
+            self.settings.inverted_controls = !self.settings.inverted_controls;
+            changed = true;
+        }
+        if self.keys.is_action_pressed(Action::ToggleRewind) {
+            self.settings.rewind_assist = !self.settings.rewind_assist;
+            changed = true;
+        }
+        if self.keys.is_action_pressed(Action::FlapAssist) {
+            self.settings.flap_assist = self.settings.flap_assist.cycled();
+            changed = true;
+        }
+        if self.keys.is_action_pressed(Action::ToggleRumble) {
+            self.settings.rumble = !self.settings.rumble;
+            changed = true;
+        }
+        if self.keys.is_action_pressed(Action::HighContrast) {
+            self.settings.high_contrast = !self.settings.high_contrast;
+            changed = true;
+        }
+        if self.keys.is_action_pressed(Action::MinimalBackground) {
+            self.settings.minimal_background = !self.settings.minimal_background;
+            changed = true;
+        }
+        if self.keys.is_action_pressed(Action::PipePreviews) {
+            self.settings.pipe_previews = !self.settings.pipe_previews;
+            changed = true;
+        }
+
+        if changed {
+            self.dirty = true;
+            // Nothing else to do if the file can't be written, the change still applies
+            let _ = self.settings.save();
+        }
+
+        if self.keys.is_action_pressed(Action::Back) {
+            return Transition::Pop;
+        }
+
+        Transition::None
+    }
+
+    fn draw(&mut self) {
+        // Only the game area, the letterbox bars stay black
+        draw_rectangle(0.0, 0.0, game_width(), game_height(), Color::new(0.31, 0.75, 0.79, 1.0));
+
+        let center_x = game_width() / 2.0;
+        let y = game_height() * 0.2;
+
+        draw_text("Settings", center_x - 70.0, y, 40.0, WHITE);
+
+        let label = format!("Volume: {}%", self.settings.volume);
+        draw_text(&label, center_x - 90.0, y + 60.0, 30.0, WHITE);
+
+        // Volume bar
+        let bar_width = 200.0;
+        let bar_x = center_x - bar_width / 2.0;
+        draw_rectangle(bar_x, y + 80.0, bar_width, 16.0, DARKGRAY);
+        draw_rectangle(bar_x, y + 80.0, bar_width * self.settings.volume_fraction(), 16.0, WHITE);
+
+        draw_text("Left/Right or mouse wheel to adjust", center_x - 150.0, y + 140.0, 20.0, WHITE);
+
+        let quality = format!(
+            "Quality: {} ({:?} to toggle)",
+            self.settings.quality.name(),
+            self.keys.key(Action::ToggleQuality)
+        );
+        draw_text(&quality, center_x - 150.0, y + 180.0, 20.0, WHITE);
+
+        let inverted = format!(
+            "Invert controls: {} ({:?} to toggle)",
+            if self.settings.inverted_controls { "on" } else { "off" },
+            self.keys.key(Action::InvertControls)
+        );
+        draw_text(&inverted, center_x - 150.0, y + 210.0, 20.0, WHITE);
+
+        let rewind = format!(
+            "Rewind on death: {} ({:?} to toggle)",
+            if self.settings.rewind_assist { "on" } else { "off" },
+            self.keys.key(Action::ToggleRewind)
+        );
+        draw_text(&rewind, center_x - 150.0, y + 240.0, 20.0, WHITE);
+
+        let rumble = format!(
+            "Gamepad rumble: {} ({:?} to toggle)",
+            if self.settings.rumble { "on" } else { "off" },
+            self.keys.key(Action::ToggleRumble)
+        );
+        draw_text(&rumble, center_x - 150.0, y + 270.0, 20.0, WHITE);
+
+        let high_contrast = format!(
+            "High contrast: {} ({:?} to toggle)",
+            if self.settings.high_contrast { "on" } else { "off" },
+            self.keys.key(Action::HighContrast)
+        );
+        draw_text(&high_contrast, center_x - 150.0, y + 300.0, 20.0, WHITE);
+
+        let minimal = format!(
+            "Plain background: {} ({:?} to toggle)",
+            if self.settings.minimal_background { "on" } else { "off" },
+            self.keys.key(Action::MinimalBackground)
+        );
+        draw_text(&minimal, center_x - 150.0, y + 330.0, 20.0, WHITE);
+
+        let previews = format!(
+            "Pipe previews: {} ({:?} to toggle)",
+            if self.settings.pipe_previews { "on" } else { "off" },
+            self.keys.key(Action::PipePreviews)
+        );
+        draw_text(&previews, center_x - 150.0, y + 360.0, 20.0, WHITE);
+
+        let assist = format!(
+            "Auto-flap assist: {} ({:?} to change)",
+            self.settings.flap_assist.name(),
+            self.keys.key(Action::FlapAssist)
+        );
+        draw_text(&assist, center_x - 150.0, y + 390.0, 20.0, WHITE);
+
+        draw_text("Esc to go back", center_x - 60.0, y + 430.0, 20.0, WHITE);
+        self.frame_drawn();
+    }
+
+    fn needs_redraw(&self) -> bool {
+        self.dirty
+    }
+
+    fn as_any(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+/*
+
+The tests validate :
+1. Mouse wheel direction maps to volume steps
+2. Arrow keys map to volume steps and combine with the wheel
+3. The static screen needs no redraw after its first frame, until a setting changes
+
+*/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wheel_steps() {
+        assert_eq!(SettingsScene::volume_steps(1.0, false, false), 1);
+        assert_eq!(SettingsScene::volume_steps(-3.0, false, false), -1);
+        assert_eq!(SettingsScene::volume_steps(0.0, false, false), 0);
+    }
+
+    #[test]
+    fn test_arrow_key_steps() {
+        assert_eq!(SettingsScene::volume_steps(0.0, false, true), 1);
+        assert_eq!(SettingsScene::volume_steps(0.0, true, false), -1);
+        assert_eq!(SettingsScene::volume_steps(1.0, false, true), 2);
+    }
+
+    #[test]
+    fn test_no_redraw_once_drawn() {
+        let mut scene = SettingsScene::new();
+        assert!(scene.needs_redraw());
+
+        scene.frame_drawn();
+        assert!(!scene.needs_redraw());
+
+        scene.dirty = true;
+        assert!(scene.needs_redraw());
+        scene.frame_drawn();
+        assert!(!scene.needs_redraw());
+    }
+}
@@ -1,54 +1,154 @@
 use macroquad::prelude::*;
-use crate::prefabs::background::Background;
+use crate::prefabs::background::Scenery;
 use crate::prefabs::button::Button;
 use crate::prefabs::ground::Ground;
-use crate::scenes::{game::GameScene, Scene, Transition};
+use crate::prefabs::scoreboard::format_run_time;
+use crate::prefabs::ticker::Ticker;
+use crate::scenes::{game::GameScene, settings::SettingsScene, Scene, Transition};
+use crate::systems::analytics::SharedAnalytics;
+use crate::systems::best_times::BestTimes;
+use crate::systems::config::GameMode;
+use crate::systems::keybindings::{Action, KeyBindings};
+use crate::systems::layout::Layout;
+use crate::systems::records::Records;
+use crate::systems::letterbox::{game_height, game_mouse_position, game_width};
+use crate::systems::resources::resource_path;
+use crate::systems::settings::Settings;
 
 pub struct TitleScene {
-    sky_texture: Option<Texture2D>,
     title: Option<Texture2D>,
     bird: Option<Texture2D>,
-    background: Option<Background>,
+    scenery: Option<Scenery>,
     ground: Option<Ground>,
     button: Option<Button>,
+    // Records scrolling along the bottom
+    ticker: Option<Ticker>,
     loading: bool,
     loading_game: bool,
+    settings: Settings,
+    keys: KeyBindings,
+    // Index into GameMode::ALL
+    selected_mode: usize,
+    // Digits typed for a shared seed, blank for a random layout
+    seed_input: String,
 }
 
 impl TitleScene {
     pub fn new() -> Self {
+        let settings = Settings::load();
+        let selected_mode = GameMode::ALL.iter().position(|mode| *mode == settings.mode).unwrap_or(0);
+
         TitleScene {
-            sky_texture: None,
             title: None,
             bird: None,
-            background: None,
+            scenery: None,
             ground: None,
             button: None,
+            ticker: None,
             loading: true,
             loading_game: false,
+            settings,
+            keys: KeyBindings::load(),
+            selected_mode,
+            seed_input: String::new(),
+        }
+    }
+
+    // u64::MAX has 20 digits
+    const MAX_SEED_DIGITS: usize = 20;
+
+    // None for a blank or unusable seed, the game then picks a random one
+    pub fn parse_seed(input: &str) -> Option<u64> {
+        input.trim().parse().ok()
+    }
+
+    fn update_seed_input(&mut self) {
+        while let Some(character) = get_char_pressed() {
+            if character.is_ascii_digit() && self.seed_input.len() < Self::MAX_SEED_DIGITS {
+                self.seed_input.push(character);
+            }
+        }
+        if is_key_pressed(KeyCode::Backspace) {
+            self.seed_input.pop();
+        }
+    }
+
+    // Best times shown for at most this many scores, the highest ones
+    const TICKER_BEST_TIMES: usize = 3;
+
+    // The records kept in storage, one after another
+    pub fn ticker_text(highscore: i32, best_distance: i32, best_times: &BestTimes) -> String {
+        if highscore <= 0 && best_distance <= 0 {
+            return "NO RECORDS YET - BE THE FIRST".to_string();
+        }
+
+        let mut entries = vec![format!("HIGH SCORE {}", highscore), format!("BEST DISTANCE {}m", best_distance)];
+        let fastest: Vec<String> = best_times
+            .entries()
+            .rev()
+            .take(Self::TICKER_BEST_TIMES)
+            .map(|(score, time)| format!("FASTEST TO {} {}", score, format_run_time(time)))
+            .collect();
+        entries.extend(fastest.into_iter().rev());
+        entries.join("   *   ")
+    }
+
+    // Build shown in the corner, to quote in bug reports
+    pub fn version_text(version: &str, git_hash: Option<&str>) -> String {
+        format!("v{} ({})", version, git_hash.unwrap_or("unknown"))
+    }
+
+    // Moves the selection one entry up or down, wrapping around at the ends
+    fn navigate(index: usize, len: usize, up: bool, down: bool) -> usize {
+        if len == 0 {
+            return 0;
         }
+        let mut index = index % len;
+        if up {
+            index = (index + len - 1) % len;
+        }
+        if down {
+            index = (index + 1) % len;
+        }
+        index
+    }
+
+    pub fn selected_mode(&self) -> GameMode {
+        GameMode::ALL[self.selected_mode]
+    }
+
+    // Returns true if the selection changed
+    fn select_mode(&mut self, up: bool, down: bool) -> bool {
+        let selected = Self::navigate(self.selected_mode, GameMode::ALL.len(), up, down);
+        let changed = selected != self.selected_mode;
+        self.selected_mode = selected;
+        changed
     }
     
     pub async fn load_assets(&mut self) {
         if self.loading {
             // Load textures
-            self.sky_texture = Some(load_texture("resources/sky.png").await.unwrap_or(Texture2D::empty()));
-            self.title = Some(load_texture("resources/title.png").await.unwrap_or(Texture2D::empty()));
-            self.bird = Some(load_texture("resources/bird.png").await.unwrap_or(Texture2D::empty()));
+            self.title = Some(load_texture(&resource_path("title.png")).await.unwrap_or(Texture2D::empty()));
+            self.bird = Some(load_texture(&resource_path("bird.png")).await.unwrap_or(Texture2D::empty()));
             
             // Initialize components
-            self.background = Some(Background::new().await);
+            self.scenery = Some(Scenery::load(&self.settings).await);
             self.ground = Some(Ground::new().await);
             self.button = Some(Button::new().await);
 
+            let records = Records::load();
+            let text = Self::ticker_text(records.highscore, records.best_distance, &records.best_times);
+            self.ticker = Some(Ticker::new(&text));
+
             self.loading = false;
         }
     }
     
-    pub async fn load_game_scene(&mut self) -> Option<Box<dyn Scene>> {
+    pub async fn load_game_scene(&mut self, analytics: SharedAnalytics) -> Option<Box<dyn Scene>> {
         if self.loading_game {
             // Add async loading indicator
-            let game_scene = GameScene::new().await;
+            let seed = Self::parse_seed(&self.seed_input);
+            let game_scene = GameScene::new(self.selected_mode(), seed, analytics).await;
             self.loading_game = false;
             Some(Box::new(game_scene))
         } else {
@@ -66,32 +166,49 @@ impl TitleScene {
 }
 
 impl Scene for TitleScene {
-    fn update(&mut self) -> Transition {
+    fn update(&mut self, dt: f32, _time_scale: f32) -> Transition {
         // If still loading assets or transitioning to game, do nothing
         if self.loading || self.loading_game {
             return Transition::None;
         }
-        
+
+        if self.select_mode(
+            self.keys.is_action_pressed(Action::MenuUp),
+            self.keys.is_action_pressed(Action::MenuDown),
+        ) {
+            // Remember the choice for next time, a failed write only loses that
+            // Reloaded first so changes made in the settings scene aren't overwritten
+            self.settings = Settings::load();
+            self.settings.mode = self.selected_mode();
+            let _ = self.settings.save();
+        }
+        self.update_seed_input();
+
         // Safe unwraps since we've ensured loading is complete
-        let background = self.background.as_mut().unwrap();
+        let scenery = self.scenery.as_mut().unwrap();
         // println!("Background created");
         let ground = self.ground.as_mut().unwrap();
         // println!("Ground created");
         let button = self.button.as_ref().unwrap();
         // println!("Button created");
         
-        background.update();
-        ground.update();
+        scenery.update(dt);
+        ground.update(dt);
+        if let Some(ticker) = &mut self.ticker {
+            ticker.update(dt);
+        }
         
         // Convert mouse position to Vec2
-        let mouse_position = Vec2::new(mouse_position().0, mouse_position().1);
+        let mouse_position = game_mouse_position();
         
         if is_mouse_button_down(MouseButton::Left) && button.contains(mouse_position) {
             // Start loading the game scene
             self.loading_game = true;
             // Return None for now, the main loop will handle the transition
             return Transition::None;
-        } else if is_key_pressed(KeyCode::Escape) {
+        } else if self.keys.is_action_pressed(Action::Settings) {
+            return Transition::Push(Box::new(SettingsScene::new()));
+        } else if self.keys.is_action_pressed(Action::Back) {
             return Transition::Pop;
         }
         
@@ -102,54 +219,66 @@ impl Scene for TitleScene {
         // Show loading screen if still loading assets
         if self.loading {
             clear_background(BLACK);
-            draw_text("Loading...", screen_width() / 2.0 - 50.0, screen_height() / 2.0, 30.0, WHITE);
+            draw_text("Loading...", game_width() / 2.0 - 50.0, game_height() / 2.0, 30.0, WHITE);
             return;
         }
         
         // Show game loading screen if transitioning to game
         if self.loading_game {
             clear_background(BLACK);
-            draw_text("Starting game...", screen_width() / 2.0 - 80.0, screen_height() / 2.0, 30.0, WHITE);
+            draw_text("Starting game...", game_width() / 2.0 - 80.0, game_height() / 2.0, 30.0, WHITE);
             return;
         }
         
         // Safe unwraps since we've ensured loading is complete
-        let sky = self.sky_texture.as_ref().unwrap();
+        let layout = Layout::current(self.ground.as_ref().unwrap().height());
         let title = self.title.as_ref().unwrap();
         let bird = self.bird.as_ref().unwrap();
-        let background = self.background.as_ref().unwrap();
+        let scenery = self.scenery.as_ref().unwrap();
         let ground = self.ground.as_ref().unwrap();
         let button = self.button.as_ref().unwrap();
         
         // Draw everything
-        draw_texture(sky, 0.0, 0.0, WHITE);
-        draw_texture(sky, 100.0, 0.0, WHITE);
-        draw_texture(sky, 200.0, 0.0, WHITE);
-        draw_texture(sky, 300.0, 0.0, WHITE);
-        draw_texture(sky, 400.0, 0.0, WHITE);
-        draw_texture(sky, 500.0, 0.0, WHITE);
-        draw_texture(sky, 600.0, 0.0, WHITE);
-        draw_texture(sky, 700.0, 0.0, WHITE);
-        draw_texture(sky, 800.0, 0.0, WHITE);
-        draw_texture(sky, 900.0, 0.0, WHITE);
-        draw_texture(sky, 1000.0, 0.0, WHITE);
-        draw_texture(sky, 1100.0, 0.0, WHITE);
-        draw_texture(sky, 1200.0, 0.0, WHITE);
-        background.draw();
+        scenery.draw(&layout);
         ground.draw();
         
         // Center the title horizontally and place 25% from top
-        let title_x = screen_width() / 2.0 - title.width() / 2.0;
-        let title_y = screen_height() * 0.25;
+        let title_x = layout.centered_x(title.width());
+        let title_y = layout.y_at(Layout::HEADER_Y_RATIO);
         draw_texture(title, title_x, title_y, WHITE);
 
         // Center the bird horizontally and vertically with offset
-        let bird_x = screen_width() / 2.0 - bird.width() / 2.0;
-        let bird_y = screen_height() / 2.0 - bird.height() / 2.0 - 60.0;
+        let bird_x = layout.centered_x(bird.width());
+        let bird_y = game_height() / 2.0 - bird.height() / 2.0 - 60.0;
         draw_texture(bird, bird_x, bird_y, WHITE);
 
 
         button.draw();
+
+        // Mode menu, listed bottom-left above the settings hint and the ticker
+        let bottom = game_height() - Ticker::HEIGHT;
+        let menu_y = bottom - 40.0 - 24.0 * GameMode::ALL.len() as f32;
+        draw_text("Mode (Up/Down):", 10.0, menu_y, 20.0, WHITE);
+        for (index, mode) in GameMode::ALL.iter().enumerate() {
+            let (marker, color) = if index == self.selected_mode { ("> ", YELLOW) } else { ("  ", WHITE) };
+            let label = format!("{}{}", marker, mode.name());
+            draw_text(&label, 10.0, menu_y + 24.0 * (index + 1) as f32, 20.0, color);
+        }
+
+        let seed = if self.seed_input.is_empty() { "random" } else { &self.seed_input };
+        let seed_label = format!("Seed: {} (type digits)", seed);
+        draw_text(&seed_label, 10.0, menu_y - 28.0, 20.0, WHITE);
+
+        let hint = format!("Press {:?} for settings", self.keys.key(Action::Settings));
+        draw_text(&hint, 10.0, bottom - 10.0, 20.0, WHITE);
+
+        let version = Self::version_text(env!("CARGO_PKG_VERSION"), option_env!("GIT_HASH"));
+        let version_width = measure_text(&version, None, 16, 1.0).width;
+        draw_text(&version, game_width() - version_width - 10.0, bottom - 10.0, 16.0, LIGHTGRAY);
+
+        if let Some(ticker) = &self.ticker {
+            ticker.draw(layout.screen);
+        }
     }
 
     fn as_any(&mut self) -> &mut dyn std::any::Any {
@@ -165,6 +294,11 @@ The tests validate:
 3. Game loading toggle
 4. State separation of loading and loading_game
 5. Flag independence (of say, game loading and say, asset loading)
+6. Mode menu navigation wraps at both ends
+7. The selected mode is the one handed to the game
+8. Seeds parse from digits, blank or invalid input means random
+9. The ticker lists the stored records, the highest best times last
+10. The version text names the version and the git hash, or "unknown" without one
 
 */
 
@@ -255,4 +389,66 @@ mod tests {
         assert!(scene.is_loading_game());
         assert!(scene.is_loading()); // still loading assets
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_mode_navigation_wraps() {
+        let len = 3;
+        assert_eq!(TitleScene::navigate(0, len, true, false), 2);
+        assert_eq!(TitleScene::navigate(2, len, false, true), 0);
+        assert_eq!(TitleScene::navigate(1, len, false, true), 2);
+        assert_eq!(TitleScene::navigate(1, len, true, true), 1);
+        assert_eq!(TitleScene::navigate(0, 0, false, true), 0);
+    }
+
+    #[test]
+    fn test_selected_mode_is_handed_to_game() {
+        let mut scene = TitleScene::new();
+        scene.selected_mode = 0;
+        assert_eq!(scene.selected_mode(), GameMode::Normal);
+
+        assert!(scene.select_mode(false, true));
+        assert_eq!(scene.selected_mode(), GameMode::Zen);
+
+        assert!(scene.select_mode(false, true));
+        assert_eq!(scene.selected_mode(), GameMode::SuddenDeath);
+
+        assert!(scene.select_mode(false, true));
+        assert_eq!(scene.selected_mode(), GameMode::Levels);
+
+        // Wraps back round to the first mode
+        assert!(scene.select_mode(false, true));
+        assert_eq!(scene.selected_mode(), GameMode::Normal);
+        assert!(!scene.select_mode(false, false));
+    }
+
+    #[test]
+    fn test_parse_seed() {
+        assert_eq!(TitleScene::parse_seed("12345"), Some(12345));
+        assert_eq!(TitleScene::parse_seed(" 42 "), Some(42));
+        assert_eq!(TitleScene::parse_seed("18446744073709551615"), Some(u64::MAX));
+
+        assert_eq!(TitleScene::parse_seed(""), None);
+        assert_eq!(TitleScene::parse_seed("   "), None);
+        assert_eq!(TitleScene::parse_seed("12ab"), None);
+        assert_eq!(TitleScene::parse_seed("-5"), None);
+        assert_eq!(TitleScene::parse_seed("18446744073709551616"), None);
+    }
+
+    #[test]
+    fn test_ticker_text() {
+        assert_eq!(TitleScene::ticker_text(0, 0, &BestTimes::default()), "NO RECORDS YET - BE THE FIRST");
+
+        let mut best_times = BestTimes::default();
+        best_times.record(&[1.5, 3.0, 4.5, 6.25]);
+        let text = TitleScene::ticker_text(4, 120, &best_times);
+        assert!(text.starts_with("HIGH SCORE 4   *   BEST DISTANCE 120m"));
+        assert!(!text.contains("FASTEST TO 1 "));
+        assert!(text.ends_with("FASTEST TO 3 00:04.500   *   FASTEST TO 4 00:06.250"));
+    }
+
+    #[test]
+    fn test_version_text() {
+        assert_eq!(TitleScene::version_text("0.1.0", Some("6e1c9b9")), "v0.1.0 (6e1c9b9)");
+        assert_eq!(TitleScene::version_text("0.1.0", None), "v0.1.0 (unknown)");
+    }
+}
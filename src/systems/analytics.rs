@@ -0,0 +1,110 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use crate::scenes::game::GameResult;
+use crate::systems::simulation::{SimEvent, Simulation};
+
+// Summary - Analytics :
+// Hooks for whoever embeds the game to count runs, scores and run lengths
+// in their own metrics sink. Every method does nothing by default, so a
+// sink only implements the ones it cares about.
+pub trait Analytics {
+    // The instructions were dismissed and a run began
+    fn on_game_start(&mut self) {}
+
+    // `score` is the total after the points just earned
+    fn on_score(&mut self, _score: i32) {}
+
+    // The run's length is in the result's duration
+    fn on_game_over(&mut self, _result: &GameResult) {}
+}
+
+// Default sink, records nothing
+pub struct NoopAnalytics;
+
+impl Analytics for NoopAnalytics {}
+
+// One sink shared by the scene manager and each GameScene it creates
+pub type SharedAnalytics = Rc<RefCell<dyn Analytics>>;
+
+pub fn noop() -> SharedAnalytics {
+    Rc::new(RefCell::new(NoopAnalytics))
+}
+
+// Calls the hooks for one frame's events. A point and a bonus in the same
+// frame are a single score update.
+pub fn report(analytics: &mut dyn Analytics, sim: &Simulation, events: &[SimEvent], previous_high: i32, run_time: f32) {
+    if events.contains(&SimEvent::Started) {
+        analytics.on_game_start();
+    }
+    if events.iter().any(|event| matches!(event, SimEvent::Scored | SimEvent::Bonus(_))) {
+        analytics.on_score(sim.score);
+    }
+    if events.iter().any(|event| matches!(event, SimEvent::GroundHit | SimEvent::Won)) {
+        analytics.on_game_over(&GameResult::new(sim, previous_high, run_time));
+    }
+}
+
+/*
+
+The tests validate :
+1. A short run reports its start, each point and its end, in that order
+
+*/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prefabs::pipes::{Pipe, PipeGroup};
+    use crate::systems::simulation::FIXED_FRAME_TIME;
+    use crate::systems::config::Speeds;
+    use ::rand::rngs::StdRng;
+    use ::rand::SeedableRng;
+
+    #[derive(Default)]
+    struct Recorder {
+        calls: Vec<String>,
+    }
+
+    impl Analytics for Recorder {
+        fn on_game_start(&mut self) {
+            self.calls.push("start".to_string());
+        }
+
+        fn on_score(&mut self, score: i32) {
+            self.calls.push(format!("score {}", score));
+        }
+
+        fn on_game_over(&mut self, result: &GameResult) {
+            self.calls.push(format!("over {} new_high={}", result.score, result.new_high));
+        }
+    }
+
+    #[test]
+    fn test_short_run_reports_in_order() {
+        let mut sim = Simulation::headless(1);
+        let mut recorder = Recorder::default();
+        let mut run_time = 0.0;
+        let mut frame = |sim: &mut Simulation, recorder: &mut Recorder, flap: bool| {
+            let events = sim.step(flap);
+            run_time += FIXED_FRAME_TIME;
+            report(recorder, sim, &events, 1, run_time);
+        };
+
+        frame(&mut sim, &mut recorder, true);
+        // Two pipe groups already behind the bird, scored on the next frames
+        for offset in [Pipe::WIDTH, Pipe::WIDTH * 2.0] {
+            let mut pipe_group = PipeGroup::new();
+            let gap_top = PipeGroup::gap_top(0.5, sim.ground_y, PipeGroup::GAP_SIZE);
+            let mut rng = StdRng::seed_from_u64(1);
+            pipe_group.reset(sim.bird.position.x - offset, gap_top, PipeGroup::GAP_SIZE, Speeds::SCROLL..=Speeds::SCROLL, 0.0, &mut rng);
+            pipe_group.enabled = false;
+            sim.pipes.push(pipe_group);
+            frame(&mut sim, &mut recorder, false);
+        }
+        while !sim.is_game_over() {
+            frame(&mut sim, &mut recorder, false);
+        }
+
+        assert_eq!(recorder.calls, vec!["start", "score 1", "score 2", "over 2 new_high=true"]);
+    }
+}
@@ -0,0 +1,155 @@
+// Sounds the Random death sound picks from, in resources/
+pub const DEATH_SOUND_FILES: [&str; 3] = ["pipe-hit.wav", "ground-hit.wav", "ouch.wav"];
+
+// What plays when the bird dies
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DeathSound {
+    // The pipe hit and ground hit sounds, each for its own collision
+    Classic,
+    // One of DEATH_SOUND_FILES, picked fresh for every death
+    Random,
+}
+
+impl DeathSound {
+    pub fn name(&self) -> &'static str {
+        match self {
+            DeathSound::Classic => "classic",
+            DeathSound::Random => "random",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<DeathSound> {
+        [DeathSound::Classic, DeathSound::Random].into_iter().find(|sound| sound.name() == name)
+    }
+}
+
+// Index into `count` loaded sounds for a roll in 0..1, None when none loaded
+pub fn pick_sound(count: usize, roll: f32) -> Option<usize> {
+    if count == 0 {
+        return None;
+    }
+    Some(((roll.max(0.0) * count as f32) as usize).min(count - 1))
+}
+
+// Summary - Ducking :
+// Turns background sounds down while an important one-shot plays, e.g. the
+// game over hit. The level drops straight to `duck_level`, holds, and fades
+// back to full over the last RELEASE seconds of `duration`.
+pub struct Ducking {
+    // Multiplier applied while ducked, 0-1
+    pub duck_level: f32,
+    // Seconds from the duck until the level is back to full
+    pub duration: f32,
+    timer: f32,
+}
+
+impl Ducking {
+    // Seconds spent fading back in, so the sound doesn't jump up
+    const RELEASE: f32 = 0.25;
+
+    pub fn new(duck_level: f32, duration: f32) -> Self {
+        Ducking {
+            duck_level: duck_level.clamp(0.0, 1.0),
+            duration,
+            timer: 0.0,
+        }
+    }
+
+    // Called when the important sound starts, restarting any duck in progress
+    pub fn duck(&mut self) {
+        self.timer = self.duration;
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        self.timer = (self.timer - dt).max(0.0);
+    }
+
+    // Multiplier for the ducked sounds' volume, 1 when nothing is ducking
+    pub fn level(&self) -> f32 {
+        let release = Self::RELEASE.min(self.duration);
+        if self.timer <= 0.0 {
+            1.0
+        } else if self.timer >= release {
+            self.duck_level
+        } else {
+            let restored = 1.0 - self.timer / release;
+            self.duck_level + (1.0 - self.duck_level) * restored
+        }
+    }
+}
+
+/*
+
+The tests validate :
+1. Ducking lowers the level, then it returns to the original after the duration
+2. The level fades back in rather than jumping
+3. Random death sound picks stay within the loaded set
+4. Death sound names round trip
+
+*/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn test_ducks_then_restores() {
+        let mut ducking = Ducking::new(0.3, 1.5);
+        assert_float_eq!(ducking.level(), 1.0, abs <= 0.001);
+
+        ducking.duck();
+        assert_float_eq!(ducking.level(), 0.3, abs <= 0.001);
+
+        ducking.update(1.0);
+        assert_float_eq!(ducking.level(), 0.3, abs <= 0.001);
+
+        ducking.update(0.5);
+        assert_float_eq!(ducking.level(), 1.0, abs <= 0.001);
+        ducking.update(1.0);
+        assert_float_eq!(ducking.level(), 1.0, abs <= 0.001);
+    }
+
+    #[test]
+    fn test_level_fades_back_in() {
+        let mut ducking = Ducking::new(0.2, 1.0);
+        ducking.duck();
+
+        let mut previous = ducking.level();
+        for _ in 0..20 {
+            ducking.update(0.05);
+            assert!(ducking.level() >= previous);
+            previous = ducking.level();
+        }
+        assert_float_eq!(previous, 1.0, abs <= 0.001);
+
+        // Halfway through the release the level is halfway back
+        ducking.duck();
+        ducking.update(1.0 - Ducking::RELEASE / 2.0);
+        assert_float_eq!(ducking.level(), 0.6, abs <= 0.001);
+    }
+
+    #[test]
+    fn test_pick_sound_stays_in_bounds() {
+        assert_eq!(pick_sound(0, 0.5), None);
+        assert_eq!(pick_sound(3, 0.0), Some(0));
+        assert_eq!(pick_sound(3, 0.5), Some(1));
+        assert_eq!(pick_sound(3, 0.999), Some(2));
+        // Out of range rolls still land on a loaded sound
+        assert_eq!(pick_sound(3, 1.0), Some(2));
+        assert_eq!(pick_sound(3, -0.5), Some(0));
+
+        for _ in 0..1000 {
+            let index = pick_sound(DEATH_SOUND_FILES.len(), ::rand::random::<f32>()).unwrap();
+            assert!(index < DEATH_SOUND_FILES.len());
+        }
+    }
+
+    #[test]
+    fn test_death_sound_names() {
+        for sound in [DeathSound::Classic, DeathSound::Random] {
+            assert_eq!(DeathSound::from_name(sound.name()), Some(sound));
+        }
+        assert_eq!(DeathSound::from_name("loud"), None);
+    }
+}
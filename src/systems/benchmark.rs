@@ -0,0 +1,129 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use crate::prefabs::pipes::Pipe;
+use crate::systems::simulation::{GamePhase, Simulation};
+
+pub const DEFAULT_FRAMES: u32 = 10_000;
+pub const DEFAULT_SEED: u64 = 1234;
+
+pub struct BenchmarkReport {
+    pub frames: u32,
+    pub seed: u64,
+    pub score: i32,
+    pub total: Duration,
+    pub min: Duration,
+    pub max: Duration,
+}
+
+impl BenchmarkReport {
+    pub fn mean(&self) -> Duration {
+        if self.frames == 0 {
+            return Duration::ZERO;
+        }
+        self.total / self.frames
+    }
+}
+
+impl fmt::Display for BenchmarkReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Benchmark: {} frames (seed {})", self.frames, self.seed)?;
+        writeln!(f, "  total: {:?}", self.total)?;
+        writeln!(f, "  mean:  {:?}", self.mean())?;
+        writeln!(f, "  min:   {:?}", self.min)?;
+        writeln!(f, "  max:   {:?}", self.max)?;
+        write!(f, "  final score: {}", self.score)
+    }
+}
+
+// Scripted input: flap once to start, then whenever the bird drops below
+// the centre of the next gap (or the middle of the screen if there is no
+// pipe ahead). A flap lifts the bird ~70px, so aim a little low.
+// While gravity is flipped everything is mirrored.
+pub fn scripted_flap(sim: &Simulation) -> bool {
+    if sim.phase() == GamePhase::Instructions {
+        return true;
+    }
+
+    let bird_x = sim.bird.position.x;
+    let target_y = sim
+        .pipes
+        .iter()
+        .filter(|pipe_group| pipe_group.alive && pipe_group.position.x + Pipe::WIDTH + Pipe::HALF_WIDTH > bird_x)
+        .min_by(|a, b| a.position.x.total_cmp(&b.position.x))
+        .map(|pipe_group| pipe_group.gap_center())
+        .unwrap_or(sim.screen.y / 2.0);
+
+    if sim.bird.gravity_sign < 0.0 {
+        sim.bird.position.y < target_y - 20.0
+    } else {
+        sim.bird.position.y > target_y + 20.0
+    }
+}
+
+// Summary - run() :
+// 1. Create a headless simulation for the seed.
+// 2. Step it `frames` times using the scripted input, timing every step.
+// 3. Report frame time statistics and the final score.
+pub fn run(seed: u64, frames: u32) -> BenchmarkReport {
+    let mut sim = Simulation::headless(seed);
+    let mut total = Duration::ZERO;
+    let mut min = Duration::MAX;
+    let mut max = Duration::ZERO;
+
+    for _ in 0..frames {
+        let flap = scripted_flap(&sim);
+
+        let start = Instant::now();
+        sim.step(flap);
+        let elapsed = start.elapsed();
+
+        total += elapsed;
+        min = min.min(elapsed);
+        max = max.max(elapsed);
+    }
+
+    BenchmarkReport {
+        frames,
+        seed: sim.seed(),
+        score: sim.score,
+        total,
+        min: if frames == 0 { Duration::ZERO } else { min },
+        max,
+    }
+}
+
+/*
+
+The tests validate :
+1. The same seed and input script give the same final score
+2. The scripted input actually scores points
+3. Frame statistics are consistent
+
+*/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_benchmark_score_is_deterministic() {
+        let first = run(DEFAULT_SEED, 3000);
+        let second = run(DEFAULT_SEED, 3000);
+        assert_eq!(first.score, second.score);
+    }
+
+    #[test]
+    fn test_scripted_input_scores() {
+        let report = run(DEFAULT_SEED, 3000);
+        assert!(report.score > 0, "Scripted input should pass at least one pipe");
+    }
+
+    #[test]
+    fn test_frame_statistics_are_consistent() {
+        let report = run(DEFAULT_SEED, 500);
+        assert_eq!(report.frames, 500);
+        assert!(report.min <= report.mean());
+        assert!(report.mean() <= report.max);
+    }
+}
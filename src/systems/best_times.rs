@@ -0,0 +1,127 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+// Summary - BestTimes :
+// Best-time-for-score-N leaderboard: the fastest any run has reached each
+// score, in seconds from the first flap. Stored as one "score milliseconds"
+// pair per line.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BestTimes {
+    times: BTreeMap<i32, f32>,
+}
+
+impl BestTimes {
+    pub fn best(&self, score: i32) -> Option<f32> {
+        self.times.get(&score).copied()
+    }
+
+    // (score, seconds) from the lowest score up
+    pub fn entries(&self) -> impl DoubleEndedIterator<Item = (i32, f32)> + '_ {
+        self.times.iter().map(|(score, time)| (*score, *time))
+    }
+
+    // `splits[i]` is the run time score i + 1 was reached at. Returns true
+    // if any of them beat the stored time.
+    pub fn record(&mut self, splits: &[f32]) -> bool {
+        let mut improved = false;
+        for (score, &time) in (1..).zip(splits) {
+            if self.best(score).is_none_or(|best| time < best) {
+                self.times.insert(score, time);
+                improved = true;
+            }
+        }
+        improved
+    }
+
+    pub fn to_text(&self) -> String {
+        self.times
+            .iter()
+            .map(|(score, time)| format!("{} {}\n", score, (time * 1000.0).round() as u64))
+            .collect()
+    }
+
+    // Lines that don't parse are skipped rather than losing the whole board
+    pub fn parse(text: &str) -> Self {
+        let times = text
+            .lines()
+            .filter_map(|line| {
+                let (score, millis) = line.trim().split_once(' ')?;
+                let score = score.parse::<i32>().ok().filter(|score| *score > 0)?;
+                let millis = millis.trim().parse::<u64>().ok()?;
+                Some((score, millis as f32 / 1000.0))
+            })
+            .collect();
+        BestTimes { times }
+    }
+
+    // A missing or unreadable file is an empty board
+    pub fn load(path: &str) -> Self {
+        fs::read_to_string(path).map(|text| Self::parse(&text)).unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &str) -> io::Result<()> {
+        if let Some(dir) = Path::new(path).parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_text())
+    }
+}
+
+/*
+
+The tests validate :
+1. Recording keeps the fastest time for each score reached
+2. The board survives a save and load, bad lines are skipped
+3. A missing file loads as an empty board
+
+*/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn test_record_keeps_fastest() {
+        let mut times = BestTimes::default();
+        assert!(times.record(&[1.5, 3.0, 4.5]));
+        assert_float_eq!(times.best(3).unwrap(), 4.5, abs <= 0.001);
+
+        // Faster to 2 but slower to 1 only improves score 2
+        assert!(times.record(&[1.6, 2.8]));
+        assert_float_eq!(times.best(1).unwrap(), 1.5, abs <= 0.001);
+        assert_float_eq!(times.best(2).unwrap(), 2.8, abs <= 0.001);
+        assert_float_eq!(times.best(3).unwrap(), 4.5, abs <= 0.001);
+
+        assert!(!times.record(&[2.0]));
+        assert!(!times.record(&[]));
+        assert_eq!(times.best(4), None);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("best_times.txt");
+        let path = path.to_str().unwrap();
+
+        let mut times = BestTimes::default();
+        times.record(&[1.234, 62.5]);
+        times.save(path).unwrap();
+        assert_eq!(BestTimes::load(path), times);
+
+        let parsed = BestTimes::parse("1 1234\nnonsense\n-3 500\n2 abc\n5 9000\n");
+        assert_float_eq!(parsed.best(1).unwrap(), 1.234, abs <= 0.001);
+        assert_float_eq!(parsed.best(5).unwrap(), 9.0, abs <= 0.001);
+        assert_eq!(parsed.best(2), None);
+        assert_eq!(parsed.best(-3), None);
+    }
+
+    #[test]
+    fn test_missing_file_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("missing.txt");
+        assert_eq!(BestTimes::load(path.to_str().unwrap()), BestTimes::default());
+    }
+}
@@ -0,0 +1,103 @@
+use macroquad::prelude::*;
+use crate::systems::resources::resource_path;
+
+// Summary - BitmapFont :
+// Chunky digit sprites for numbers, like the original game's score.
+// digits.png (e.g. from a resource pack) holds the digits 0 to 9 side by
+// side in one row, all the same width. Without it the TTF font is used.
+// Clones share the texture.
+#[derive(Clone)]
+pub struct BitmapFont {
+    texture: Texture2D,
+    digit_size: Vec2,
+}
+
+impl BitmapFont {
+    // Gap between two digits, as a fraction of a digit's width
+    const SPACING: f32 = 0.1;
+
+    // None when there's no digit sheet to load
+    pub async fn load() -> Option<Self> {
+        let texture = load_texture(&resource_path("digits.png")).await.ok()?;
+        texture.set_filter(FilterMode::Nearest);
+        let digit_size = Self::digit_size(texture.width(), texture.height())?;
+        Some(BitmapFont { texture, digit_size })
+    }
+
+    // Size of one digit on a `width` x `height` sheet, None for an empty one
+    pub fn digit_size(width: f32, height: f32) -> Option<Vec2> {
+        (width >= 10.0 && height > 0.0).then(|| vec2(width / 10.0, height))
+    }
+
+    pub fn digit_source(digit: u32, digit_size: Vec2) -> Rect {
+        Rect::new(digit.min(9) as f32 * digit_size.x, 0.0, digit_size.x, digit_size.y)
+    }
+
+    // Each digit of `number` with the left edge it's drawn at, the whole
+    // number centred on `center_x`
+    pub fn digit_positions(number: u32, center_x: f32, digit_width: f32) -> Vec<(u32, f32)> {
+        let digits: Vec<u32> = number.to_string().chars().filter_map(|c| c.to_digit(10)).collect();
+        let step = digit_width * (1.0 + Self::SPACING);
+        let total_width = step * digits.len() as f32 - digit_width * Self::SPACING;
+        let left = center_x - total_width / 2.0;
+        digits
+            .into_iter()
+            .enumerate()
+            .map(|(i, digit)| (digit, left + i as f32 * step))
+            .collect()
+    }
+
+    // `height` in pixels, the width follows the sheet's proportions
+    pub fn draw_number(&self, number: u32, center_x: f32, top: f32, height: f32) {
+        let scale = height / self.digit_size.y;
+        let size = self.digit_size * scale;
+        for (digit, x) in Self::digit_positions(number, center_x, size.x) {
+            draw_texture_ex(&self.texture, x, top, WHITE, DrawTextureParams {
+                source: Some(Self::digit_source(digit, self.digit_size)),
+                dest_size: Some(size),
+                ..Default::default()
+            });
+        }
+    }
+}
+
+/*
+
+The tests validate :
+1. The sheet is sliced into ten equal digits, empty sheets are rejected
+2. Digits of a multi-digit number are laid out left to right, centred
+
+*/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn test_digit_slicing() {
+        let size = BitmapFont::digit_size(240.0, 36.0).unwrap();
+        assert_float_eq!(size.x, 24.0, abs <= 0.001);
+        assert_float_eq!(size.y, 36.0, abs <= 0.001);
+        assert_eq!(BitmapFont::digit_source(7, size), Rect::new(168.0, 0.0, 24.0, 36.0));
+        assert_eq!(BitmapFont::digit_source(12, size), Rect::new(216.0, 0.0, 24.0, 36.0));
+
+        assert_eq!(BitmapFont::digit_size(0.0, 36.0), None);
+        assert_eq!(BitmapFont::digit_size(240.0, 0.0), None);
+    }
+
+    #[test]
+    fn test_digit_positions() {
+        // 20 wide digits 2 apart: 3 digits take 64 pixels
+        let positions = BitmapFont::digit_positions(907, 100.0, 20.0);
+        let digits: Vec<u32> = positions.iter().map(|(digit, _)| *digit).collect();
+        assert_eq!(digits, vec![9, 0, 7]);
+        assert_float_eq!(positions[0].1, 68.0, abs <= 0.001);
+        assert_float_eq!(positions[1].1, 90.0, abs <= 0.001);
+        assert_float_eq!(positions[2].1, 112.0, abs <= 0.001);
+
+        let single = BitmapFont::digit_positions(0, 100.0, 20.0);
+        assert_eq!(single.len(), 1);
+        assert_float_eq!(single[0].1, 90.0, abs <= 0.001);
+    }
+}
@@ -0,0 +1,101 @@
+use macroquad::prelude::*;
+
+// Summary - FollowCamera :
+// Horizontal camera for the scrolling variant, where the bird really flies
+// to the right through pipes placed at world positions. The camera trails
+// the bird so it stays `offset` pixels from the left edge of the screen.
+pub struct FollowCamera {
+    // World x of the left edge of the screen
+    pub x: f32,
+    pub offset: f32,
+    // How quickly the camera catches up, higher is snappier
+    pub smoothing: f32,
+}
+
+impl FollowCamera {
+    const DEFAULT_SMOOTHING: f32 = 10.0;
+
+    pub fn new(offset: f32) -> Self {
+        FollowCamera {
+            x: 0.0,
+            offset,
+            smoothing: Self::DEFAULT_SMOOTHING,
+        }
+    }
+
+    pub fn target_x(&self, bird_x: f32) -> f32 {
+        bird_x - self.offset
+    }
+
+    // Eases towards the target, framerate independent
+    pub fn follow(&mut self, bird_x: f32, dt: f32) {
+        let blend = 1.0 - (-self.smoothing * dt).exp();
+        self.x += (self.target_x(bird_x) - self.x) * blend;
+    }
+
+    pub fn snap_to(&mut self, bird_x: f32) {
+        self.x = self.target_x(bird_x);
+    }
+
+    // Camera2D mapping world space onto the screen, y pointing down like
+    // the default screen space. (from_display_rect flips y when drawing
+    // to the screen, so the zoom is built by hand.)
+    pub fn to_camera2d(&self, screen: Vec2) -> Camera2D {
+        Camera2D {
+            target: vec2(self.x + screen.x / 2.0, screen.y / 2.0),
+            zoom: vec2(2.0 / screen.x, 2.0 / screen.y),
+            ..Default::default()
+        }
+    }
+}
+
+/*
+
+The tests validate :
+1. The camera x tracks the bird's world x minus the configured offset
+2. Following eases in rather than jumping
+3. The Camera2D is centred on the visible part of the world
+
+*/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn test_camera_tracks_bird_with_offset() {
+        let mut camera = FollowCamera::new(320.0);
+        let mut bird_x = 320.0;
+
+        for _ in 0..600 {
+            bird_x += 3.0;
+            camera.follow(bird_x, 1.0 / 60.0);
+        }
+
+        // Trails by a constant amount at a constant speed, so allow a small lag
+        assert!((camera.x - (bird_x - 320.0)).abs() < 20.0);
+
+        camera.snap_to(bird_x);
+        assert_float_eq!(camera.x, bird_x - 320.0, abs <= 0.001);
+    }
+
+    #[test]
+    fn test_follow_eases_in() {
+        let mut camera = FollowCamera::new(100.0);
+        camera.follow(600.0, 1.0 / 60.0);
+
+        assert!(camera.x > 0.0);
+        assert!(camera.x < 500.0);
+    }
+
+    #[test]
+    fn test_camera2d_centred_on_view() {
+        let mut camera = FollowCamera::new(100.0);
+        camera.snap_to(1100.0);
+
+        let camera2d = camera.to_camera2d(vec2(800.0, 600.0));
+        assert_float_eq!(camera2d.target.x, 1400.0, abs <= 0.001);
+        assert_float_eq!(camera2d.target.y, 300.0, abs <= 0.001);
+    }
+}
@@ -0,0 +1,293 @@
+use crate::prefabs::bird::Bird;
+use crate::prefabs::pipes::PipeGroup;
+use crate::GRAVITY;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GameMode {
+    Normal,
+    // Endless flight: pipes scroll by but nothing kills the bird and nothing is scored
+    Zen,
+    // Expert preset: smallest gap, fastest scroll and tightest spacing
+    SuddenDeath,
+    // A pipe goal per level, each level a little harder than the last
+    Levels,
+}
+
+impl GameMode {
+    // Order in which the modes are listed on the title screen
+    pub const ALL: [GameMode; 4] = [GameMode::Normal, GameMode::Zen, GameMode::SuddenDeath, GameMode::Levels];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameMode::Normal => "normal",
+            GameMode::Zen => "zen",
+            GameMode::SuddenDeath => "sudden_death",
+            GameMode::Levels => "levels",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<GameMode> {
+        Self::ALL.into_iter().find(|mode| mode.name() == name)
+    }
+}
+
+// Summary - Speeds :
+// How fast the world scrolls, in pixels per second. Everything moves by
+// speed * dt, so the distance covered doesn't depend on the frame rate;
+// at 60 FPS one frame moves as far as the old pixels-per-frame values did.
+pub struct Speeds;
+
+impl Speeds {
+    // Pipes and ground, 3 pixels a frame at 60 FPS
+    pub const SCROLL: f32 = 180.0;
+    // Background layers as a share of SCROLL, the further away the slower
+    pub const FOREST_FACTOR: f32 = 0.75;
+    pub const CITYSCAPE_FACTOR: f32 = 0.5;
+    pub const CLOUD_FACTOR: f32 = 0.25;
+
+    // Pixels covered in `dt` seconds at `speed` pixels per second
+    pub fn distance(speed: f32, dt: f32) -> f32 {
+        speed * dt
+    }
+}
+
+// Rendering detail, Low drops the cloud and cityscape layers for weak hardware
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Quality {
+    High,
+    Low,
+}
+
+impl Quality {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Quality::High => "high",
+            Quality::Low => "low",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Quality> {
+        [Quality::High, Quality::Low].into_iter().find(|quality| quality.name() == name)
+    }
+
+    pub fn toggled(&self) -> Quality {
+        match self {
+            Quality::High => Quality::Low,
+            Quality::Low => Quality::High,
+        }
+    }
+}
+
+// How the bird is steered. FollowMouse is a sandbox: no gravity, the bird
+// glides towards the pointer's height.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ControlScheme {
+    Flap,
+    FollowMouse,
+}
+
+impl ControlScheme {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ControlScheme::Flap => "flap",
+            ControlScheme::FollowMouse => "follow_mouse",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<ControlScheme> {
+        [ControlScheme::Flap, ControlScheme::FollowMouse].into_iter().find(|scheme| scheme.name() == name)
+    }
+}
+
+// Summary - GameConfig :
+// Tunable gameplay values. The defaults match the original game.
+pub struct GameConfig {
+    pub mode: GameMode,
+
+    // Downward pull on the bird
+    pub gravity: f32,
+
+    // Height of the opening between the top and bottom pipe, in pixels
+    pub gap_size: f32,
+
+    // Horizontal distance between two pipe groups, in pixels
+    pub pipe_spacing: f32,
+
+    // Pixels per second the pipes move towards the bird, see Speeds
+    pub scroll_speed: f32,
+
+    // Seconds per wing beat frame of the bird animation
+    pub bird_frame_duration: f32,
+
+    // Seconds of countdown before the bird starts falling (0 = none)
+    pub countdown_duration: f32,
+
+    // Seconds at the start of a run in which pipes pass through the bird,
+    // against dying to a bad first flap. The ground still counts (0 = off,
+    // the default).
+    pub start_grace: f32,
+
+    // Experimental: the bird flies right through pipes at fixed world
+    // positions and the camera follows it, instead of the world scrolling
+    pub scrolling_camera: bool,
+
+    // The bird hangs in place once the run starts until the first flap,
+    // giving the player a moment to get ready
+    pub gravity_on_first_flap: bool,
+
+    // Pipes and the ground can kill the bird. Off lets it pass through
+    // pipes and rest on the ground, like in zen mode but still scoring.
+    pub collisions: bool,
+
+    // Chance that a new pipe gap holds a gravity flip power-up (0 = never)
+    pub gravity_flip_chance: f32,
+
+    // Seconds gravity stays flipped after collecting one
+    pub gravity_flip_duration: f32,
+
+    // Share of power-ups that make the bird bigger or smaller instead of
+    // flipping gravity (0 = flips only)
+    pub size_powerup_share: f32,
+
+    // Seconds the bird keeps its new size
+    pub size_powerup_duration: f32,
+
+    // Share of power-ups that give a charge to smash through one pipe (0 = none)
+    pub smash_powerup_share: f32,
+
+    // MSAA samples per pixel for the window. More samples smooth the edges
+    // of shapes and text but cost fill rate, and the pixel-art sprites gain
+    // nothing from it, so keep it at 1 (off) unless the UI needs it.
+    pub sample_count: i32,
+
+    // Each pipe group scrolls up to this fraction faster or slower than
+    // scroll_speed, growing with the score (0 = all the same speed)
+    pub pipe_speed_variation: f32,
+
+    // Pixels trimmed off every side of the pipe hitboxes. The sprites have
+    // a lip, so a full size hitbox feels too tight around the gap.
+    pub pipe_collision_inset: f32,
+
+    // How strongly gaps are pulled towards the middle of the screen, where
+    // they're easiest to reach (0 = anywhere, 1 = strongest)
+    pub gap_center_bias: f32,
+
+    // Snapshot the run every CHECKPOINT_INTERVAL points so a death can be
+    // continued from there, once per run
+    pub checkpoints: bool,
+
+    // Finite mode: the run is won once this many pipe groups are passed (0 = endless)
+    pub pipe_limit: u32,
+
+    // Chance that a pipe group has only a top or only a bottom pipe (0 = never)
+    pub single_pipe_chance: f32,
+
+    // "Floor is lava": pixels per second the ground rises during a run (0 = off)
+    pub ground_rise_speed: f32,
+
+    // Most floating texts on screen at once, the oldest go first (0 = none)
+    pub max_floating_texts: usize,
+
+    // Frames per second to cap rendering at, to save battery (0 = uncapped).
+    // The world moves in fixed steps, so gameplay speed doesn't change with
+    // it. The web build ignores it.
+    pub fps_cap: u32,
+
+    // Longest frame time handed to the game, in seconds. A stall (e.g. while
+    // loading) would otherwise run a burst of world steps in one frame, and
+    // the bird could crash before a single one is drawn (0 = no limit).
+    pub max_frame_time: f32,
+}
+
+impl GameConfig {
+    pub fn new() -> Self {
+        GameConfig {
+            mode: GameMode::Normal,
+            gravity: GRAVITY,
+            gap_size: PipeGroup::GAP_SIZE,
+            pipe_spacing: 240.0,
+            scroll_speed: Speeds::SCROLL,
+            bird_frame_duration: Bird::DEFAULT_FRAME_DURATION,
+            countdown_duration: 0.0,
+            start_grace: 0.0,
+            scrolling_camera: false,
+            gravity_on_first_flap: false,
+            collisions: true,
+            gravity_flip_chance: 0.1,
+            gravity_flip_duration: 5.0,
+            size_powerup_share: 0.3,
+            size_powerup_duration: 5.0,
+            smash_powerup_share: 0.15,
+            sample_count: 1,
+            pipe_speed_variation: 0.0,
+            pipe_collision_inset: 2.0,
+            gap_center_bias: 0.0,
+            checkpoints: false,
+            pipe_limit: 0,
+            single_pipe_chance: 0.0,
+            ground_rise_speed: 0.0,
+            max_floating_texts: 16,
+            fps_cap: 0,
+            max_frame_time: 1.0 / 20.0,
+        }
+    }
+}
+
+/*
+
+The tests validate :
+1. Mode names round trip and unknown names are rejected
+2. Quality names round trip and toggling alternates
+3. Control scheme names round trip
+4. At 60 FPS the speeds in pixels per second move as far per frame as the old pixels-per-frame values
+
+*/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn test_mode_names_round_trip() {
+        for mode in GameMode::ALL {
+            assert_eq!(GameMode::from_name(mode.name()), Some(mode));
+        }
+        assert_eq!(GameMode::from_name("hardcore"), None);
+    }
+
+    #[test]
+    fn test_quality_names_and_toggle() {
+        for quality in [Quality::High, Quality::Low] {
+            assert_eq!(Quality::from_name(quality.name()), Some(quality));
+            assert_eq!(quality.toggled().toggled(), quality);
+        }
+        assert_eq!(Quality::High.toggled(), Quality::Low);
+        assert_eq!(Quality::from_name("ultra"), None);
+    }
+
+    #[test]
+    fn test_control_scheme_names() {
+        for scheme in [ControlScheme::Flap, ControlScheme::FollowMouse] {
+            assert_eq!(ControlScheme::from_name(scheme.name()), Some(scheme));
+        }
+        assert_eq!(ControlScheme::from_name("joystick"), None);
+    }
+
+    #[test]
+    fn test_speeds_match_old_per_frame_movement() {
+        let frame = 1.0 / 60.0;
+        assert_float_eq!(Speeds::distance(Speeds::SCROLL, frame), 3.0, abs <= 0.001);
+        assert_float_eq!(Speeds::distance(Speeds::SCROLL * Speeds::FOREST_FACTOR, frame), 2.25, abs <= 0.001);
+        assert_float_eq!(Speeds::distance(Speeds::SCROLL * Speeds::CITYSCAPE_FACTOR, frame), 1.5, abs <= 0.001);
+        assert_float_eq!(Speeds::distance(Speeds::SCROLL * Speeds::CLOUD_FACTOR, frame), 0.75, abs <= 0.001);
+
+        // A second's worth of frames covers the per-second value
+        let mut covered = 0.0;
+        for _ in 0..60 {
+            covered += Speeds::distance(Speeds::SCROLL, frame);
+        }
+        assert_float_eq!(covered, Speeds::SCROLL, abs <= 0.01);
+        assert_float_eq!(GameConfig::new().scroll_speed, Speeds::SCROLL, abs <= 0.001);
+    }
+}
@@ -0,0 +1,213 @@
+use macroquad::prelude::*;
+use crate::systems::config::GameConfig;
+use crate::systems::letterbox::game_width;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Set { field: String, value: f32 },
+}
+
+// Parses `set <field> <value>`
+pub fn parse_command(line: &str) -> Result<Command, String> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    match words.as_slice() {
+        ["set", field, value] => {
+            let value = value
+                .parse::<f32>()
+                .map_err(|_| format!("'{}' is not a number", value))?;
+            if !value.is_finite() {
+                return Err(format!("'{}' is not a number", value));
+            }
+            Ok(Command::Set { field: field.to_string(), value })
+        }
+        ["set", ..] => Err("usage: set <field> <value>".to_string()),
+        [] => Err("empty command".to_string()),
+        [name, ..] => Err(format!("unknown command '{}'", name)),
+    }
+}
+
+// Applies a value to the named GameConfig field, returning what changed
+pub fn apply(config: &mut GameConfig, field: &str, value: f32) -> Result<String, String> {
+    let target = match field {
+        "gravity" => &mut config.gravity,
+        "gap" => &mut config.gap_size,
+        "spacing" => &mut config.pipe_spacing,
+        "speed" => &mut config.scroll_speed,
+        "countdown" => &mut config.countdown_duration,
+        "grace" => &mut config.start_grace,
+        "flip_chance" => &mut config.gravity_flip_chance,
+        "flip_duration" => &mut config.gravity_flip_duration,
+        "size_share" => &mut config.size_powerup_share,
+        "size_duration" => &mut config.size_powerup_duration,
+        "smash_share" => &mut config.smash_powerup_share,
+        "rise" => &mut config.ground_rise_speed,
+        "speed_variation" => &mut config.pipe_speed_variation,
+        "single_pipes" => &mut config.single_pipe_chance,
+        "center_bias" => &mut config.gap_center_bias,
+        "inset" => &mut config.pipe_collision_inset,
+        _ => return Err(format!("unknown field '{}'", field)),
+    };
+    if value < 0.0 {
+        return Err(format!("{} can't be negative", field));
+    }
+    *target = value;
+    Ok(format!("{} = {}", field, value))
+}
+
+// Summary - Console :
+// Developer console for live tuning. Toggled by the console key, it reads
+// typed characters, runs `set <field> <value>` on Enter and keeps a short
+// log of results.
+pub struct Console {
+    pub open: bool,
+    input: String,
+    log: Vec<String>,
+}
+
+impl Console {
+    const LOG_LINES: usize = 6;
+
+    pub fn new() -> Self {
+        Console {
+            open: false,
+            input: String::new(),
+            log: Vec::new(),
+        }
+    }
+
+    pub fn update(&mut self, toggle: bool, config: &mut GameConfig) {
+        if toggle {
+            self.open = !self.open;
+        }
+
+        // Drain typed characters even while closed so they don't pile up
+        while let Some(character) = get_char_pressed() {
+            if self.open {
+                self.type_char(character);
+            }
+        }
+
+        if !self.open {
+            return;
+        }
+        if is_key_pressed(KeyCode::Backspace) {
+            self.input.pop();
+        }
+        if is_key_pressed(KeyCode::Enter) {
+            let line = std::mem::take(&mut self.input);
+            self.execute(&line, config);
+        }
+    }
+
+    fn type_char(&mut self, character: char) {
+        // The toggle key itself and control characters aren't part of commands
+        if character != '`' && !character.is_control() {
+            self.input.push(character);
+        }
+    }
+
+    pub fn execute(&mut self, line: &str, config: &mut GameConfig) {
+        let result = parse_command(line).and_then(|command| match command {
+            Command::Set { field, value } => apply(config, &field, value),
+        });
+        let message = match result {
+            Ok(message) => message,
+            Err(error) => format!("error: {}", error),
+        };
+
+        self.log.push(format!("> {}", line));
+        self.log.push(message);
+        let overflow = self.log.len().saturating_sub(Self::LOG_LINES);
+        self.log.drain(..overflow);
+    }
+
+    pub fn draw(&self) {
+        if !self.open {
+            return;
+        }
+
+        let line_height = 20.0;
+        let height = line_height * (Self::LOG_LINES + 1) as f32 + 10.0;
+        draw_rectangle(0.0, 0.0, game_width(), height, Color::new(0.0, 0.0, 0.0, 0.7));
+
+        for (i, line) in self.log.iter().enumerate() {
+            draw_text(line, 8.0, line_height * (i + 1) as f32, 18.0, LIGHTGRAY);
+        }
+        let prompt = format!("] {}_", self.input);
+        draw_text(&prompt, 8.0, height - 8.0, 18.0, WHITE);
+    }
+}
+
+/*
+
+The tests validate :
+1. Valid set commands parse into field and value
+2. Invalid commands, missing arguments and bad numbers are rejected
+3. Applying a command changes the matching config field
+4. Unknown fields and negative values leave the config alone
+5. The log keeps the command and its result, trimmed to the last lines
+
+*/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn test_parse_valid_set() {
+        assert_eq!(
+            parse_command("set gravity 8.5"),
+            Ok(Command::Set { field: "gravity".to_string(), value: 8.5 })
+        );
+        assert_eq!(
+            parse_command("  set   gap 180 "),
+            Ok(Command::Set { field: "gap".to_string(), value: 180.0 })
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_input() {
+        assert!(parse_command("").is_err());
+        assert!(parse_command("jump").is_err());
+        assert!(parse_command("set gravity").is_err());
+        assert!(parse_command("set gravity heavy").is_err());
+        assert!(parse_command("set gravity 1 2").is_err());
+        assert!(parse_command("set gravity NaN").is_err());
+    }
+
+    #[test]
+    fn test_apply_changes_config() {
+        let mut config = GameConfig::new();
+        apply(&mut config, "gravity", 8.5).unwrap();
+        apply(&mut config, "gap", 180.0).unwrap();
+
+        assert_float_eq!(config.gravity, 8.5, abs <= 0.001);
+        assert_float_eq!(config.gap_size, 180.0, abs <= 0.001);
+    }
+
+    #[test]
+    fn test_apply_rejects_unknown_and_negative() {
+        let mut config = GameConfig::new();
+        let gravity = config.gravity;
+
+        assert!(apply(&mut config, "wind", 1.0).is_err());
+        assert!(apply(&mut config, "gravity", -1.0).is_err());
+        assert_float_eq!(config.gravity, gravity, abs <= 0.001);
+    }
+
+    #[test]
+    fn test_log_records_results() {
+        let mut console = Console::new();
+        let mut config = GameConfig::new();
+
+        console.execute("set gap 200", &mut config);
+        assert_eq!(console.log, vec!["> set gap 200".to_string(), "gap = 200".to_string()]);
+
+        for _ in 0..10 {
+            console.execute("nonsense", &mut config);
+        }
+        assert_eq!(console.log.len(), Console::LOG_LINES);
+        assert!(console.log.last().unwrap().starts_with("error:"));
+    }
+}
@@ -0,0 +1,337 @@
+use crate::systems::config::{GameConfig, GameMode};
+
+// Summary - AntiFrustration :
+// Keeps count of how many runs in a row ended below `low_score`.
+// Once that count reaches `death_limit`, the pipe gap is widened by
+// `gap_bonus` until the player manages a good run again.
+pub struct AntiFrustration {
+    pub consecutive_low_deaths: u32,
+    pub low_score: i32,
+    pub death_limit: u32,
+    pub gap_bonus: f32,
+}
+
+impl AntiFrustration {
+    pub fn new() -> Self {
+        AntiFrustration {
+            consecutive_low_deaths: 0,
+            low_score: 3,
+            death_limit: 3,
+            gap_bonus: 40.0,
+        }
+    }
+
+    pub fn record_run(&mut self, score: i32) {
+        if score < self.low_score {
+            self.consecutive_low_deaths += 1;
+        } else {
+            // A good run puts the difficulty back to normal
+            self.consecutive_low_deaths = 0;
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.consecutive_low_deaths >= self.death_limit
+    }
+
+    pub fn gap_size(&self, base_gap: f32) -> f32 {
+        if self.is_active() {
+            base_gap + self.gap_bonus
+        } else {
+            base_gap
+        }
+    }
+}
+
+// Summary - SuddenDeath :
+// Expert preset at the far end of the difficulty range. The gap still
+// leaves room for a few bird heights, so every pipe is passable but only
+// with precise flaps.
+pub struct SuddenDeath;
+
+impl SuddenDeath {
+    pub const GAP_SIZE: f32 = 110.0;
+    pub const SCROLL_SPEED: f32 = 300.0;
+    pub const PIPE_SPACING: f32 = 180.0;
+
+    pub fn apply(config: &mut GameConfig) {
+        config.mode = GameMode::SuddenDeath;
+        config.gap_size = Self::GAP_SIZE;
+        config.scroll_speed = Self::SCROLL_SPEED;
+        config.pipe_spacing = Self::PIPE_SPACING;
+    }
+}
+
+// Summary - LevelConfig :
+// One level of the levels mode: the pipes to pass to complete it and the
+// pipe layout it is played with. The list starts at normal difficulty and
+// tightens towards the sudden death preset.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LevelConfig {
+    pub goal: i32,
+    pub gap_size: f32,
+    pub scroll_speed: f32,
+    pub pipe_spacing: f32,
+}
+
+impl LevelConfig {
+    pub const LIST: [LevelConfig; 5] = [
+        LevelConfig { goal: 5, gap_size: 160.0, scroll_speed: 180.0, pipe_spacing: 240.0 },
+        LevelConfig { goal: 8, gap_size: 150.0, scroll_speed: 204.0, pipe_spacing: 230.0 },
+        LevelConfig { goal: 10, gap_size: 140.0, scroll_speed: 228.0, pipe_spacing: 220.0 },
+        LevelConfig { goal: 12, gap_size: 130.0, scroll_speed: 252.0, pipe_spacing: 210.0 },
+        LevelConfig { goal: 15, gap_size: 120.0, scroll_speed: 276.0, pipe_spacing: 200.0 },
+    ];
+    // Added to the last level for each level played beyond the list
+    const EXTRA_GAP_STEP: f32 = -5.0;
+    const EXTRA_SPEED_STEP: f32 = 12.0;
+    const EXTRA_SPACING_STEP: f32 = -5.0;
+
+    // Level `index`, 0 being the first. Past the end of the list the last
+    // level repeats, a step harder each time until it reaches sudden death.
+    pub fn for_level(index: usize) -> LevelConfig {
+        let last = Self::LIST[Self::LIST.len() - 1];
+        let Some(extra) = index.checked_sub(Self::LIST.len() - 1).filter(|extra| *extra > 0) else {
+            return Self::LIST[index];
+        };
+        let extra = extra as f32;
+        LevelConfig {
+            goal: last.goal,
+            gap_size: (last.gap_size + Self::EXTRA_GAP_STEP * extra).max(SuddenDeath::GAP_SIZE),
+            scroll_speed: (last.scroll_speed + Self::EXTRA_SPEED_STEP * extra).min(SuddenDeath::SCROLL_SPEED),
+            pipe_spacing: (last.pipe_spacing + Self::EXTRA_SPACING_STEP * extra).max(SuddenDeath::PIPE_SPACING),
+        }
+    }
+
+    pub fn apply(&self, config: &mut GameConfig) {
+        config.mode = GameMode::Levels;
+        config.gap_size = self.gap_size;
+        config.scroll_speed = self.scroll_speed;
+        config.pipe_spacing = self.pipe_spacing;
+    }
+
+    pub fn is_complete(&self, score: i32) -> bool {
+        score >= self.goal
+    }
+}
+
+// How much the auto-flap assist helps
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FlapAssist {
+    Off,
+    Light,
+    Strong,
+}
+
+impl FlapAssist {
+    pub const ALL: [FlapAssist; 3] = [FlapAssist::Off, FlapAssist::Light, FlapAssist::Strong];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            FlapAssist::Off => "off",
+            FlapAssist::Light => "light",
+            FlapAssist::Strong => "strong",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<FlapAssist> {
+        Self::ALL.into_iter().find(|assist| assist.name() == name)
+    }
+
+    pub fn cycled(&self) -> FlapAssist {
+        match self {
+            FlapAssist::Off => FlapAssist::Light,
+            FlapAssist::Light => FlapAssist::Strong,
+            FlapAssist::Strong => FlapAssist::Off,
+        }
+    }
+
+    // Pixels the bird may sink past the target before a flap, and the
+    // shortest time between two assisted flaps. None when off.
+    fn tuning(&self) -> Option<(f32, f32)> {
+        match self {
+            FlapAssist::Off => None,
+            FlapAssist::Light => Some((40.0, 0.5)),
+            FlapAssist::Strong => Some((12.0, 0.25)),
+        }
+    }
+}
+
+// Summary - AutoFlap :
+// Accessibility assist that flaps for the player to hold the bird near the
+// next gap centre. The player can still flap on top of it.
+pub struct AutoFlap {
+    pub assist: FlapAssist,
+    cooldown: f32,
+}
+
+impl AutoFlap {
+    pub fn new(assist: FlapAssist) -> Self {
+        AutoFlap { assist, cooldown: 0.0 }
+    }
+
+    // Whether to flap this frame. `sink` is how far the bird centre is past
+    // the target in the direction it falls, `falling` whether it is moving
+    // that way rather than still rising from the last flap.
+    pub fn update(&mut self, dt: f32, sink: f32, falling: bool) -> bool {
+        self.cooldown = (self.cooldown - dt).max(0.0);
+        let Some((threshold, cooldown)) = self.assist.tuning() else {
+            return false;
+        };
+        if self.cooldown > 0.0 || !falling || sink < threshold {
+            return false;
+        }
+        self.cooldown = cooldown;
+        true
+    }
+}
+
+/*
+
+The tests validate :
+1. Low scoring runs increment the death counter
+2. A good run resets the death counter
+3. Gap stays at base size below the death limit
+4. Gap widens once the death limit is reached and returns to normal after a good run
+5. The sudden death preset uses the smallest gap, fastest scroll and tightest spacing
+6. Strong auto-flap flaps when well below the gap centre, not above it, and waits out its cooldown
+7. Auto-flap off never flaps, and the assist levels cycle and round trip by name
+8. A level is complete once its pipe goal is reached
+9. Every level is at least as hard as the one before, also past the end of the list
+
+*/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn test_low_runs_increment_counter() {
+        let mut assist = AntiFrustration::new();
+        assist.record_run(0);
+        assist.record_run(2);
+        assert_eq!(assist.consecutive_low_deaths, 2);
+    }
+
+    #[test]
+    fn test_good_run_resets_counter() {
+        let mut assist = AntiFrustration::new();
+        assist.record_run(0);
+        assist.record_run(1);
+        assist.record_run(assist.low_score);
+        assert_eq!(assist.consecutive_low_deaths, 0);
+    }
+
+    #[test]
+    fn test_gap_unchanged_below_limit() {
+        let mut assist = AntiFrustration::new();
+        for _ in 0..assist.death_limit - 1 {
+            assist.record_run(0);
+        }
+        assert!(!assist.is_active());
+        assert_float_eq!(assist.gap_size(160.0), 160.0, abs <= 0.001);
+    }
+
+    #[test]
+    fn test_gap_widens_at_limit_and_recovers() {
+        let mut assist = AntiFrustration::new();
+        for _ in 0..assist.death_limit {
+            assist.record_run(0);
+        }
+        assert!(assist.is_active());
+        assert_float_eq!(assist.gap_size(160.0), 160.0 + assist.gap_bonus, abs <= 0.001);
+
+        assist.record_run(10);
+        assert_float_eq!(assist.gap_size(160.0), 160.0, abs <= 0.001);
+    }
+
+    #[test]
+    fn test_sudden_death_preset_is_extreme() {
+        let mut config = GameConfig::new();
+        let normal = GameConfig::new();
+        SuddenDeath::apply(&mut config);
+
+        assert_eq!(config.mode, GameMode::SuddenDeath);
+        assert_float_eq!(config.gap_size, 110.0, abs <= 0.001);
+        assert_float_eq!(config.scroll_speed, 300.0, abs <= 0.001);
+        assert_float_eq!(config.pipe_spacing, 180.0, abs <= 0.001);
+
+        assert!(config.gap_size < normal.gap_size);
+        assert!(config.scroll_speed > normal.scroll_speed);
+        assert!(config.pipe_spacing < normal.pipe_spacing);
+    }
+
+    #[test]
+    fn test_strong_auto_flap_below_gap_only() {
+        let mut auto_flap = AutoFlap::new(FlapAssist::Strong);
+
+        // Above the gap centre, or only just below it
+        assert!(!auto_flap.update(1.0 / 60.0, -30.0, true));
+        assert!(!auto_flap.update(1.0 / 60.0, 5.0, true));
+        // Below but still rising from a flap
+        assert!(!auto_flap.update(1.0 / 60.0, 30.0, false));
+
+        assert!(auto_flap.update(1.0 / 60.0, 30.0, true));
+        // Not again straight away
+        assert!(!auto_flap.update(1.0 / 60.0, 30.0, true));
+        assert!(auto_flap.update(0.5, 30.0, true));
+    }
+
+    #[test]
+    fn test_auto_flap_off_and_levels() {
+        let mut auto_flap = AutoFlap::new(FlapAssist::Off);
+        assert!(!auto_flap.update(1.0, 500.0, true));
+
+        // Light waits for a bigger drop than strong
+        let mut light = AutoFlap::new(FlapAssist::Light);
+        assert!(!light.update(1.0 / 60.0, 30.0, true));
+        assert!(light.update(1.0 / 60.0, 50.0, true));
+
+        for assist in FlapAssist::ALL {
+            assert_eq!(FlapAssist::from_name(assist.name()), Some(assist));
+        }
+        assert_eq!(FlapAssist::Strong.cycled(), FlapAssist::Off);
+        assert_eq!(FlapAssist::from_name("max"), None);
+    }
+
+    #[test]
+    fn test_level_goal_detection() {
+        let first = LevelConfig::for_level(0);
+        assert!(!first.is_complete(0));
+        assert!(!first.is_complete(first.goal - 1));
+        assert!(first.is_complete(first.goal));
+        assert!(first.is_complete(first.goal + 3));
+
+        let mut config = GameConfig::new();
+        LevelConfig::for_level(2).apply(&mut config);
+        assert_eq!(config.mode, GameMode::Levels);
+        assert_float_eq!(config.gap_size, 140.0, abs <= 0.001);
+        assert_float_eq!(config.scroll_speed, 228.0, abs <= 0.001);
+        assert_float_eq!(config.pipe_spacing, 220.0, abs <= 0.001);
+    }
+
+    #[test]
+    fn test_levels_escalate() {
+        assert_eq!(LevelConfig::for_level(0), LevelConfig::LIST[0]);
+        for index in 1..20 {
+            let (easier, harder) = (LevelConfig::for_level(index - 1), LevelConfig::for_level(index));
+            assert!(harder.goal >= easier.goal);
+            assert!(harder.gap_size <= easier.gap_size);
+            assert!(harder.scroll_speed >= easier.scroll_speed);
+            assert!(harder.pipe_spacing <= easier.pipe_spacing);
+        }
+
+        // One step past the list is harder than its last level
+        let last = LevelConfig::LIST[LevelConfig::LIST.len() - 1];
+        let beyond = LevelConfig::for_level(LevelConfig::LIST.len());
+        assert!(beyond.gap_size < last.gap_size);
+        assert!(beyond.scroll_speed > last.scroll_speed);
+
+        // And it never gets harder than sudden death
+        let far = LevelConfig::for_level(100);
+        assert_float_eq!(far.gap_size, SuddenDeath::GAP_SIZE, abs <= 0.001);
+        assert_float_eq!(far.scroll_speed, SuddenDeath::SCROLL_SPEED, abs <= 0.001);
+        assert_float_eq!(far.pipe_spacing, SuddenDeath::PIPE_SPACING, abs <= 0.001);
+    }
+}
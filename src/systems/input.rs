@@ -0,0 +1,108 @@
+// Where rumble requests end up, swapped for a recording one in tests
+pub trait RumbleBackend {
+    fn gamepad_connected(&self) -> bool;
+    fn vibrate(&mut self, strength: f32, duration: f32);
+}
+
+// macroquad has no gamepad support yet, so there is never anything to shake
+pub struct NoGamepad;
+
+impl RumbleBackend for NoGamepad {
+    fn gamepad_connected(&self) -> bool {
+        false
+    }
+
+    fn vibrate(&mut self, _strength: f32, _duration: f32) {}
+}
+
+// Summary - Rumble :
+// Gamepad vibration behind the rumble setting. Requests are dropped when
+// the setting is off or no gamepad is connected.
+pub struct Rumble {
+    backend: Box<dyn RumbleBackend>,
+    pub enabled: bool,
+}
+
+impl Rumble {
+    pub fn new(enabled: bool) -> Self {
+        Self::with_backend(enabled, Box::new(NoGamepad))
+    }
+
+    pub fn with_backend(enabled: bool, backend: Box<dyn RumbleBackend>) -> Self {
+        Rumble { backend, enabled }
+    }
+
+    // `strength` from 0 to 1, `duration` in seconds. Returns true if the
+    // request reached a gamepad.
+    pub fn rumble(&mut self, strength: f32, duration: f32) -> bool {
+        if !self.enabled || !self.backend.gamepad_connected() {
+            return false;
+        }
+        self.backend.vibrate(strength.clamp(0.0, 1.0), duration.max(0.0));
+        true
+    }
+}
+
+/*
+
+The tests validate :
+1. Rumble reaches the gamepad only when enabled and one is connected
+2. Strength is clamped to 0-1
+
+*/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use float_eq::assert_float_eq;
+
+    // (strength, duration) of every vibrate call
+    type Calls = Rc<RefCell<Vec<(f32, f32)>>>;
+
+    struct Recorder {
+        connected: bool,
+        calls: Calls,
+    }
+
+    impl RumbleBackend for Recorder {
+        fn gamepad_connected(&self) -> bool {
+            self.connected
+        }
+
+        fn vibrate(&mut self, strength: f32, duration: f32) {
+            self.calls.borrow_mut().push((strength, duration));
+        }
+    }
+
+    fn rumble(enabled: bool, connected: bool) -> (Rumble, Calls) {
+        let calls = Rc::new(RefCell::new(Vec::new()));
+        let backend = Recorder { connected, calls: Rc::clone(&calls) };
+        (Rumble::with_backend(enabled, Box::new(backend)), calls)
+    }
+
+    #[test]
+    fn test_rumble_gating() {
+        let (mut enabled, calls) = rumble(true, true);
+        assert!(enabled.rumble(0.5, 0.2));
+        assert_eq!(calls.borrow().len(), 1);
+
+        let (mut disabled, calls) = rumble(false, true);
+        assert!(!disabled.rumble(0.5, 0.2));
+        assert!(calls.borrow().is_empty());
+
+        let (mut unplugged, calls) = rumble(true, false);
+        assert!(!unplugged.rumble(0.5, 0.2));
+        assert!(calls.borrow().is_empty());
+
+        assert!(!Rumble::new(true).rumble(0.5, 0.2));
+    }
+
+    #[test]
+    fn test_strength_is_clamped() {
+        let (mut rumble, calls) = rumble(true, true);
+        rumble.rumble(3.0, 0.2);
+        assert_float_eq!(calls.borrow()[0].0, 1.0, abs <= 0.001);
+    }
+}
@@ -0,0 +1,237 @@
+use std::collections::HashMap;
+use std::fs;
+use macroquad::prelude::*;
+use crate::KEYS_FILE_NAME;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Flap,
+    Back,
+    Settings,
+    MenuUp,
+    MenuDown,
+    VolumeDown,
+    VolumeUp,
+    Console,
+    ToggleQuality,
+    ToggleDebug,
+    InvertControls,
+    ToggleRewind,
+    ToggleRumble,
+    TimeScale,
+    HighContrast,
+    MinimalBackground,
+    PipePreviews,
+    FlapAssist,
+    Continue,
+    DebugScoreboard,
+}
+
+impl Action {
+    pub const ALL: [Action; 20] = [
+        Action::Flap,
+        Action::Back,
+        Action::Settings,
+        Action::MenuUp,
+        Action::MenuDown,
+        Action::VolumeDown,
+        Action::VolumeUp,
+        Action::Console,
+        Action::ToggleQuality,
+        Action::ToggleDebug,
+        Action::InvertControls,
+        Action::ToggleRewind,
+        Action::ToggleRumble,
+        Action::TimeScale,
+        Action::HighContrast,
+        Action::MinimalBackground,
+        Action::PipePreviews,
+        Action::FlapAssist,
+        Action::Continue,
+        Action::DebugScoreboard,
+    ];
+
+    // Name used for the action in keys.toml
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::Flap => "flap",
+            Action::Back => "back",
+            Action::Settings => "settings",
+            Action::MenuUp => "menu_up",
+            Action::MenuDown => "menu_down",
+            Action::VolumeDown => "volume_down",
+            Action::VolumeUp => "volume_up",
+            Action::Console => "console",
+            Action::ToggleQuality => "toggle_quality",
+            Action::ToggleDebug => "toggle_debug",
+            Action::InvertControls => "invert_controls",
+            Action::ToggleRewind => "toggle_rewind",
+            Action::ToggleRumble => "toggle_rumble",
+            Action::TimeScale => "time_scale",
+            Action::HighContrast => "high_contrast",
+            Action::MinimalBackground => "minimal_background",
+            Action::PipePreviews => "pipe_previews",
+            Action::FlapAssist => "flap_assist",
+            Action::Continue => "continue",
+            Action::DebugScoreboard => "debug_scoreboard",
+        }
+    }
+
+    pub fn default_key(&self) -> KeyCode {
+        match self {
+            Action::Flap => KeyCode::Space,
+            Action::Back => KeyCode::Escape,
+            Action::Settings => KeyCode::S,
+            Action::MenuUp => KeyCode::Up,
+            Action::MenuDown => KeyCode::Down,
+            Action::VolumeDown => KeyCode::Left,
+            Action::VolumeUp => KeyCode::Right,
+            Action::Console => KeyCode::GraveAccent,
+            Action::ToggleQuality => KeyCode::G,
+            Action::ToggleDebug => KeyCode::F3,
+            Action::InvertControls => KeyCode::I,
+            Action::ToggleRewind => KeyCode::R,
+            Action::ToggleRumble => KeyCode::V,
+            Action::TimeScale => KeyCode::F4,
+            Action::HighContrast => KeyCode::H,
+            Action::MinimalBackground => KeyCode::B,
+            Action::PipePreviews => KeyCode::P,
+            Action::FlapAssist => KeyCode::A,
+            Action::Continue => KeyCode::C,
+            Action::DebugScoreboard => KeyCode::F2,
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Action> {
+        Self::ALL.into_iter().find(|action| action.name() == name)
+    }
+}
+
+// Key names accepted in keys.toml (matched case-insensitively)
+const KEY_NAMES: &[(&str, KeyCode)] = &[
+    ("A", KeyCode::A), ("B", KeyCode::B), ("C", KeyCode::C), ("D", KeyCode::D),
+    ("E", KeyCode::E), ("F", KeyCode::F), ("G", KeyCode::G), ("H", KeyCode::H),
+    ("I", KeyCode::I), ("J", KeyCode::J), ("K", KeyCode::K), ("L", KeyCode::L),
+    ("M", KeyCode::M), ("N", KeyCode::N), ("O", KeyCode::O), ("P", KeyCode::P),
+    ("Q", KeyCode::Q), ("R", KeyCode::R), ("S", KeyCode::S), ("T", KeyCode::T),
+    ("U", KeyCode::U), ("V", KeyCode::V), ("W", KeyCode::W), ("X", KeyCode::X),
+    ("Y", KeyCode::Y), ("Z", KeyCode::Z), ("0", KeyCode::Key0), ("1", KeyCode::Key1),
+    ("2", KeyCode::Key2), ("3", KeyCode::Key3), ("4", KeyCode::Key4), ("5", KeyCode::Key5),
+    ("6", KeyCode::Key6), ("7", KeyCode::Key7), ("8", KeyCode::Key8), ("9", KeyCode::Key9),
+    ("Space", KeyCode::Space), ("GraveAccent", KeyCode::GraveAccent), ("Escape", KeyCode::Escape), ("Enter", KeyCode::Enter),
+    ("Tab", KeyCode::Tab), ("Backspace", KeyCode::Backspace), ("Insert", KeyCode::Insert),
+    ("Delete", KeyCode::Delete), ("Up", KeyCode::Up), ("Down", KeyCode::Down),
+    ("Left", KeyCode::Left), ("Right", KeyCode::Right), ("PageUp", KeyCode::PageUp),
+    ("PageDown", KeyCode::PageDown), ("Home", KeyCode::Home), ("End", KeyCode::End),
+    ("LeftShift", KeyCode::LeftShift), ("RightShift", KeyCode::RightShift),
+    ("LeftControl", KeyCode::LeftControl), ("RightControl", KeyCode::RightControl),
+    ("LeftAlt", KeyCode::LeftAlt), ("RightAlt", KeyCode::RightAlt), ("F1", KeyCode::F1),
+    ("F2", KeyCode::F2), ("F3", KeyCode::F3), ("F4", KeyCode::F4), ("F5", KeyCode::F5),
+    ("F6", KeyCode::F6), ("F7", KeyCode::F7), ("F8", KeyCode::F8), ("F9", KeyCode::F9),
+    ("F10", KeyCode::F10), ("F11", KeyCode::F11), ("F12", KeyCode::F12),
+];
+
+pub fn key_from_name(name: &str) -> Option<KeyCode> {
+    KEY_NAMES
+        .iter()
+        .find(|(key_name, _)| key_name.eq_ignore_ascii_case(name))
+        .map(|(_, key)| *key)
+}
+
+// Summary - KeyBindings :
+// Which key triggers each action. Read from keys.toml, a flat TOML table of
+// `action = "Key"` pairs, e.g. `flap = "W"`. Actions that are missing or
+// bound to an unknown key keep their default.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyBindings {
+    keys: HashMap<Action, KeyCode>,
+}
+
+impl KeyBindings {
+    pub fn new() -> Self {
+        KeyBindings {
+            keys: Action::ALL.iter().map(|action| (*action, action.default_key())).collect(),
+        }
+    }
+
+    pub fn load() -> Self {
+        Self::load_from(KEYS_FILE_NAME)
+    }
+
+    pub fn load_from(path: &str) -> Self {
+        let mut bindings = KeyBindings::new();
+        if let Ok(content) = fs::read_to_string(path) {
+            for line in content.lines() {
+                // Drop comments, section headers aren't used
+                let line = line.split('#').next().unwrap_or("").trim();
+                if let Some((action, key)) = line.split_once('=') {
+                    bindings.apply(action.trim(), key.trim().trim_matches('"'));
+                }
+            }
+        }
+        bindings
+    }
+
+    fn apply(&mut self, action: &str, key: &str) {
+        if let (Some(action), Some(key)) = (Action::from_name(action), key_from_name(key)) {
+            self.keys.insert(action, key);
+        }
+    }
+
+    pub fn key(&self, action: Action) -> KeyCode {
+        self.keys.get(&action).copied().unwrap_or(action.default_key())
+    }
+
+    pub fn is_action_pressed(&self, action: Action) -> bool {
+        is_key_pressed(self.key(action))
+    }
+}
+
+/*
+
+The tests validate :
+1. Defaults apply without a bindings file
+2. A custom binding changes the flap key
+3. Unknown key names and actions fall back to the defaults
+
+*/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_defaults_without_file() {
+        let tmp = NamedTempFile::new().unwrap();
+        let missing = tmp.path().with_extension("missing");
+        let bindings = KeyBindings::load_from(missing.to_str().unwrap());
+
+        assert_eq!(bindings, KeyBindings::new());
+        assert_eq!(bindings.key(Action::Flap), KeyCode::Space);
+        assert_eq!(bindings.key(Action::Back), KeyCode::Escape);
+    }
+
+    #[test]
+    fn test_custom_flap_binding() {
+        let tmp = NamedTempFile::new().unwrap();
+        let path = tmp.path().to_str().unwrap();
+        fs::write(path, "# my keys\nflap = \"W\"\nback = \"q\"\n").unwrap();
+
+        let bindings = KeyBindings::load_from(path);
+        assert_eq!(bindings.key(Action::Flap), KeyCode::W);
+        assert_eq!(bindings.key(Action::Back), KeyCode::Q);
+        assert_eq!(bindings.key(Action::Settings), KeyCode::S);
+    }
+
+    #[test]
+    fn test_invalid_key_falls_back_to_default() {
+        let tmp = NamedTempFile::new().unwrap();
+        let path = tmp.path().to_str().unwrap();
+        fs::write(path, "flap = \"NotAKey\"\njump = \"J\"\n").unwrap();
+
+        let bindings = KeyBindings::load_from(path);
+        assert_eq!(bindings.key(Action::Flap), KeyCode::Space);
+        assert_eq!(bindings, KeyBindings::new());
+    }
+}
@@ -0,0 +1,157 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use macroquad::prelude::*;
+use crate::systems::letterbox::{game_height, game_width};
+
+// Summary - Layout :
+// Screen positions worked out from the current screen size and named
+// ratios, so the scenes line up the same way at any resolution.
+// The ground height comes from the ground texture rather than a guess.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Layout {
+    pub screen: Vec2,
+    pub ground_height: f32,
+}
+
+impl Layout {
+    // Vertical positions as fractions of the screen height
+    pub const HEADER_Y_RATIO: f32 = 0.25;
+    pub const COUNTDOWN_Y_RATIO: f32 = 0.4;
+    pub const INSTRUCTIONS_Y_RATIO: f32 = 0.6;
+
+    pub fn new(screen: Vec2, ground_height: f32) -> Self {
+        Layout { screen, ground_height }
+    }
+
+    pub fn current(ground_height: f32) -> Self {
+        Self::new(vec2(game_width(), game_height()), ground_height)
+    }
+
+    // Top of the ground, where the playfield ends
+    pub fn ground_y(&self) -> f32 {
+        self.screen.y - self.ground_height
+    }
+
+    // Left edge that centres something `width` wide on the screen
+    pub fn centered_x(&self, width: f32) -> f32 {
+        self.screen.x / 2.0 - width / 2.0
+    }
+
+    pub fn y_at(&self, ratio: f32) -> f32 {
+        self.screen.y * ratio
+    }
+
+    // X positions of the copies needed to tile a texture across the screen
+    pub fn tile_offsets(&self, tile_width: f32) -> Vec<f32> {
+        self.scrolled_tile_offsets(tile_width, 0.0)
+    }
+
+    // Same for a strip scrolled left by `scroll_pos` (zero or negative).
+    // A texture that failed to load has no width, so nothing is drawn
+    // rather than dividing by zero.
+    pub fn scrolled_tile_offsets(&self, tile_width: f32, scroll_pos: f32) -> Vec<f32> {
+        if !(tile_width > 0.0 && tile_width.is_finite() && scroll_pos.is_finite()) {
+            warn_zero_width_once();
+            return Vec::new();
+        }
+        let count = ((self.screen.x - scroll_pos) / tile_width).ceil().max(1.0) as usize;
+        (0..count).map(|i| scroll_pos + i as f32 * tile_width).collect()
+    }
+}
+
+// Wraps a scroll position into [-tile_width, 0), left alone for empty
+// textures. Always landing in the same range keeps long sessions from
+// drifting, and the tile offsets are worked out from it fresh each draw.
+pub fn wrap_scroll(scroll_pos: f32, tile_width: f32) -> f32 {
+    if tile_width > 0.0 && tile_width.is_finite() {
+        let wrapped = scroll_pos.rem_euclid(tile_width) - tile_width;
+        // rem_euclid can round up to the full width for tiny negative positions
+        if wrapped < 0.0 { wrapped } else { -tile_width }
+    } else {
+        scroll_pos
+    }
+}
+
+static ZERO_WIDTH_WARNED: AtomicBool = AtomicBool::new(false);
+
+// Would otherwise repeat every frame
+fn warn_zero_width_once() {
+    if !ZERO_WIDTH_WARNED.swap(true, Ordering::Relaxed) {
+        log::warn!("Not tiling a texture without width, it probably failed to load");
+    }
+}
+
+/*
+
+The tests validate :
+1. Ground top follows the screen height and ground texture height
+2. Centring and ratio positions scale with the resolution
+3. Tiles cover the whole screen width
+4. Scrolled tiles still cover the screen, zero-width textures get no copies
+5. Wrapped scroll positions always land in [-width, 0)
+
+*/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    fn small() -> Layout {
+        Layout::new(vec2(800.0, 600.0), 112.0)
+    }
+
+    fn large() -> Layout {
+        Layout::new(vec2(1920.0, 1080.0), 112.0)
+    }
+
+    #[test]
+    fn test_ground_y() {
+        assert_float_eq!(small().ground_y(), 488.0, abs <= 0.001);
+        assert_float_eq!(large().ground_y(), 968.0, abs <= 0.001);
+    }
+
+    #[test]
+    fn test_positions_scale_with_resolution() {
+        assert_float_eq!(small().centered_x(184.0), 308.0, abs <= 0.001);
+        assert_float_eq!(large().centered_x(184.0), 868.0, abs <= 0.001);
+
+        assert_float_eq!(small().y_at(Layout::HEADER_Y_RATIO), 150.0, abs <= 0.001);
+        assert_float_eq!(large().y_at(Layout::HEADER_Y_RATIO), 270.0, abs <= 0.001);
+        assert_float_eq!(small().y_at(Layout::INSTRUCTIONS_Y_RATIO), 360.0, abs <= 0.001);
+        assert_float_eq!(large().y_at(Layout::INSTRUCTIONS_Y_RATIO), 648.0, abs <= 0.001);
+    }
+
+    #[test]
+    fn test_tiles_cover_screen() {
+        let tiles = small().tile_offsets(288.0);
+        assert_eq!(tiles, vec![0.0, 288.0, 576.0]);
+
+        let tiles = large().tile_offsets(288.0);
+        assert_eq!(tiles.len(), 7);
+        assert!(tiles.last().unwrap() + 288.0 >= 1920.0);
+
+        assert!(small().tile_offsets(0.0).is_empty());
+    }
+
+    #[test]
+    fn test_scrolled_tiles() {
+        let tiles = small().scrolled_tile_offsets(288.0, -100.0);
+        assert_eq!(tiles, vec![-100.0, 188.0, 476.0, 764.0]);
+
+        assert!(small().scrolled_tile_offsets(0.0, -100.0).is_empty());
+        assert!(small().scrolled_tile_offsets(288.0, f32::NAN).is_empty());
+
+        assert_float_eq!(wrap_scroll(-300.0, 288.0), -12.0, abs <= 0.001);
+        assert_float_eq!(wrap_scroll(-300.0, 0.0), -300.0, abs <= 0.001);
+    }
+
+    #[test]
+    fn test_wrap_scroll_range() {
+        assert_float_eq!(wrap_scroll(0.0, 288.0), -288.0, abs <= 0.001);
+        assert_float_eq!(wrap_scroll(-288.0, 288.0), -288.0, abs <= 0.001);
+        assert_float_eq!(wrap_scroll(-1e-9, 288.0), -288.0, abs <= 0.001);
+        assert_float_eq!(wrap_scroll(-576.5, 288.0), -0.5, abs <= 0.001);
+        assert_float_eq!(wrap_scroll(10.0, 288.0), -278.0, abs <= 0.001);
+        assert_float_eq!(wrap_scroll(-5.0, f32::INFINITY), -5.0, abs <= 0.001);
+    }
+}
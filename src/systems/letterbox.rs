@@ -0,0 +1,210 @@
+use std::cell::{Cell, RefCell};
+use macroquad::prelude::*;
+
+// Height of the game area when letterboxing, the width follows the aspect ratio
+pub const GAME_HEIGHT: f32 = 600.0;
+
+// Summary - Letterbox :
+// Fits a fixed aspect ratio game area into the window, scaled up or down
+// uniformly and centred, leaving black bars on the other axis.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Letterbox {
+    // Size of the game area in game coordinates
+    pub game_size: Vec2,
+    // Where the game area ends up in the window, in window pixels
+    pub viewport: Rect,
+    pub scale: f32,
+}
+
+impl Letterbox {
+    // `aspect_ratio` is width / height, e.g. 9.0 / 16.0 for portrait
+    pub fn fit(window: Vec2, aspect_ratio: f32, game_height: f32) -> Self {
+        let game_size = vec2(game_height * aspect_ratio, game_height);
+        let scale = (window.x / game_size.x).min(window.y / game_size.y);
+        let size = game_size * scale;
+        let offset = (window - size) / 2.0;
+
+        Letterbox {
+            game_size,
+            viewport: Rect::new(offset.x, offset.y, size.x, size.y),
+            scale,
+        }
+    }
+
+    // Maps a point in window pixels (e.g. the mouse) into game coordinates
+    pub fn to_game(self, point: Vec2) -> Vec2 {
+        (point - vec2(self.viewport.x, self.viewport.y)) / self.scale
+    }
+
+    // Camera drawing the game area into the viewport. GL viewports count y
+    // from the bottom of the window.
+    pub fn camera(&self, window_height: f32) -> Camera2D {
+        let gl_y = window_height - self.viewport.y - self.viewport.h;
+        Camera2D {
+            target: self.game_size / 2.0,
+            zoom: vec2(2.0 / self.game_size.x, 2.0 / self.game_size.y),
+            viewport: Some((
+                self.viewport.x.round() as i32,
+                gl_y.round() as i32,
+                self.viewport.w.round() as i32,
+                self.viewport.h.round() as i32,
+            )),
+            ..Default::default()
+        }
+    }
+}
+
+// Accepts "9:16" style ratios or a plain number
+pub fn parse_aspect_ratio(value: &str) -> Option<f32> {
+    let ratio = match value.split_once(':') {
+        Some((width, height)) => width.trim().parse::<f32>().ok()? / height.trim().parse::<f32>().ok()?,
+        None => value.trim().parse::<f32>().ok()?,
+    };
+    (ratio.is_finite() && ratio > 0.0).then_some(ratio)
+}
+
+thread_local! {
+    // Letterbox for the current frame, None when the game fills the window
+    static CURRENT: Cell<Option<Letterbox>> = const { Cell::new(None) };
+    // Texture draw_into_texture() is filling, None when drawing into the window
+    static TARGET: RefCell<Option<RenderTarget>> = const { RefCell::new(None) };
+}
+
+pub fn set_current(letterbox: Option<Letterbox>) {
+    CURRENT.with(|current| current.set(letterbox));
+}
+
+pub fn current() -> Option<Letterbox> {
+    CURRENT.with(|current| current.get())
+}
+
+// Size of the game area. Use these instead of screen_width()/screen_height()
+// so layout follows the letterbox.
+pub fn game_width() -> f32 {
+    current().map_or_else(screen_width, |letterbox| letterbox.game_size.x)
+}
+
+pub fn game_height() -> f32 {
+    current().map_or_else(screen_height, |letterbox| letterbox.game_size.y)
+}
+
+// A point in window pixels (e.g. a touch) in game coordinates
+pub fn game_position(point: Vec2) -> Vec2 {
+    current().map_or(point, |letterbox| letterbox.to_game(point))
+}
+
+pub fn game_mouse_position() -> Vec2 {
+    game_position(mouse_position().into())
+}
+
+// Viewport for cameras that draw into the game area. A texture holds only
+// the game area, so it has none.
+pub fn viewport() -> Option<(i32, i32, i32, i32)> {
+    if current_target().is_some() {
+        return None;
+    }
+    current().and_then(|letterbox| letterbox.camera(screen_height()).viewport)
+}
+
+// Render target for cameras that draw into the game area
+pub fn current_target() -> Option<RenderTarget> {
+    TARGET.with(|target| target.borrow().clone())
+}
+
+// Back to drawing in game coordinates, for use instead of set_default_camera()
+pub fn set_game_camera() {
+    if let Some(target) = current_target() {
+        let size = vec2(game_width(), game_height());
+        set_camera(&Camera2D {
+            target: size / 2.0,
+            zoom: vec2(2.0 / size.x, 2.0 / size.y),
+            render_target: Some(target),
+            ..Default::default()
+        });
+        return;
+    }
+    match current() {
+        Some(letterbox) => set_camera(&letterbox.camera(screen_height())),
+        None => set_default_camera(),
+    }
+}
+
+// Runs `draw` into a texture of the game area at its size on screen, for
+// showing the frame again later with draw_texture_ex() over the game area.
+// Cameras set through set_game_camera() while drawing stay on the texture.
+pub fn draw_into_texture(draw: impl FnOnce()) -> RenderTarget {
+    let size = current().map_or_else(|| vec2(screen_width(), screen_height()), |letterbox| letterbox.viewport.size());
+    let target = render_target((size.x as u32).max(1), (size.y as u32).max(1));
+    target.texture.set_filter(FilterMode::Nearest);
+
+    TARGET.with(|current| *current.borrow_mut() = Some(target.clone()));
+    set_game_camera();
+    clear_background(BLACK);
+    draw();
+    TARGET.with(|current| *current.borrow_mut() = None);
+    set_game_camera();
+    target
+}
+
+/*
+
+The tests validate :
+1. Wide windows get bars on the sides, tall windows above and below
+2. Screen points map into game coordinates through the offset and scale
+3. Aspect ratios parse from "w:h" or a number, invalid ones are rejected
+
+*/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn test_fit_adds_bars() {
+        // Portrait 9:16 in a landscape window: bars left and right
+        let letterbox = Letterbox::fit(vec2(1600.0, 900.0), 9.0 / 16.0, 640.0);
+        assert_float_eq!(letterbox.game_size.x, 360.0, abs <= 0.001);
+        assert_float_eq!(letterbox.scale, 900.0 / 640.0, abs <= 0.001);
+        assert_float_eq!(letterbox.viewport.h, 900.0, abs <= 0.001);
+        assert_float_eq!(letterbox.viewport.x, (1600.0 - 360.0 * 900.0 / 640.0) / 2.0, abs <= 0.001);
+        assert_float_eq!(letterbox.viewport.y, 0.0, abs <= 0.001);
+
+        // Landscape 4:3 in a tall window: bars top and bottom
+        let letterbox = Letterbox::fit(vec2(400.0, 800.0), 4.0 / 3.0, 600.0);
+        assert_float_eq!(letterbox.viewport.w, 400.0, abs <= 0.001);
+        assert_float_eq!(letterbox.viewport.h, 300.0, abs <= 0.001);
+        assert_float_eq!(letterbox.viewport.y, 250.0, abs <= 0.001);
+    }
+
+    #[test]
+    fn test_mouse_maps_into_game_space() {
+        let letterbox = Letterbox::fit(vec2(1600.0, 900.0), 9.0 / 16.0, 640.0);
+
+        // Top-left and bottom-right corners of the game area
+        let top_left = letterbox.to_game(vec2(letterbox.viewport.x, 0.0));
+        assert_float_eq!(top_left.x, 0.0, abs <= 0.001);
+        assert_float_eq!(top_left.y, 0.0, abs <= 0.001);
+
+        let bottom_right = letterbox.to_game(vec2(letterbox.viewport.right(), 900.0));
+        assert_float_eq!(bottom_right.x, 360.0, abs <= 0.01);
+        assert_float_eq!(bottom_right.y, 640.0, abs <= 0.01);
+
+        // The window centre is the game centre
+        let center = letterbox.to_game(vec2(800.0, 450.0));
+        assert_float_eq!(center.x, 180.0, abs <= 0.01);
+        assert_float_eq!(center.y, 320.0, abs <= 0.01);
+
+        // Clicks on the bars land outside the game area
+        assert!(letterbox.to_game(vec2(10.0, 450.0)).x < 0.0);
+    }
+
+    #[test]
+    fn test_parse_aspect_ratio() {
+        assert_float_eq!(parse_aspect_ratio("9:16").unwrap(), 0.5625, abs <= 0.0001);
+        assert_float_eq!(parse_aspect_ratio(" 1.5 ").unwrap(), 1.5, abs <= 0.0001);
+        assert_eq!(parse_aspect_ratio("wide"), None);
+        assert_eq!(parse_aspect_ratio("16:0"), None);
+        assert_eq!(parse_aspect_ratio("-2"), None);
+    }
+}
@@ -0,0 +1,99 @@
+use log::{LevelFilter, Log, Metadata, Record};
+
+// Summary - StderrLogger :
+// Minimal backend for the `log` facade. Gameplay events are logged from
+// the simulation, and RUST_LOG picks how much of it ends up on stderr,
+// e.g. `RUST_LOG=debug` to include every flap.
+struct StderrLogger;
+
+impl Log for StderrLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            eprintln!("[{} {}] {}", record.level(), record.target(), record.args());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: StderrLogger = StderrLogger;
+
+// Level named by RUST_LOG, warnings only when unset or unknown
+pub fn level_from_env(value: Option<&str>) -> LevelFilter {
+    value
+        .and_then(|value| value.trim().parse().ok())
+        .unwrap_or(LevelFilter::Warn)
+}
+
+pub fn init() {
+    let level = level_from_env(std::env::var("RUST_LOG").ok().as_deref());
+    if log::set_logger(&LOGGER).is_ok() {
+        log::set_max_level(level);
+    }
+}
+
+// Test logger keeping each thread's messages apart, so tests running in
+// parallel only see their own
+#[cfg(test)]
+pub mod capture {
+    use std::cell::RefCell;
+    use std::sync::Once;
+    use log::{LevelFilter, Log, Metadata, Record};
+
+    struct CaptureLogger;
+
+    thread_local! {
+        static MESSAGES: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+    }
+
+    impl Log for CaptureLogger {
+        fn enabled(&self, _metadata: &Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &Record) {
+            MESSAGES.with(|messages| messages.borrow_mut().push(format!("{} {}", record.level(), record.args())));
+        }
+
+        fn flush(&self) {}
+    }
+
+    static LOGGER: CaptureLogger = CaptureLogger;
+    static INIT: Once = Once::new();
+
+    // Runs `f` and returns what it logged, as "LEVEL message" lines
+    pub fn logs_of(f: impl FnOnce()) -> Vec<String> {
+        INIT.call_once(|| {
+            let _ = log::set_logger(&LOGGER);
+            log::set_max_level(LevelFilter::Trace);
+        });
+        MESSAGES.with(|messages| messages.borrow_mut().clear());
+        f();
+        MESSAGES.with(|messages| messages.take())
+    }
+}
+
+/*
+
+The tests validate :
+1. RUST_LOG values map to log levels, falling back to warnings
+
+*/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_level_from_env() {
+        assert_eq!(level_from_env(Some("debug")), LevelFilter::Debug);
+        assert_eq!(level_from_env(Some("INFO")), LevelFilter::Info);
+        assert_eq!(level_from_env(Some("off")), LevelFilter::Off);
+        assert_eq!(level_from_env(Some("chatty")), LevelFilter::Warn);
+        assert_eq!(level_from_env(None), LevelFilter::Warn);
+    }
+}
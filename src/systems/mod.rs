@@ -1,3 +1,29 @@
+pub mod analytics;
+pub mod audio;
+pub mod benchmark;
+pub mod best_times;
+pub mod bitmap_font;
+pub mod camera;
+pub mod config;
+pub mod console;
+pub mod difficulty;
+pub mod input;
+pub mod keybindings;
+pub mod layout;
+pub mod letterbox;
+pub mod logging;
 pub mod physics;
+pub mod platform;
+pub mod records;
+pub mod replay;
+pub mod resources;
+pub mod rewind;
 pub mod scenemanagement;
-pub mod storage;
\ No newline at end of file
+pub mod settings;
+pub mod simulation;
+pub mod storage;
+#[cfg(test)]
+pub mod test_support;
+pub mod theme;
+pub mod ui;
+pub mod window_icon;
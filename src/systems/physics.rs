@@ -12,6 +12,51 @@ pub fn check_collision(rect1: &Rect, rect2: &Rect) -> bool {
         && rect1.y + rect1.h > rect2.y
 }
 
+// The region two rects share, for placing effects at the contact point.
+// None when check_collision is false, so touching edges don't count.
+pub fn collision_overlap(rect1: &Rect, rect2: &Rect) -> Option<Rect> {
+    if !check_collision(rect1, rect2) {
+        return None;
+    }
+    let left = rect1.x.max(rect2.x);
+    let top = rect1.y.max(rect2.y);
+    let right = (rect1.x + rect1.w).min(rect2.x + rect2.w);
+    let bottom = (rect1.y + rect1.h).min(rect2.y + rect2.h);
+    Some(Rect::new(left, top, right - left, bottom - top))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CollisionKind {
+    TopPipe,
+    BottomPipe,
+    Ground,
+}
+
+impl CollisionKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CollisionKind::TopPipe => "top pipe",
+            CollisionKind::BottomPipe => "bottom pipe",
+            CollisionKind::Ground => "ground",
+        }
+    }
+}
+
+// What the bird hit and where the two rects overlapped, in world space
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CollisionInfo {
+    pub kind: CollisionKind,
+    pub overlap: Rect,
+}
+
+impl CollisionInfo {
+    // None when the rects don't collide
+    pub fn between(kind: CollisionKind, rect1: &Rect, rect2: &Rect) -> Option<Self> {
+        let overlap = collision_overlap(rect1, rect2)?;
+        Some(CollisionInfo { kind, overlap })
+    }
+}
+
 /*
 
 The tests validate :
@@ -19,6 +64,8 @@ The tests validate :
 2. Collision detection during no overlap
 3. Physics logic when a collison happens
 4. Physics logic when a collision doesn't happen
+5. Collision info holds the overlapping part of both rects
+6. The overlap rect for partial overlap, containment and no overlap
 
 */
 
@@ -74,4 +121,36 @@ mod physics_tests {
         let other = Rect::new(100.0, 100.0, 50.0, 50.0);
         assert!(!body.collides_with(&other), "Body should not collide with other rect");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_collision_info_overlap() {
+        let rect1 = Rect::new(0.0, 0.0, 50.0, 50.0);
+        let rect2 = Rect::new(25.0, 30.0, 50.0, 50.0);
+        let info = CollisionInfo::between(CollisionKind::Ground, &rect1, &rect2).unwrap();
+        assert_eq!(info.kind, CollisionKind::Ground);
+        assert_eq!(info.overlap, Rect::new(25.0, 30.0, 25.0, 20.0));
+
+        // Touching edges don't count, same as check_collision
+        let touching = Rect::new(50.0, 0.0, 10.0, 10.0);
+        assert_eq!(CollisionInfo::between(CollisionKind::Ground, &rect1, &touching), None);
+    }
+
+    #[test]
+    fn test_collision_overlap() {
+        let rect = Rect::new(0.0, 0.0, 50.0, 50.0);
+
+        // Partial overlap, either way round
+        let other = Rect::new(40.0, -10.0, 30.0, 30.0);
+        assert_eq!(collision_overlap(&rect, &other), Some(Rect::new(40.0, 0.0, 10.0, 20.0)));
+        assert_eq!(collision_overlap(&other, &rect), Some(Rect::new(40.0, 0.0, 10.0, 20.0)));
+
+        // Containment gives the inner rect
+        let inner = Rect::new(10.0, 15.0, 5.0, 8.0);
+        assert_eq!(collision_overlap(&rect, &inner), Some(inner));
+        assert_eq!(collision_overlap(&inner, &rect), Some(inner));
+
+        // Apart or only touching
+        assert_eq!(collision_overlap(&rect, &Rect::new(100.0, 100.0, 50.0, 50.0)), None);
+        assert_eq!(collision_overlap(&rect, &Rect::new(0.0, 50.0, 50.0, 10.0)), None);
+    }
+}
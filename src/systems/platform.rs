@@ -0,0 +1,71 @@
+use macroquad::prelude::*;
+
+// Whether the player can currently see the game
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Visibility {
+    Visible,
+    Hidden,
+}
+
+impl Visibility {
+    // A minimized window has no drawable area on most native platforms
+    pub fn from_window_size(width: f32, height: f32) -> Visibility {
+        if width < 1.0 || height < 1.0 {
+            Visibility::Hidden
+        } else {
+            Visibility::Visible
+        }
+    }
+}
+
+// Nothing is updated or drawn while nobody can see the game
+pub fn should_pause(visibility: Visibility) -> bool {
+    visibility == Visibility::Hidden
+}
+
+// Browsers report hidden tabs through the page visibility API, which
+// web/visibility.js hands over as flappy_document_hidden. The wasm imports
+// it, so the web build doesn't start without the script.
+#[cfg(target_arch = "wasm32")]
+pub fn visibility() -> Visibility {
+    unsafe extern "C" {
+        fn flappy_document_hidden() -> i32;
+    }
+    if unsafe { flappy_document_hidden() } != 0 {
+        Visibility::Hidden
+    } else {
+        Visibility::Visible
+    }
+}
+
+// macroquad has no minimize event on native, so go by the window size
+#[cfg(not(target_arch = "wasm32"))]
+pub fn visibility() -> Visibility {
+    Visibility::from_window_size(screen_width(), screen_height())
+}
+
+/*
+
+The tests validate :
+1. Only a hidden game pauses
+2. A window without drawable area counts as hidden
+
+*/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_pause() {
+        assert!(should_pause(Visibility::Hidden));
+        assert!(!should_pause(Visibility::Visible));
+    }
+
+    #[test]
+    fn test_minimized_window_is_hidden() {
+        assert_eq!(Visibility::from_window_size(0.0, 0.0), Visibility::Hidden);
+        assert_eq!(Visibility::from_window_size(800.0, 0.0), Visibility::Hidden);
+        assert_eq!(Visibility::from_window_size(800.0, 600.0), Visibility::Visible);
+    }
+}
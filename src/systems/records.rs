@@ -0,0 +1,166 @@
+use std::path::PathBuf;
+use crate::systems::best_times::BestTimes;
+use crate::systems::replay::{self, Replay};
+use crate::systems::storage::{self, SessionStats, StorageError};
+use crate::{BEST_TIMES_FILE_NAME, DISTANCE_FILE_NAME, FILE_NAME, REPLAY_FILE_NAME, TIME_PLAYED_FILE_NAME};
+
+// Summary - Records :
+// Everything kept across runs: high score, best distance, best times, time
+// played and the replay of the last run. With `persist` off they are only
+// kept in memory, so test runs leave no files behind.
+pub struct Records {
+    pub highscore: i32,
+    pub best_distance: i32,
+    pub best_times: BestTimes,
+    pub session: SessionStats,
+    pub persist: bool,
+    // Folder every file goes in, None for the usual places (see storage::save_path)
+    dir: Option<PathBuf>,
+}
+
+impl Records {
+    pub fn load() -> Self {
+        Self::load_from(None)
+    }
+
+    pub fn load_from(dir: Option<PathBuf>) -> Self {
+        let mut records = Records {
+            highscore: 0,
+            best_distance: 0,
+            best_times: BestTimes::default(),
+            session: SessionStats::new(),
+            persist: true,
+            dir,
+        };
+        records.highscore = storage::read_from(&records.path(FILE_NAME)).unwrap_or(0);
+        records.best_distance = storage::read_best_distance_from(&records.path(DISTANCE_FILE_NAME)).unwrap_or(0);
+        records.best_times = BestTimes::load(&records.path(BEST_TIMES_FILE_NAME));
+        records
+    }
+
+    fn path(&self, file_name: &str) -> String {
+        match &self.dir {
+            Some(dir) => dir.join(file_name).to_string_lossy().into_owned(),
+            None => storage::save_path(file_name),
+        }
+    }
+
+    // The replay stays next to the executable unless a folder was given
+    fn replay_path(&self) -> String {
+        match &self.dir {
+            Some(dir) => dir.join(REPLAY_FILE_NAME).to_string_lossy().into_owned(),
+            None => REPLAY_FILE_NAME.to_string(),
+        }
+    }
+
+    // Keeps `score` if it ties or beats the high score. The error is for
+    // the player, the new high score is kept in memory either way.
+    pub fn record_score(&mut self, score: i32) -> Result<(), StorageError> {
+        if score < self.highscore {
+            return Ok(());
+        }
+        self.highscore = score;
+        if !self.persist {
+            log::debug!("high score {} not saved, persistence is off", score);
+            return Ok(());
+        }
+        storage::write_to(&self.path(FILE_NAME), score)
+    }
+
+    pub fn record_distance(&mut self, meters: i32) {
+        if meters <= self.best_distance {
+            return;
+        }
+        self.best_distance = meters;
+        // Losing the best distance isn't worth interrupting the game for
+        if self.persist {
+            let _ = storage::write_best_distance_to(&self.path(DISTANCE_FILE_NAME), meters);
+        }
+    }
+
+    // `splits[i]` is the run time score i + 1 was reached at
+    pub fn record_splits(&mut self, splits: &[f32]) {
+        if self.best_times.record(splits)
+            && self.persist
+            && let Err(error) = self.best_times.save(&self.path(BEST_TIMES_FILE_NAME))
+        {
+            log::warn!("Failed to save best times: {}", error);
+        }
+    }
+
+    // Safe to call more than once, only what wasn't saved yet is added
+    pub fn save_session(&mut self) {
+        if !self.persist {
+            return;
+        }
+        let path = self.path(TIME_PLAYED_FILE_NAME);
+        if let Err(error) = self.session.save_to(&path) {
+            log::warn!("Failed to save time played: {}", error);
+        }
+    }
+
+    pub fn save_replay(&self, replay: &Replay) {
+        if !self.persist {
+            return;
+        }
+        if let Err(error) = replay::save_replay(&self.replay_path(), replay) {
+            log::warn!("{}", error);
+        }
+    }
+}
+
+/*
+
+The tests validate :
+1. With persistence off new records are kept in memory and no file is written,
+   with it on every record is stored and loads back
+2. Only a tying or better score replaces the high score
+
+*/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record_everything(records: &mut Records) {
+        records.record_score(42).unwrap();
+        records.record_distance(120);
+        records.record_splits(&[1.0, 2.5]);
+        records.session.add_time(3.0);
+        records.save_session();
+        records.save_replay(&Replay::new(7));
+    }
+
+    #[test]
+    fn test_persistence_switch() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let mut records = Records::load_from(Some(dir.path().to_path_buf()));
+        records.persist = false;
+        record_everything(&mut records);
+        assert_eq!(records.highscore, 42);
+        assert_eq!(records.best_distance, 120);
+        assert_eq!(records.best_times.best(2), Some(2.5));
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+
+        let mut records = Records::load_from(Some(dir.path().to_path_buf()));
+        record_everything(&mut records);
+        let loaded = Records::load_from(Some(dir.path().to_path_buf()));
+        assert_eq!(loaded.highscore, 42);
+        assert_eq!(loaded.best_distance, 120);
+        assert_eq!(loaded.best_times.best(2), Some(2.5));
+        assert_eq!(storage::read_time_played_from(&loaded.path(TIME_PLAYED_FILE_NAME)).unwrap(), 3);
+        assert_eq!(replay::load_replay(&loaded.replay_path()).unwrap().seed, 7);
+    }
+
+    #[test]
+    fn test_only_better_scores_count() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut records = Records::load_from(Some(dir.path().to_path_buf()));
+
+        records.record_score(10).unwrap();
+        records.record_score(4).unwrap();
+        assert_eq!(records.highscore, 10);
+        assert_eq!(Records::load_from(Some(dir.path().to_path_buf())).highscore, 10);
+    }
+}
@@ -0,0 +1,177 @@
+use std::fmt;
+use std::fs;
+use std::io;
+
+// First bytes of every .flap file
+pub const MAGIC: [u8; 4] = *b"FLAP";
+// Bumped whenever the layout below changes
+pub const VERSION: u8 = 1;
+// Magic, version, seed, score and flap count
+const HEADER_LEN: usize = 4 + 1 + 8 + 4 + 4;
+
+// Why a replay file couldn't be read
+#[derive(Debug)]
+pub enum ReplayError {
+    BadMagic,
+    UnsupportedVersion(u8),
+    Truncated,
+    Io(io::Error),
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::BadMagic => write!(f, "not a replay file (bad magic header)"),
+            ReplayError::UnsupportedVersion(version) => write!(f, "unsupported replay version {}", version),
+            ReplayError::Truncated => write!(f, "replay file is truncated"),
+            ReplayError::Io(err) => write!(f, "could not access replay: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+impl From<io::Error> for ReplayError {
+    fn from(err: io::Error) -> Self {
+        ReplayError::Io(err)
+    }
+}
+
+// Summary - Replay :
+// A run as a seed plus the frames the player flapped on, with the score it
+// ended on. Stored as a .flap file: the header (magic, version, seed, score
+// and flap count, little endian) followed by one u32 frame per flap.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Replay {
+    pub seed: u64,
+    // World frames since the run started, in order
+    pub flaps: Vec<u32>,
+    pub score: i32,
+}
+
+impl Replay {
+    pub fn new(seed: u64) -> Self {
+        Replay { seed, flaps: Vec::new(), score: 0 }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_LEN + self.flaps.len() * 4);
+        bytes.extend_from_slice(&MAGIC);
+        bytes.push(VERSION);
+        bytes.extend_from_slice(&self.seed.to_le_bytes());
+        bytes.extend_from_slice(&self.score.to_le_bytes());
+        bytes.extend_from_slice(&(self.flaps.len() as u32).to_le_bytes());
+        for frame in &self.flaps {
+            bytes.extend_from_slice(&frame.to_le_bytes());
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Replay, ReplayError> {
+        if bytes.len() < MAGIC.len() || bytes[..MAGIC.len()] != MAGIC {
+            return Err(ReplayError::BadMagic);
+        }
+        let mut reader = Reader { bytes, position: MAGIC.len() };
+
+        let version = reader.take::<1>()?[0];
+        if version != VERSION {
+            return Err(ReplayError::UnsupportedVersion(version));
+        }
+        let seed = u64::from_le_bytes(reader.take()?);
+        let score = i32::from_le_bytes(reader.take()?);
+        let count = u32::from_le_bytes(reader.take()?) as usize;
+
+        // Checked up front so a corrupt count can't ask for a huge allocation
+        if bytes.len() - reader.position < count.saturating_mul(4) {
+            return Err(ReplayError::Truncated);
+        }
+        let flaps = (0..count)
+            .map(|_| reader.take().map(u32::from_le_bytes))
+            .collect::<Result<Vec<u32>, ReplayError>>()?;
+
+        Ok(Replay { seed, flaps, score })
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl Reader<'_> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], ReplayError> {
+        let end = self.position + N;
+        let chunk = self.bytes.get(self.position..end).ok_or(ReplayError::Truncated)?;
+        self.position = end;
+        Ok(chunk.try_into().expect("slice has N bytes"))
+    }
+}
+
+pub fn save_replay(path: &str, replay: &Replay) -> Result<(), ReplayError> {
+    fs::write(path, replay.to_bytes())?;
+    Ok(())
+}
+
+pub fn load_replay(path: &str) -> Result<Replay, ReplayError> {
+    Replay::from_bytes(&fs::read(path)?)
+}
+
+/*
+
+The tests validate :
+1. Replays round trip through bytes and through a file
+2. A bad magic header is rejected with a clear error
+3. Unknown versions and truncated files are rejected
+
+*/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::NamedTempFile;
+
+    fn sample() -> Replay {
+        Replay {
+            seed: 0xDEAD_BEEF_1234,
+            flaps: vec![0, 14, 30, 31, 1200],
+            score: 17,
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let replay = sample();
+        assert_eq!(Replay::from_bytes(&replay.to_bytes()).unwrap(), replay);
+
+        let empty = Replay::new(5);
+        assert_eq!(Replay::from_bytes(&empty.to_bytes()).unwrap(), empty);
+
+        let tmp = NamedTempFile::new().unwrap();
+        let path = tmp.path().to_str().unwrap();
+        save_replay(path, &replay).unwrap();
+        assert_eq!(load_replay(path).unwrap(), replay);
+    }
+
+    #[test]
+    fn test_bad_magic_is_rejected() {
+        let mut bytes = sample().to_bytes();
+        bytes[0] = b'X';
+
+        let error = Replay::from_bytes(&bytes).unwrap_err();
+        assert!(matches!(error, ReplayError::BadMagic));
+        assert_eq!(error.to_string(), "not a replay file (bad magic header)");
+
+        assert!(matches!(Replay::from_bytes(b"FL"), Err(ReplayError::BadMagic)));
+    }
+
+    #[test]
+    fn test_bad_version_and_truncation() {
+        let mut bytes = sample().to_bytes();
+        bytes[4] = 99;
+        assert!(matches!(Replay::from_bytes(&bytes), Err(ReplayError::UnsupportedVersion(99))));
+
+        let bytes = sample().to_bytes();
+        assert!(matches!(Replay::from_bytes(&bytes[..10]), Err(ReplayError::Truncated)));
+        assert!(matches!(Replay::from_bytes(&bytes[..bytes.len() - 1]), Err(ReplayError::Truncated)));
+    }
+}
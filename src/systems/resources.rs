@@ -0,0 +1,81 @@
+use std::path::PathBuf;
+use crate::{RESOURCES_DIR, RESOURCE_PACK_DIR};
+
+// Summary - ResourcePack :
+// Looks up game assets by name. Files found in the override directory
+// (a folder of replacement PNGs/WAVs) win, anything missing there falls
+// back to the built-in resources.
+pub struct ResourcePack {
+    pub base: PathBuf,
+    pub override_dir: PathBuf,
+}
+
+impl ResourcePack {
+    pub fn new(base: impl Into<PathBuf>, override_dir: impl Into<PathBuf>) -> Self {
+        ResourcePack {
+            base: base.into(),
+            override_dir: override_dir.into(),
+        }
+    }
+
+    // `name` is relative to the resources directory, e.g. "font/flappy-font.ttf"
+    pub fn resolve(&self, name: &str) -> PathBuf {
+        let replacement = self.override_dir.join(name);
+        if replacement.is_file() {
+            replacement
+        } else {
+            self.base.join(name)
+        }
+    }
+}
+
+// Path to load a resource from, preferring the resource pack
+pub fn resource_path(name: &str) -> String {
+    let pack = ResourcePack::new(RESOURCES_DIR, RESOURCE_PACK_DIR);
+    pack.resolve(name).to_string_lossy().into_owned()
+}
+
+/*
+
+The tests validate :
+1. A file present in the override directory is picked over the base
+2. Files missing from the override directory come from the base
+3. The helper falls back to the built-in resources by default
+
+*/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::Path;
+
+    #[test]
+    fn test_resolver_prefers_override() {
+        let base = tempfile::tempdir().unwrap();
+        let pack_dir = tempfile::tempdir().unwrap();
+        fs::write(base.path().join("pipes.png"), "base").unwrap();
+        fs::write(pack_dir.path().join("pipes.png"), "override").unwrap();
+
+        let pack = ResourcePack::new(base.path(), pack_dir.path());
+        assert_eq!(pack.resolve("pipes.png"), pack_dir.path().join("pipes.png"));
+    }
+
+    #[test]
+    fn test_resolver_falls_back_to_base() {
+        let base = tempfile::tempdir().unwrap();
+        let pack_dir = tempfile::tempdir().unwrap();
+        fs::create_dir(pack_dir.path().join("font")).unwrap();
+
+        let pack = ResourcePack::new(base.path(), pack_dir.path());
+        assert_eq!(pack.resolve("flap.wav"), base.path().join("flap.wav"));
+        assert_eq!(pack.resolve("font/flappy-font.ttf"), base.path().join("font/flappy-font.ttf"));
+        // A directory with the same name isn't a replacement
+        assert_eq!(pack.resolve("font"), base.path().join("font"));
+    }
+
+    #[test]
+    fn test_resource_path_defaults_to_built_in() {
+        assert!(Path::new(&resource_path("does-not-exist.png")).starts_with(RESOURCES_DIR));
+    }
+}
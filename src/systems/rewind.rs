@@ -0,0 +1,105 @@
+use std::collections::VecDeque;
+use crate::systems::simulation::GameSnapshot;
+
+// Summary - Rewind :
+// Assist that forgives one death per run. The last `capacity` frames are
+// kept in a ring buffer; on death the oldest one (about 1.5 seconds back)
+// is handed back so the round can carry on from there.
+pub struct Rewind {
+    history: VecDeque<GameSnapshot>,
+    capacity: usize,
+    used: bool,
+}
+
+impl Rewind {
+    // 1.5 seconds at 60 frames per second
+    pub const DEFAULT_CAPACITY: usize = 90;
+
+    pub fn new(capacity: usize) -> Self {
+        Rewind {
+            history: VecDeque::with_capacity(capacity),
+            capacity,
+            used: false,
+        }
+    }
+
+    pub fn record(&mut self, snapshot: GameSnapshot) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.history.len() == self.capacity {
+            self.history.pop_front();
+        }
+        self.history.push_back(snapshot);
+    }
+
+    // Oldest buffered frame, only once per run
+    pub fn take(&mut self) -> Option<GameSnapshot> {
+        if self.used {
+            return None;
+        }
+        let snapshot = self.history.pop_front()?;
+        self.used = true;
+        self.history.clear();
+        Some(snapshot)
+    }
+
+    // New run: the rewind is available again
+    pub fn reset(&mut self) {
+        self.history.clear();
+        self.used = false;
+    }
+}
+
+/*
+
+The tests validate :
+1. The buffer keeps only the most recent frames
+2. Rewinding returns the oldest frame and only works once per run
+3. Reset makes the rewind available again
+
+*/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::simulation::Simulation;
+
+    // Records one snapshot per step, so the score tells frames apart
+    fn record_frames(rewind: &mut Rewind, sim: &mut Simulation, frames: i32) {
+        for frame in 0..frames {
+            sim.score = frame;
+            rewind.record(sim.snapshot());
+        }
+    }
+
+    #[test]
+    fn test_buffer_keeps_recent_frames() {
+        let mut sim = Simulation::headless(1);
+        let mut rewind = Rewind::new(5);
+        record_frames(&mut rewind, &mut sim, 12);
+        assert_eq!(rewind.history.len(), 5);
+
+        // Frames 7 to 11 are left, the oldest is 7
+        sim.restore(&rewind.take().unwrap());
+        assert_eq!(sim.score, 7);
+    }
+
+    #[test]
+    fn test_rewind_once_per_run() {
+        let mut sim = Simulation::headless(1);
+        let mut rewind = Rewind::new(5);
+        assert!(rewind.take().is_none());
+
+        record_frames(&mut rewind, &mut sim, 3);
+        assert!(rewind.take().is_some());
+        assert_eq!(rewind.history.len(), 0);
+
+        record_frames(&mut rewind, &mut sim, 3);
+        assert!(rewind.take().is_none());
+
+        rewind.reset();
+        record_frames(&mut rewind, &mut sim, 3);
+        assert!(rewind.take().is_some());
+    }
+}
@@ -1,17 +1,37 @@
+use crate::scenes::game::GameScene;
 use crate::scenes::{title::TitleScene, Scene, Transition};
+use crate::systems::analytics::SharedAnalytics;
 
 pub struct SceneManager {
     scenes: Vec<Box<dyn Scene>>,
+    // Multiplies the frame time handed to scenes, for debugging (1 = normal)
+    pub time_scale: f32,
+    // Handed to every GameScene loaded from the title
+    analytics: SharedAnalytics,
 }
 
 impl SceneManager {
-    pub fn new() -> SceneManager {
+    pub fn new(analytics: SharedAnalytics) -> SceneManager {
         let initial_scene = Box::new(TitleScene::new());
         SceneManager {
             scenes: vec![initial_scene],
+            time_scale: 1.0,
+            analytics,
         }
     }
 
+    // Speeds the time scale key steps through
+    pub const TIME_SCALES: [f32; 4] = [1.0, 0.5, 0.25, 2.0];
+
+    // Next entry of TIME_SCALES, back to normal speed from anywhere else
+    pub fn cycle_time_scale(&mut self) {
+        let index = Self::TIME_SCALES.iter().position(|scale| *scale == self.time_scale);
+        self.time_scale = match index {
+            Some(index) => Self::TIME_SCALES[(index + 1) % Self::TIME_SCALES.len()],
+            None => 1.0,
+        };
+    }
+
     // Summary - pre_update():
     // If the current scene is a TitleScene:
     // - If it's in the loading state → load its assets asynchronously
@@ -19,43 +39,88 @@ impl SceneManager {
     //     → Load the GameScene asynchronously
     //     → Replace TitleScene with the GameScene in the stack
     pub async fn pre_update(&mut self) {
-        // Is the scenes Vector(Stack) empty ? Returns mut ref to last(top) scene
-        if let Some(scene) = self.scenes.last_mut() {
-            // Is the top scene a TitleScene ? Returns mut ref to TitleScene
-            if let Some(title_scene) = scene.as_any().downcast_mut::<TitleScene>() {
-                if title_scene.is_loading() {
-                    title_scene.load_assets().await;
-                }
-    
-                if title_scene.is_loading_game() {
-                    if let Some(game_scene) = title_scene.load_game_scene().await {
-                        // Replace: pop old title scene, push new game scene
-                        self.scenes.pop(); // Remove TitleScene
-                        self.scenes.push(game_scene); // Add GameScene
-                    }
-                }
+        // Is the top scene a TitleScene ? Returns mut ref to TitleScene
+        let analytics = self.analytics.clone();
+        if let Some(title_scene) = self.top_scene_as::<TitleScene>() {
+            if title_scene.is_loading() {
+                title_scene.load_assets().await;
+            }
+
+            if title_scene.is_loading_game()
+                && let Some(game_scene) = title_scene.load_game_scene(analytics).await
+            {
+                // Replace: pop old title scene, push new game scene
+                self.scenes.pop(); // Remove TitleScene
+                self.scenes.push(game_scene); // Add GameScene
+                log::debug!("title replaced by {}", self.current_scene_name());
             }
         }
     }
 
+    // The top scene as a `T`, None when the stack is empty or the top
+    // scene is something else
+    pub fn top_scene_as<T: 'static>(&mut self) -> Option<&mut T> {
+        self.scenes.last_mut()?.as_any().downcast_mut::<T>()
+    }
+
     // Summary - update():
     // If there's an active scene:
-    // - Call its update() method.
-    // - If the scene requests to Pop itself, remove it from the stack.
-    // - (Optional) If it wants to Push a new scene, add that scene to the stack.
+    // - Call its update() method with `dt` scaled by the time scale, and
+    //   the time scale itself.
+    // - If the scene requests to Pop itself, remove it from the stack,
+    //   logging the result of a finished GameScene.
+    // - If it wants to Push a new scene, add that scene to the stack.
 
-    pub fn update(&mut self) {
+    pub fn update(&mut self, dt: f32) {
+        let dt = dt * self.time_scale;
         if let Some(active_scene) = self.scenes.last_mut() {
-            match active_scene.update() {
+            match active_scene.update(dt, self.time_scale) {
                 Transition::None => {}
-                //Transition::Push(scene) => self.scenes.push(scene),
+                Transition::Push(scene) => {
+                    log::debug!("{} pushed {}", self.current_scene_name(), scene.name());
+                    self.scenes.push(scene);
+                }
                 Transition::Pop => {
-                    self.scenes.pop();
+                    if let Some(mut scene) = self.scenes.pop()
+                        && let Some(game_scene) = scene.as_any().downcast_mut::<GameScene>()
+                        && let Some(result) = game_scene.result()
+                    {
+                        log::info!("last run: score {} (seed {}) in {:.1}s", result.score, result.seed, result.duration);
+                    }
+                    log::debug!("popped back to {}", self.current_scene_name());
                 }
             }
         }
     }
 
+    // An empty stack still draws, which is where the game exits
+    pub fn needs_redraw(&self) -> bool {
+        self.scenes.last().is_none_or(|scene| scene.needs_redraw())
+    }
+
+    // Scenes further down the stack may still have sounds playing
+    pub fn hidden(&mut self) {
+        for scene in &mut self.scenes {
+            scene.hidden();
+        }
+    }
+
+    // Window title of the active scene.
+    // miniquad 0.4 can only set the title when the window is created, so the
+    // game loop does not apply this yet.
+    #[allow(dead_code)]
+    pub fn title(&self) -> String {
+        match self.scenes.last() {
+            Some(active_scene) => active_scene.title(),
+            None => crate::WINDOW_TITLE.to_string(),
+        }
+    }
+
+    // Name of the active scene, for logs and tests
+    pub fn current_scene_name(&self) -> &'static str {
+        self.scenes.last().map_or("<empty>", |scene| scene.name())
+    }
+
     pub fn draw(&mut self) {
         if let Some(active_scene) = self.scenes.last_mut() {
             active_scene.draw();
@@ -73,6 +138,14 @@ The tests validate :
 1. Scene stack pops on Transition::Pop
 2. Scene draw is called: Verifies draw method is invoked for the active scene.
 3. Game exits if no scenes remain.
+4. Scene stack grows on Transition::Push.
+5. The time scale multiplies the dt passed to the scene and is passed along itself, and the key cycles through the speeds.
+6. The active scene's name follows transitions, "<empty>" with no scenes left
+7. top_scene_as finds the top scene only when it has the asked for type
+8. Redraws follow the top scene, and an empty stack always draws
+9. A long frame cut down to the frame time cap still hands the scene the time scale unchanged
+10. Hiding the game reaches every scene on the stack
+11. Window title comes from the top scene, the game's own title with no scenes left
 
 A note : 
 test_game_exits_when_no_scenes_left() is commented out as it calls manager.draw()
@@ -85,12 +158,17 @@ Please uncomment this test case if needed.
 mod scenemanagement_tests {
     use super::*;
     use crate::scenes::{Transition, Scene};
-    use std::cell::RefCell;
+    use crate::systems::analytics;
+    use crate::systems::config::GameConfig;
+    use std::cell::{Cell, RefCell};
     use std::rc::Rc;
 
     struct MockScene {
         transition: RefCell<Transition>,
         draw_called: Rc<RefCell<bool>>,
+        last_dt: Rc<Cell<f32>>,
+        last_time_scale: Rc<Cell<f32>>,
+        hidden: Rc<Cell<bool>>,
     }
 
     impl MockScene {
@@ -98,12 +176,17 @@ mod scenemanagement_tests {
             MockScene {
                 transition: RefCell::new(transition),
                 draw_called,
+                last_dt: Rc::new(Cell::new(0.0)),
+                last_time_scale: Rc::new(Cell::new(0.0)),
+                hidden: Rc::new(Cell::new(false)),
             }
         }
     }
 
     impl Scene for MockScene {
-        fn update(&mut self) -> Transition {
+        fn update(&mut self, dt: f32, time_scale: f32) -> Transition {
+            self.last_dt.set(dt);
+            self.last_time_scale.set(time_scale);
             self.transition.replace(Transition::None)
         }
 
@@ -111,6 +194,10 @@ mod scenemanagement_tests {
             *self.draw_called.borrow_mut() = true;
         }
 
+        fn hidden(&mut self) {
+            self.hidden.set(true);
+        }
+
         fn as_any(&mut self) -> &mut dyn std::any::Any {
             self
         }
@@ -124,9 +211,11 @@ mod scenemanagement_tests {
                 Box::new(MockScene::new(Transition::None, draw_called.clone())),
                 Box::new(MockScene::new(Transition::Pop, draw_called.clone())),
             ],
+            time_scale: 1.0,
+            analytics: analytics::noop(),
         };
 
-        manager.update();
+        manager.update(1.0 / 60.0);
         assert_eq!(manager.scenes.len(), 1, "Scene stack should pop on Transition::Pop");
     }
 
@@ -135,16 +224,205 @@ mod scenemanagement_tests {
         let draw_called = Rc::new(RefCell::new(false));
         let mut manager = SceneManager {
             scenes: vec![Box::new(MockScene::new(Transition::None, draw_called.clone()))],
+            time_scale: 1.0,
+            analytics: analytics::noop(),
         };
 
         manager.draw();
         assert!(*draw_called.borrow(), "Draw should be called on the top scene");
     }
 
+    #[test]
+    fn test_scene_stack_push_on_transition() {
+        let draw_called = Rc::new(RefCell::new(false));
+        let pushed = Box::new(MockScene::new(Transition::None, draw_called.clone()));
+        let mut manager = SceneManager {
+            scenes: vec![Box::new(MockScene::new(Transition::Push(pushed), draw_called.clone()))],
+            time_scale: 1.0,
+            analytics: analytics::noop(),
+        };
+
+        manager.update(1.0 / 60.0);
+        assert_eq!(manager.scenes.len(), 2, "Scene stack should grow on Transition::Push");
+    }
+
+    #[test]
+    fn test_time_scale_scales_dt() {
+        let draw_called = Rc::new(RefCell::new(false));
+        let scene = MockScene::new(Transition::None, draw_called);
+        let last_dt = scene.last_dt.clone();
+        let last_time_scale = scene.last_time_scale.clone();
+        let mut manager = SceneManager {
+            scenes: vec![Box::new(scene)],
+            time_scale: 0.5,
+            analytics: analytics::noop(),
+        };
+
+        manager.update(0.016);
+        assert_eq!(last_dt.get(), 0.008);
+        assert_eq!(last_time_scale.get(), 0.5);
+
+        manager.time_scale = 1.0;
+        manager.cycle_time_scale();
+        assert_eq!(manager.time_scale, 0.5);
+        for _ in 0..SceneManager::TIME_SCALES.len() - 1 {
+            manager.cycle_time_scale();
+        }
+        assert_eq!(manager.time_scale, 1.0);
+    }
+
+    struct OtherScene;
+
+    impl Scene for OtherScene {
+        fn update(&mut self, _dt: f32, _time_scale: f32) -> Transition {
+            Transition::Pop
+        }
+
+        fn draw(&mut self) {}
+
+        fn as_any(&mut self) -> &mut dyn std::any::Any {
+            self
+        }
+    }
+
+    #[test]
+    fn test_scene_name_follows_transitions() {
+        let draw_called = Rc::new(RefCell::new(false));
+        let mut manager = SceneManager {
+            scenes: vec![Box::new(MockScene::new(Transition::Push(Box::new(OtherScene)), draw_called))],
+            time_scale: 1.0,
+            analytics: analytics::noop(),
+        };
+        assert_eq!(manager.current_scene_name(), "MockScene");
+
+        manager.update(1.0 / 60.0);
+        assert_eq!(manager.current_scene_name(), "OtherScene");
+
+        manager.update(1.0 / 60.0);
+        assert_eq!(manager.current_scene_name(), "MockScene");
+
+        let empty = SceneManager { scenes: vec![], time_scale: 1.0, analytics: analytics::noop() };
+        assert_eq!(empty.current_scene_name(), "<empty>");
+    }
+
+    #[test]
+    fn test_top_scene_as() {
+        let draw_called = Rc::new(RefCell::new(false));
+        let mut manager = SceneManager {
+            scenes: vec![Box::new(MockScene::new(Transition::None, draw_called))],
+            time_scale: 1.0,
+            analytics: analytics::noop(),
+        };
+        assert!(manager.top_scene_as::<MockScene>().is_some());
+        assert!(manager.top_scene_as::<TitleScene>().is_none());
+
+        // Only the top of the stack counts
+        manager.scenes.push(Box::new(TitleScene::new()));
+        assert!(manager.top_scene_as::<TitleScene>().is_some());
+        assert!(manager.top_scene_as::<MockScene>().is_none());
+
+        let mut empty = SceneManager { scenes: vec![], time_scale: 1.0, analytics: analytics::noop() };
+        assert!(empty.top_scene_as::<MockScene>().is_none());
+    }
+
+    struct StaticScene;
+
+    impl Scene for StaticScene {
+        fn update(&mut self, _dt: f32, _time_scale: f32) -> Transition {
+            Transition::Pop
+        }
+
+        fn draw(&mut self) {}
+
+        fn as_any(&mut self) -> &mut dyn std::any::Any {
+            self
+        }
+
+        fn needs_redraw(&self) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn test_needs_redraw_follows_top_scene() {
+        let draw_called = Rc::new(RefCell::new(false));
+        let mut manager = SceneManager {
+            scenes: vec![Box::new(MockScene::new(Transition::None, draw_called))],
+            time_scale: 1.0,
+            analytics: analytics::noop(),
+        };
+        assert!(manager.needs_redraw());
+
+        manager.scenes.push(Box::new(StaticScene));
+        assert!(!manager.needs_redraw());
+
+        // Popping the static scene brings back one that redraws
+        manager.update(1.0 / 60.0);
+        assert!(manager.needs_redraw());
+
+        let empty = SceneManager { scenes: vec![], time_scale: 1.0, analytics: analytics::noop() };
+        assert!(empty.needs_redraw());
+    }
+
+    #[test]
+    fn test_clamped_frame_keeps_time_scale() {
+        let draw_called = Rc::new(RefCell::new(false));
+        let scene = MockScene::new(Transition::None, draw_called);
+        let last_dt = scene.last_dt.clone();
+        let last_time_scale = scene.last_time_scale.clone();
+        let mut manager = SceneManager {
+            scenes: vec![Box::new(scene)],
+            time_scale: 1.0,
+            analytics: analytics::noop(),
+        };
+
+        // A two second stall arrives already cut down to the cap, which
+        // must not read as the game being slowed down
+        let max = GameConfig::new().max_frame_time;
+        manager.update(max);
+        assert_eq!(last_dt.get(), max);
+        assert_eq!(last_time_scale.get(), 1.0);
+
+        manager.time_scale = 0.5;
+        manager.update(max);
+        assert_eq!(last_time_scale.get(), 0.5);
+    }
+
+    #[test]
+    fn test_hidden_reaches_every_scene() {
+        let bottom = MockScene::new(Transition::None, Rc::new(RefCell::new(false)));
+        let top = MockScene::new(Transition::None, Rc::new(RefCell::new(false)));
+        let bottom_hidden = bottom.hidden.clone();
+        let top_hidden = top.hidden.clone();
+        let mut manager = SceneManager {
+            scenes: vec![Box::new(bottom), Box::new(top)],
+            time_scale: 1.0,
+            analytics: analytics::noop(),
+        };
+
+        manager.hidden();
+        assert!(bottom_hidden.get());
+        assert!(top_hidden.get());
+    }
+
+    #[test]
+    fn test_title_uses_top_scene() {
+        let draw_called = Rc::new(RefCell::new(false));
+        let manager = SceneManager {
+            scenes: vec![Box::new(MockScene::new(Transition::None, draw_called))],
+            time_scale: 1.0,
+            analytics: analytics::noop(),
+        };
+        assert_eq!(manager.title(), crate::WINDOW_TITLE);
+
+        let empty = SceneManager { scenes: vec![], time_scale: 1.0, analytics: analytics::noop() };
+        assert_eq!(empty.title(), crate::WINDOW_TITLE);
+    }
+
     // #[test]
     // #[should_panic(expected = "exit")]
     // fn test_game_exits_when_no_scenes_left() {
-    //     let mut manager = SceneManager { scenes: vec![] };
+    //     let mut manager = SceneManager { scenes: vec![], time_scale: 1.0, analytics: analytics::noop() };
     //     manager.draw(); // Should trigger process::exit
     // }
 }
@@ -0,0 +1,413 @@
+use std::fs;
+use macroquad::prelude::Color;
+use crate::SETTINGS_FILE_NAME;
+use crate::systems::audio::DeathSound;
+use crate::systems::config::{ControlScheme, GameMode, Quality};
+use crate::systems::difficulty::FlapAssist;
+use crate::systems::letterbox::parse_aspect_ratio;
+use crate::systems::theme::{parse_hex_color, to_hex_color, Theme};
+
+// Summary - Settings :
+// Player preferences saved between sessions as `key=value` lines.
+// Unknown keys and unreadable values are ignored so old or hand-edited
+// files still load.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Settings {
+    // Master volume in percent (0-100)
+    pub volume: i32,
+
+    // Mode last picked on the title screen
+    pub mode: GameMode,
+
+    pub quality: Quality,
+
+    // Fixed width / height ratio with black bars around the game, None fills the window
+    pub aspect_ratio: Option<f32>,
+
+    // Flaps dive and gravity pulls up
+    pub inverted_controls: bool,
+
+    // Assist: the first death of each run rewinds 1.5 seconds instead
+    pub rewind_assist: bool,
+
+    // Accessibility: flaps automatically to hold the bird near the next gap
+    pub flap_assist: FlapAssist,
+
+    // Short gamepad rumble on death
+    pub rumble: bool,
+
+    // Bigger HUD and scoreboard text on dark panels
+    pub high_contrast: bool,
+
+    // On-screen flap button for touch screens. On by default for the web
+    // build, elsewhere only switchable by editing the file
+    pub flap_button: bool,
+
+    // Flat background_color instead of the sky and parallax layers
+    pub minimal_background: bool,
+
+    // Saved as "#rrggbb"
+    pub background_color: Color,
+
+    // Faint outlines of the next gaps at the right edge before they scroll in
+    pub pipe_previews: bool,
+
+    // Only switchable by editing the file
+    pub death_sound: DeathSound,
+
+    // Set once the first-run tutorial has been completed
+    pub tutorial_seen: bool,
+
+    // Experimental follow camera, only switchable by editing the file
+    pub scrolling_camera: bool,
+
+    // No gravity until the first flap, only switchable by editing the file
+    pub gravity_on_first_flap: bool,
+
+    // Follow-mouse sandbox and whether pipes still kill in it, only
+    // switchable by editing the file
+    pub control_scheme: ControlScheme,
+    pub follow_mouse_collisions: bool,
+
+    // Continue a death from the last checkpoint, only switchable by editing the file
+    pub checkpoints: bool,
+
+    // Red tint as the pipes speed up, only switchable by editing the file
+    pub rage_tint: bool,
+
+    // Accessibility option: still background layers and no spinning bird,
+    // only switchable by editing the file
+    pub reduced_motion: bool,
+
+    // Pipes to pass to win a run, 0 for endless runs. Only switchable by editing the file
+    pub pipe_limit: u32,
+
+    // Score shown by the debug scoreboard key in debug builds, only switchable by editing the file
+    pub debug_score: i32,
+
+    // Cartoon squash and stretch of the bird on flaps, only switchable by editing the file
+    pub squash_stretch: bool,
+}
+
+impl Settings {
+    pub const VOLUME_STEP: i32 = 5;
+
+    pub fn new() -> Self {
+        Settings {
+            volume: 100,
+            mode: GameMode::Normal,
+            quality: Quality::High,
+            aspect_ratio: None,
+            inverted_controls: false,
+            rewind_assist: false,
+            flap_assist: FlapAssist::Off,
+            rumble: true,
+            high_contrast: false,
+            flap_button: cfg!(target_arch = "wasm32"),
+            minimal_background: false,
+            background_color: Theme::new().sky_top,
+            pipe_previews: false,
+            death_sound: DeathSound::Classic,
+            tutorial_seen: false,
+            scrolling_camera: false,
+            gravity_on_first_flap: false,
+            control_scheme: ControlScheme::Flap,
+            follow_mouse_collisions: true,
+            checkpoints: false,
+            rage_tint: false,
+            reduced_motion: false,
+            pipe_limit: 0,
+            debug_score: 42,
+            squash_stretch: false,
+        }
+    }
+
+    pub fn load() -> Self {
+        Self::load_from(SETTINGS_FILE_NAME)
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        self.save_to(SETTINGS_FILE_NAME)
+    }
+
+    pub fn load_from(path: &str) -> Self {
+        let mut settings = Settings::new();
+        if let Ok(content) = fs::read_to_string(path) {
+            for line in content.lines() {
+                if let Some((key, value)) = line.split_once('=') {
+                    settings.apply(key.trim(), value.trim());
+                }
+            }
+        }
+        settings
+    }
+
+    pub fn save_to(&self, path: &str) -> std::io::Result<()> {
+        fs::write(
+            path,
+            format!(
+                "volume={}\nmode={}\nquality={}\naspect_ratio={}\ninverted_controls={}\nrewind_assist={}\nflap_assist={}\nrumble={}\nhigh_contrast={}\nflap_button={}\nminimal_background={}\nbackground_color={}\npipe_previews={}\ndeath_sound={}\ntutorial_seen={}\nscrolling_camera={}\ngravity_on_first_flap={}\ncontrol_scheme={}\nfollow_mouse_collisions={}\ncheckpoints={}\nrage_tint={}\nreduced_motion={}\npipe_limit={}\ndebug_score={}\nsquash_stretch={}\n",
+                self.volume,
+                self.mode.name(),
+                self.quality.name(),
+                self.aspect_ratio.map_or("off".to_string(), |ratio| ratio.to_string()),
+                self.inverted_controls,
+                self.rewind_assist,
+                self.flap_assist.name(),
+                self.rumble,
+                self.high_contrast,
+                self.flap_button,
+                self.minimal_background,
+                to_hex_color(self.background_color),
+                self.pipe_previews,
+                self.death_sound.name(),
+                self.tutorial_seen,
+                self.scrolling_camera,
+                self.gravity_on_first_flap,
+                self.control_scheme.name(),
+                self.follow_mouse_collisions,
+                self.checkpoints,
+                self.rage_tint,
+                self.reduced_motion,
+                self.pipe_limit,
+                self.debug_score,
+                self.squash_stretch
+            ),
+        )
+    }
+
+    fn apply(&mut self, key: &str, value: &str) {
+        match key {
+            "volume" => {
+                if let Ok(volume) = value.parse::<i32>() {
+                    self.volume = volume.clamp(0, 100);
+                }
+            }
+            "mode" => {
+                if let Some(mode) = GameMode::from_name(value) {
+                    self.mode = mode;
+                }
+            }
+            "quality" => {
+                if let Some(quality) = Quality::from_name(value) {
+                    self.quality = quality;
+                }
+            }
+            "aspect_ratio" => {
+                if value == "off" {
+                    self.aspect_ratio = None;
+                } else if let Some(ratio) = parse_aspect_ratio(value) {
+                    self.aspect_ratio = Some(ratio);
+                }
+            }
+            "inverted_controls" => {
+                if let Ok(enabled) = value.parse::<bool>() {
+                    self.inverted_controls = enabled;
+                }
+            }
+            "rewind_assist" => {
+                if let Ok(enabled) = value.parse::<bool>() {
+                    self.rewind_assist = enabled;
+                }
+            }
+            "flap_assist" => {
+                if let Some(assist) = FlapAssist::from_name(value) {
+                    self.flap_assist = assist;
+                }
+            }
+            "rumble" => {
+                if let Ok(enabled) = value.parse::<bool>() {
+                    self.rumble = enabled;
+                }
+            }
+            "high_contrast" => {
+                if let Ok(enabled) = value.parse::<bool>() {
+                    self.high_contrast = enabled;
+                }
+            }
+            "flap_button" => {
+                if let Ok(enabled) = value.parse::<bool>() {
+                    self.flap_button = enabled;
+                }
+            }
+            "minimal_background" => {
+                if let Ok(enabled) = value.parse::<bool>() {
+                    self.minimal_background = enabled;
+                }
+            }
+            "background_color" => {
+                if let Some(color) = parse_hex_color(value) {
+                    self.background_color = color;
+                }
+            }
+            "pipe_previews" => {
+                if let Ok(enabled) = value.parse::<bool>() {
+                    self.pipe_previews = enabled;
+                }
+            }
+            "death_sound" => {
+                if let Some(sound) = DeathSound::from_name(value) {
+                    self.death_sound = sound;
+                }
+            }
+            "tutorial_seen" => {
+                if let Ok(seen) = value.parse::<bool>() {
+                    self.tutorial_seen = seen;
+                }
+            }
+            "scrolling_camera" => {
+                if let Ok(enabled) = value.parse::<bool>() {
+                    self.scrolling_camera = enabled;
+                }
+            }
+            "gravity_on_first_flap" => {
+                if let Ok(enabled) = value.parse::<bool>() {
+                    self.gravity_on_first_flap = enabled;
+                }
+            }
+            "control_scheme" => {
+                if let Some(scheme) = ControlScheme::from_name(value) {
+                    self.control_scheme = scheme;
+                }
+            }
+            "follow_mouse_collisions" => {
+                if let Ok(enabled) = value.parse::<bool>() {
+                    self.follow_mouse_collisions = enabled;
+                }
+            }
+            "checkpoints" => {
+                if let Ok(enabled) = value.parse::<bool>() {
+                    self.checkpoints = enabled;
+                }
+            }
+            "rage_tint" => {
+                if let Ok(enabled) = value.parse::<bool>() {
+                    self.rage_tint = enabled;
+                }
+            }
+            "reduced_motion" => {
+                if let Ok(enabled) = value.parse::<bool>() {
+                    self.reduced_motion = enabled;
+                }
+            }
+            "pipe_limit" => {
+                if let Ok(limit) = value.parse::<u32>() {
+                    self.pipe_limit = limit;
+                }
+            }
+            "debug_score" => {
+                if let Ok(score) = value.parse::<i32>() {
+                    self.debug_score = score.max(0);
+                }
+            }
+            "squash_stretch" => {
+                if let Ok(enabled) = value.parse::<bool>() {
+                    self.squash_stretch = enabled;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Changes the volume by `steps` increments of VOLUME_STEP.
+    // Returns true if the volume actually changed.
+    pub fn adjust_volume(&mut self, steps: i32) -> bool {
+        let volume = (self.volume + steps * Self::VOLUME_STEP).clamp(0, 100);
+        let changed = volume != self.volume;
+        self.volume = volume;
+        changed
+    }
+
+    pub fn volume_fraction(&self) -> f32 {
+        self.volume as f32 / 100.0
+    }
+}
+
+/*
+
+The tests validate :
+1. Volume adjusts in 5% steps
+2. Volume is clamped at 0% and 100%
+3. Settings round trip through a file
+4. Missing or invalid files fall back to defaults
+
+*/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_volume_adjusts_in_steps() {
+        let mut settings = Settings { volume: 50, ..Settings::new() };
+        assert!(settings.adjust_volume(1));
+        assert_eq!(settings.volume, 55);
+        assert!(settings.adjust_volume(-2));
+        assert_eq!(settings.volume, 45);
+    }
+
+    #[test]
+    fn test_volume_clamps_at_bounds() {
+        let mut settings = Settings { volume: 0, ..Settings::new() };
+        assert!(!settings.adjust_volume(-1));
+        assert_eq!(settings.volume, 0);
+
+        let mut settings = Settings { volume: 100, ..Settings::new() };
+        assert!(!settings.adjust_volume(1));
+        assert_eq!(settings.volume, 100);
+
+        let mut settings = Settings { volume: 97, ..Settings::new() };
+        assert!(settings.adjust_volume(1));
+        assert_eq!(settings.volume, 100);
+    }
+
+    #[test]
+    fn test_settings_round_trip() {
+        let tmp = NamedTempFile::new().unwrap();
+        let path = tmp.path().to_str().unwrap();
+
+        let settings = Settings {
+            volume: 35,
+            mode: GameMode::Zen,
+            quality: Quality::Low,
+            aspect_ratio: Some(0.5625),
+            inverted_controls: true,
+            rewind_assist: true,
+            flap_assist: FlapAssist::Strong,
+            rumble: false,
+            high_contrast: true,
+            flap_button: true,
+            minimal_background: true,
+            background_color: Color::from_rgba(0x20, 0x30, 0x40, 255),
+            pipe_previews: true,
+            death_sound: DeathSound::Random,
+            tutorial_seen: true,
+            scrolling_camera: true,
+            gravity_on_first_flap: true,
+            control_scheme: ControlScheme::FollowMouse,
+            follow_mouse_collisions: false,
+            checkpoints: true,
+            rage_tint: true,
+            reduced_motion: true,
+            pipe_limit: 50,
+            debug_score: 7,
+            squash_stretch: true,
+        };
+        settings.save_to(path).unwrap();
+
+        assert_eq!(Settings::load_from(path), settings);
+    }
+
+    #[test]
+    fn test_invalid_settings_fall_back_to_defaults() {
+        let tmp = NamedTempFile::new().unwrap();
+        let path = tmp.path().to_str().unwrap();
+        fs::write(path, "volume=loud\nmode=sideways\nbackground_color=#12345\nunknown=1\n").unwrap();
+
+        assert_eq!(Settings::load_from(path), Settings::new());
+
+        let missing = tmp.path().with_extension("missing");
+        assert_eq!(Settings::load_from(missing.to_str().unwrap()), Settings::new());
+    }
+}
@@ -0,0 +1,1349 @@
+use std::ops::RangeInclusive;
+use macroquad::prelude::*;
+use ::rand::rngs::StdRng;
+use ::rand::{Rng, SeedableRng};
+
+use crate::prefabs::bird::{Bird, BirdState};
+use crate::prefabs::ground::Ground;
+use crate::prefabs::pipes::{Pipe, PipeGenerator, PipeGroup};
+use crate::prefabs::powerup::{PowerUp, PowerUpKind};
+use crate::systems::camera::FollowCamera;
+use crate::systems::config::{GameConfig, GameMode, Speeds};
+use crate::systems::difficulty::AntiFrustration;
+use crate::systems::physics::{CollisionInfo, CollisionKind, PhysicsBody};
+
+// Length of one world step, and the frame time when stepping without a window (60 FPS)
+pub const FIXED_FRAME_TIME: f32 = 1.0 / 60.0;
+
+// Default macroquad window size, used for headless runs
+pub const DEFAULT_SCREEN: Vec2 = Vec2::new(800.0, 600.0);
+
+// Height of resources/ground.png, used for headless runs
+pub const DEFAULT_GROUND_HEIGHT: f32 = 112.0;
+
+// Passing within this many pixels of the gap centre earns the needle bonus
+pub const NEEDLE_BAND: f32 = 12.0;
+pub const NEEDLE_BONUS: i32 = 1;
+
+// Scroll distance that counts as one meter on the distance meter
+pub const PIXELS_PER_METER: f32 = 30.0;
+
+/// Phases a round goes through. Transitions:
+/// Instructions -> (Countdown ->) Playing -> GameOver -> Instructions (reset)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GamePhase {
+    Instructions,
+    Countdown,
+    Playing,
+    GameOver,
+}
+
+/// Something that happened during a simulation step that the scene may react to
+/// (sounds, scroll flags, high score).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SimEvent {
+    // The instructions were dismissed, see Simulation::start
+    Started,
+    Flapped,
+    Scored,
+    Bonus(i32),
+    PipeHit,
+    GroundHit,
+    GravityFlipped,
+    // Carries the new size scale
+    Resized(f32),
+    // The bird flew close past a pipe, once per group
+    Whoosh,
+    // Picked up a smash charge
+    SmashCharged,
+    // Broke through a pipe group using up a charge, carries where it was hit
+    Smashed(Vec2),
+    // Reached a multiple of CHECKPOINT_INTERVAL with checkpoints on
+    Checkpoint,
+    // Passed config.pipe_limit pipe groups, the run is over and won
+    Won,
+}
+
+// Copy of the round's state at one frame, see Simulation::snapshot
+#[derive(Clone)]
+pub struct GameSnapshot {
+    bird: BirdState,
+    pipes: Vec<PipeGroup>,
+    powerups: Vec<PowerUp>,
+    pipe_generator: PipeGenerator,
+    camera_x: f32,
+    score: i32,
+    pipes_passed: u32,
+    smash_charges: u32,
+    distance: f32,
+    last_collision: Option<CollisionInfo>,
+    phase: GamePhase,
+    gravity_flip_timer: f32,
+    ground_rise: f32,
+    rng: StdRng,
+}
+
+// Summary - Simulation :
+// Owns the gameplay state (bird, pipes, score) and rules, without any
+// textures, sounds or window access. GameScene drives it every frame and
+// draws the result; the benchmark and tests drive it headless via step().
+pub struct Simulation {
+    pub config: GameConfig,
+    pub bird: Bird,
+    pub pipes: Vec<PipeGroup>,
+    pub powerups: Vec<PowerUp>,
+    pub pipe_generator: PipeGenerator,
+    pub anti_frustration: AntiFrustration,
+    // Only moves when config.scrolling_camera is on, otherwise stays at x = 0
+    pub camera: FollowCamera,
+
+    pub score: i32,
+    // Pipe groups passed this round, unlike the score without bonuses
+    pub pipes_passed: u32,
+    // Pipe hits the bird can survive, one used up per pipe group smashed
+    pub smash_charges: u32,
+    // Pixels flown this round, see meters()
+    pub distance: f32,
+    // What killed the bird most recently, for the debug overlay
+    pub last_collision: Option<CollisionInfo>,
+    // Latest checkpoint of the run, and whether a death was already continued from one
+    checkpoint: Option<GameSnapshot>,
+    checkpoint_used: bool,
+    // The round ended by reaching config.pipe_limit rather than a crash
+    won: bool,
+    phase: GamePhase,
+    countdown_timer: f32,
+    gravity_flip_timer: f32,
+
+    pub screen: Vec2,
+    pub ground_y: f32,
+    // How far the ground has risen above its usual place, see rise_ground.
+    // ground_y already has it taken off.
+    pub ground_rise: f32,
+
+    seed: u64,
+    rng: StdRng,
+}
+
+impl Simulation {
+    // Points between two checkpoints, see GameConfig::checkpoints
+    pub const CHECKPOINT_INTERVAL: i32 = 10;
+
+    pub fn new(seed: u64, mut bird: Bird, screen: Vec2, ground_y: f32) -> Self {
+        let config = GameConfig::new();
+        bird.set_frame_duration(config.bird_frame_duration);
+
+        let camera = FollowCamera::new(bird.fixed_x_position);
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut pipe_generator = PipeGenerator::new();
+        pipe_generator.fill_gaps(&mut rng);
+
+        Simulation {
+            config,
+            camera,
+            bird,
+            pipes: Vec::new(),
+            powerups: Vec::new(),
+            pipe_generator,
+            anti_frustration: AntiFrustration::new(),
+            score: 0,
+            pipes_passed: 0,
+            smash_charges: 0,
+            checkpoint: None,
+            checkpoint_used: false,
+            won: false,
+            distance: 0.0,
+            last_collision: None,
+            phase: GamePhase::Instructions,
+            countdown_timer: 0.0,
+            gravity_flip_timer: 0.0,
+            screen,
+            ground_y,
+            ground_rise: 0.0,
+            seed,
+            rng,
+        }
+    }
+
+    pub fn headless(seed: u64) -> Self {
+        let bird = Bird::headless(vec2(DEFAULT_SCREEN.x / 2.5, DEFAULT_SCREEN.y / 2.0));
+        Simulation::new(seed, bird, DEFAULT_SCREEN, DEFAULT_SCREEN.y - DEFAULT_GROUND_HEIGHT)
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn phase(&self) -> GamePhase {
+        self.phase
+    }
+
+    pub fn is_game_over(&self) -> bool {
+        self.phase == GamePhase::GameOver
+    }
+
+    // Instructions -> Countdown, or straight to Playing without a countdown
+    pub fn start(&mut self) {
+        if self.phase != GamePhase::Instructions {
+            return;
+        }
+
+        if self.config.countdown_duration > 0.0 {
+            self.phase = GamePhase::Countdown;
+            self.countdown_timer = self.config.countdown_duration;
+        } else {
+            self.begin_playing();
+        }
+    }
+
+    fn begin_playing(&mut self) {
+        self.phase = GamePhase::Playing;
+        self.bird.allow_gravity = !self.config.gravity_on_first_flap;
+        // Blinks like after a rewind, so it's clear why a pipe didn't count
+        self.bird.invulnerable_timer = self.config.start_grace;
+        self.pipe_generator.start();
+    }
+
+    // Everything needed to carry on the round from this frame later on.
+    // Config, screen and seed stay with the simulation.
+    pub fn snapshot(&self) -> GameSnapshot {
+        GameSnapshot {
+            bird: self.bird.state(),
+            pipes: self.pipes.clone(),
+            powerups: self.powerups.clone(),
+            pipe_generator: self.pipe_generator.clone(),
+            camera_x: self.camera.x,
+            score: self.score,
+            pipes_passed: self.pipes_passed,
+            smash_charges: self.smash_charges,
+            distance: self.distance,
+            last_collision: self.last_collision,
+            phase: self.phase,
+            gravity_flip_timer: self.gravity_flip_timer,
+            ground_rise: self.ground_rise,
+            rng: self.rng.clone(),
+        }
+    }
+
+    // Whether a finished run can still be continued from its last checkpoint
+    pub fn can_resume(&self) -> bool {
+        self.is_game_over() && !self.won && self.checkpoint.is_some() && !self.checkpoint_used
+    }
+
+    pub fn checkpoint_score(&self) -> Option<i32> {
+        self.checkpoint.as_ref().map(|checkpoint| checkpoint.score)
+    }
+
+    // Puts a finished run back to its last checkpoint, once per run
+    pub fn resume_from_checkpoint(&mut self) -> bool {
+        if !self.can_resume() {
+            return false;
+        }
+        if let Some(checkpoint) = self.checkpoint.take() {
+            self.restore(&checkpoint);
+            self.checkpoint_used = true;
+            log::info!("resumed from the checkpoint at score {}", self.score);
+        }
+        true
+    }
+
+    pub fn restore(&mut self, snapshot: &GameSnapshot) {
+        self.bird.restore(&snapshot.bird);
+        self.pipes = snapshot.pipes.clone();
+        self.powerups = snapshot.powerups.clone();
+        self.pipe_generator = snapshot.pipe_generator.clone();
+        self.camera.x = snapshot.camera_x;
+        self.score = snapshot.score;
+        self.pipes_passed = snapshot.pipes_passed;
+        self.smash_charges = snapshot.smash_charges;
+        self.distance = snapshot.distance;
+        self.last_collision = snapshot.last_collision;
+        self.phase = snapshot.phase;
+        self.gravity_flip_timer = snapshot.gravity_flip_timer;
+        self.ground_rise = snapshot.ground_rise;
+        self.rng = snapshot.rng.clone();
+    }
+
+    // Raises the ground by `amount` pixels, up to Ground::MAX_RISE
+    pub fn rise_ground(&mut self, amount: f32) {
+        self.ground_rise = (self.ground_rise + amount).clamp(0.0, Ground::MAX_RISE);
+    }
+
+    pub fn countdown_remaining(&self) -> f32 {
+        self.countdown_timer
+    }
+
+    pub fn meters(&self) -> i32 {
+        (self.distance / PIXELS_PER_METER) as i32
+    }
+
+    // Seconds left of the current gravity flip, 0 when gravity is normal
+    pub fn gravity_flip_remaining(&self) -> f32 {
+        self.gravity_flip_timer
+    }
+
+    fn flip_gravity(&mut self) {
+        self.bird.gravity_sign = -1.0;
+        self.gravity_flip_timer = self.config.gravity_flip_duration;
+    }
+
+    fn restore_gravity(&mut self) {
+        self.bird.gravity_sign = 1.0;
+        self.gravity_flip_timer = 0.0;
+    }
+
+    // Any phase -> Instructions
+    pub fn reset(&mut self, seed: u64) {
+        self.phase = GamePhase::Instructions;
+        self.countdown_timer = 0.0;
+        self.pipes.clear();
+        self.powerups.clear();
+        self.gravity_flip_timer = 0.0;
+        self.pipe_generator = PipeGenerator::new();
+        self.bird.reset(self.screen.y);
+        self.camera.snap_to(self.bird.position.x);
+        self.score = 0;
+        self.pipes_passed = 0;
+        self.smash_charges = 0;
+        self.distance = 0.0;
+        self.ground_rise = 0.0;
+        self.last_collision = None;
+        self.checkpoint = None;
+        self.checkpoint_used = false;
+        self.won = false;
+
+        self.seed = seed;
+        self.rng = StdRng::seed_from_u64(seed);
+        self.pipe_generator.fill_gaps(&mut self.rng);
+        log::info!("round reset with seed {}", seed);
+    }
+
+    pub fn update_bird(&mut self, dt: f32) {
+        if self.phase == GamePhase::Countdown {
+            self.countdown_timer -= dt;
+            if self.countdown_timer <= 0.0 {
+                self.countdown_timer = 0.0;
+                self.begin_playing();
+            }
+        }
+
+        if self.gravity_flip_timer > 0.0 {
+            self.gravity_flip_timer -= dt;
+            if self.gravity_flip_timer <= 0.0 {
+                self.restore_gravity();
+            }
+        }
+
+        if self.phase != GamePhase::Instructions {
+            let bird_x = self.bird.position.x;
+            self.bird.gravity = self.config.gravity;
+            self.bird.step(dt, self.screen.y);
+            self.bird.position.x = bird_x;
+
+            if self.config.scrolling_camera {
+                // The bird covers the distance the world would have scrolled
+                if self.phase == GamePhase::Playing && self.bird.alive {
+                    self.bird.position.x += Speeds::distance(self.config.scroll_speed, dt);
+                }
+                self.camera.follow(self.bird.position.x, dt);
+            }
+        }
+    }
+
+    // Returns true if the bird actually flapped
+    pub fn flap(&mut self) -> bool {
+        if self.bird.alive && self.phase != GamePhase::GameOver {
+            // Ends the gravity_on_first_flap hover
+            if self.phase == GamePhase::Playing {
+                self.bird.allow_gravity = true;
+            }
+            self.bird.flap();
+            log::debug!("flap at y = {:.1}", self.bird.position.y);
+            return true;
+        }
+        false
+    }
+
+    // Summary - update_world() :
+    // 1. Score pipes the bird has passed (plus the needle bonus) and scroll them.
+    // 2. Check pipe and ground collisions.
+    // 3. Spawn (or recycle) a pipe group when the generator fires, keeping
+    //    groups `config.pipe_spacing` pixels apart.
+    pub fn update_world(&mut self) -> Vec<SimEvent> {
+        let mut events = Vec::new();
+        if self.phase != GamePhase::Playing {
+            return events;
+        }
+        let score_before = self.score;
+
+        // Each step moves the world one fixed frame's worth of scroll_speed
+        let step_distance = Speeds::distance(self.config.scroll_speed, FIXED_FRAME_TIME);
+        if self.bird.alive {
+            self.distance += step_distance;
+        }
+
+        let scoring = self.config.mode != GameMode::Zen;
+        let bird_rect = self.bird.get_collision_rect();
+        for pipe_group in &mut self.pipes {
+            if self.bird.alive && pipe_group.check_whoosh(&bird_rect) {
+                events.push(SimEvent::Whoosh);
+            }
+            if scoring && !pipe_group.has_scored && pipe_group.position.x + Pipe::HALF_WIDTH <= self.bird.position.x {
+                pipe_group.has_scored = true;
+                self.score += 1;
+                self.pipes_passed += 1;
+                events.push(SimEvent::Scored);
+                log::info!("scored, score is now {}", self.score);
+
+                let bird_center_y = self.bird.get_collision_rect().center().y;
+                let bonus = needle_bonus(bird_center_y, pipe_group.gap_center());
+                if bonus > 0 {
+                    self.score += bonus;
+                    events.push(SimEvent::Bonus(bonus));
+                    log::debug!("needle bonus +{}", bonus);
+                }
+            }
+            if self.config.scrolling_camera {
+                pipe_group.retire_if_behind(self.camera.x);
+            } else {
+                pipe_group.update(FIXED_FRAME_TIME);
+            }
+        }
+
+        let mut collected = Vec::new();
+        for powerup in &mut self.powerups {
+            if !self.bird.alive {
+                break;
+            }
+            if self.config.scrolling_camera {
+                powerup.retire_if_behind(self.camera.x);
+            } else {
+                powerup.update(FIXED_FRAME_TIME);
+            }
+            if powerup.alive && powerup.collides_with(&bird_rect) {
+                powerup.alive = false;
+                collected.push(powerup.kind);
+            }
+        }
+        self.powerups.retain(|powerup| powerup.alive);
+        for kind in collected {
+            match (kind, kind.size_scale()) {
+                (PowerUpKind::Smash, _) => {
+                    self.smash_charges += 1;
+                    events.push(SimEvent::SmashCharged);
+                    log::debug!("smash charges now {}", self.smash_charges);
+                }
+                (_, Some(scale)) => {
+                    self.bird.resize(scale, self.config.size_powerup_duration);
+                    events.push(SimEvent::Resized(scale));
+                    log::debug!("bird resized to {}", scale);
+                }
+                (_, None) => {
+                    self.flip_gravity();
+                    events.push(SimEvent::GravityFlipped);
+                    log::debug!("gravity flipped");
+                }
+            }
+        }
+
+        if self.config.mode == GameMode::Zen || !self.config.collisions {
+            // Nothing is lethal, the bird just rests on the ground
+            let max_y = self.ground_y - self.bird.get_collision_rect().h;
+            self.bird.position.y = self.bird.position.y.min(max_y);
+        } else {
+            self.check_for_collisions(&mut events);
+        }
+
+        // Derived every frame so spacing stays constant if the speed changes
+        self.pipe_generator.set_spacing(self.config.pipe_spacing, step_distance);
+        if self.pipe_generator.should_spawn_pipe() {
+            self.spawn_pipe();
+        }
+
+        // Taken once the frame is over, so a death on the same frame isn't kept
+        let interval = Self::CHECKPOINT_INTERVAL;
+        if self.config.checkpoints && self.bird.alive && self.score / interval > score_before.max(0) / interval {
+            self.checkpoint = Some(self.snapshot());
+            events.push(SimEvent::Checkpoint);
+            log::info!("checkpoint at score {}", self.score);
+        }
+
+        if self.bird.alive && self.config.pipe_limit > 0 && self.pipes_passed >= self.config.pipe_limit {
+            self.win();
+            events.push(SimEvent::Won);
+        }
+
+        events
+    }
+
+    // Ends the round as a win: the bird hovers where it is and the world stops
+    fn win(&mut self) {
+        log::info!("won after {} pipes with score {}", self.pipes_passed, self.score);
+        self.won = true;
+        self.phase = GamePhase::GameOver;
+        self.bird.allow_gravity = false;
+        self.restore_gravity();
+        self.pipe_generator.stop();
+        for pipe_group in &mut self.pipes {
+            pipe_group.enabled = false;
+        }
+    }
+
+    // Debug shortcut: ends the round on the spot with `score`, to look at
+    // the scoreboard without playing for it
+    pub fn force_game_over(&mut self, score: i32) {
+        log::debug!("forced game over with score {}", score);
+        self.score = score;
+        self.bird.kill(false);
+        self.restore_gravity();
+        self.phase = GamePhase::GameOver;
+        self.pipe_generator.stop();
+        for pipe_group in &mut self.pipes {
+            pipe_group.enabled = false;
+        }
+    }
+
+    pub fn has_won(&self) -> bool {
+        self.won
+    }
+
+    // One full headless frame: bird physics, optional flap, then the world.
+    // The first flap also dismisses the instructions, like a click does.
+    pub fn step(&mut self, flap: bool) -> Vec<SimEvent> {
+        self.update_bird(FIXED_FRAME_TIME);
+
+        let mut events = Vec::new();
+        if flap {
+            if self.phase == GamePhase::Instructions {
+                self.start();
+                events.push(SimEvent::Started);
+            }
+            if self.flap() {
+                events.push(SimEvent::Flapped);
+            }
+        }
+
+        events.extend(self.update_world());
+        events
+    }
+
+    fn ground_rect(&self) -> Rect {
+        Rect::new(self.camera.x, self.ground_y, self.screen.x, self.screen.y - self.ground_y)
+    }
+
+    fn check_for_collisions(&mut self, events: &mut Vec<SimEvent>) {
+        let mut bird_died = false;
+        // Pipes pass through an invulnerable bird, the ground still counts
+        if self.bird.alive && !self.bird.is_invulnerable() {
+            let bird_rect = self.bird.get_collision_rect();
+            for pipe_group in &mut self.pipes {
+                let Some(collision) = pipe_group.collision(&bird_rect) else {
+                    continue;
+                };
+                self.last_collision = Some(collision);
+
+                // A charge breaks the whole group, which counts as passing it
+                if self.smash_charges > 0 {
+                    self.smash_charges -= 1;
+                    pipe_group.alive = false;
+                    pipe_group.enabled = false;
+                    events.push(SimEvent::Smashed(collision.overlap.center()));
+                    log::info!("smashed the {}, {} charges left", collision.kind.name(), self.smash_charges);
+                    if !pipe_group.has_scored {
+                        pipe_group.has_scored = true;
+                        self.score += 1;
+                        self.pipes_passed += 1;
+                        events.push(SimEvent::Scored);
+                    }
+                } else {
+                    bird_died = true;
+                }
+            }
+        }
+
+        if bird_died {
+            events.push(SimEvent::PipeHit);
+            if let Some(collision) = self.last_collision {
+                log::info!("hit the {} at score {}, overlap {:?}", collision.kind.name(), self.score, collision.overlap);
+            }
+            self.bird.kill(true);
+            self.restore_gravity();
+            self.pipe_generator.stop();
+
+            for pipe_group in &mut self.pipes {
+                pipe_group.enabled = false;
+            }
+        }
+
+        let ground_rect = self.ground_rect();
+        let ground_hit = CollisionInfo::between(CollisionKind::Ground, &ground_rect, &self.bird.get_collision_rect());
+        if let Some(collision) = ground_hit {
+            self.last_collision = Some(collision);
+            events.push(SimEvent::GroundHit);
+            log::info!("hit the ground at score {}", self.score);
+            self.bird.kill(false);
+            self.restore_gravity();
+
+            // Playing -> GameOver
+            self.phase = GamePhase::GameOver;
+            self.pipe_generator.stop();
+            self.anti_frustration.record_run(self.score);
+
+            for pipe_group in &mut self.pipes {
+                pipe_group.enabled = false;
+            }
+        }
+    }
+
+    fn spawn_pipe(&mut self) {
+        // Kept in line with the real ground texture by GameScene::sync_screen
+        let ground_y = self.ground_y;
+        let gap_size = self.next_gap_size();
+        let gap_roll = self.pipe_generator.next_gap(&mut self.rng);
+        let gap_top = PipeGroup::gap_top(PipeGroup::center_biased(gap_roll, self.config.gap_center_bias), ground_y, gap_size);
+        // Just off the right edge of the screen
+        let spawn_x = self.camera.x + self.screen.x;
+        let speeds = self.pipe_speeds(spawn_x);
+
+        // Try to reuse an existing pipe group first, otherwise create a new one
+        let index = match self.pipes.iter().position(|pipe_group| !pipe_group.alive) {
+            Some(index) => index,
+            None => {
+                self.pipes.push(PipeGroup::new());
+                self.pipes.len() - 1
+            }
+        };
+        let single_pipe_chance = self.config.single_pipe_chance;
+        self.pipes[index].reset(spawn_x, gap_top, gap_size, speeds, single_pipe_chance, &mut self.rng);
+        self.pipes[index].collision_inset = self.config.pipe_collision_inset;
+
+        // Sometimes put a power-up in the middle of the gap
+        if self.rng.random::<f32>() < self.config.gravity_flip_chance {
+            let center = vec2(spawn_x + Pipe::HALF_WIDTH, self.pipes[index].gap_center());
+            let kind = Self::powerup_kind(
+                self.rng.random::<f32>(),
+                self.config.size_powerup_share,
+                self.config.smash_powerup_share,
+            );
+            let mut powerup = PowerUp::new(center, kind);
+            powerup.scroll_speed = self.pipes[index].scroll_speed;
+            self.powerups.push(powerup);
+        }
+    }
+
+    fn next_gap_size(&self) -> f32 {
+        // Sudden death never widens the gap, every point is meant to be hard
+        if self.config.mode == GameMode::SuddenDeath {
+            self.config.gap_size
+        } else {
+            self.anti_frustration.gap_size(self.config.gap_size)
+        }
+    }
+
+    // Top and height of the gaps the next groups will get if spawned now,
+    // soonest first
+    pub fn upcoming_gaps(&self) -> Vec<(f32, f32)> {
+        let gap_size = self.next_gap_size();
+        self.pipe_generator
+            .upcoming_gaps()
+            .map(|gap_roll| PipeGroup::center_biased(gap_roll, self.config.gap_center_bias))
+            .map(|gap_roll| (PipeGroup::gap_top(gap_roll, self.ground_y, gap_size), gap_size))
+            .collect()
+    }
+
+    // Centre of the next gap the bird has to get through: the nearest group
+    // not yet behind it, or the first queued gap before any has spawned
+    pub fn next_gap_center(&self) -> f32 {
+        let bird_x = self.bird.position.x;
+        self.pipes
+            .iter()
+            .filter(|pipe_group| pipe_group.alive && pipe_group.position.x + Pipe::WIDTH > bird_x)
+            .min_by(|a, b| a.position.x.total_cmp(&b.position.x))
+            .map(|pipe_group| pipe_group.gap_center())
+            .or_else(|| self.upcoming_gaps().first().map(|(gap_top, gap_size)| gap_top + gap_size / 2.0))
+            .unwrap_or(self.screen.y / 2.0)
+    }
+
+    // Picks a kind from a roll in 0..1, the size share split evenly
+    // between growing and shrinking
+    fn powerup_kind(roll: f32, size_share: f32, smash_share: f32) -> PowerUpKind {
+        if roll < size_share / 2.0 {
+            PowerUpKind::Grow
+        } else if roll < size_share {
+            PowerUpKind::Shrink
+        } else if roll < size_share + smash_share {
+            PowerUpKind::Smash
+        } else {
+            PowerUpKind::GravityFlip
+        }
+    }
+
+    // Speeds for a group spawned at `spawn_x`, capped so it can't run into
+    // the newest group already on screen
+    fn pipe_speeds(&self, spawn_x: f32) -> RangeInclusive<f32> {
+        let range = PipeGroup::speed_range(self.config.scroll_speed, self.config.pipe_speed_variation, self.score);
+        let limit = self
+            .pipes
+            .iter()
+            .filter(|pipe_group| pipe_group.alive && pipe_group.position.x < spawn_x)
+            .max_by(|a, b| a.position.x.total_cmp(&b.position.x))
+            .map_or(f32::INFINITY, |ahead| {
+                PipeGroup::catch_up_limit(spawn_x - self.camera.x, ahead.position.x - self.camera.x, ahead.scroll_speed)
+            });
+        let end = range.end().min(limit);
+        range.start().min(end)..=end
+    }
+}
+
+// Bonus for "threading the needle": passing close to the centre of the gap
+pub fn needle_bonus(bird_center_y: f32, gap_center: f32) -> i32 {
+    if (bird_center_y - gap_center).abs() <= NEEDLE_BAND {
+        NEEDLE_BONUS
+    } else {
+        0
+    }
+}
+
+/*
+
+The tests validate :
+1. Bird stays put until the first flap starts the game
+2. Pipes spawn after the generator interval
+3. Ground collision ends the game and stops further flaps
+4. Reset restores a fresh round with the new seed
+5. Same seed and inputs give the same pipes and score
+6. Needle bonus only within the band around the gap centre
+7. Phase transitions: Instructions -> Playing -> GameOver -> Instructions
+8. Countdown phase holds the bird until it runs out
+9. Zen mode: pipe overlaps don't kill and nothing is scored
+10. Pipe gaps are placed against the real ground height, not a fixed 112px
+11. Scrolling camera: the bird moves through still pipes and the camera follows
+12. Collecting a power-up flips gravity until the timer runs out
+13. Distance accumulates while flying, stops at death and resets
+14. The last collision records which pipe or the ground was hit and the overlap
+15. Restoring a snapshot brings back the recorded bird and pipe state
+16. Scoring logs the new score
+17. Pipes don't kill an invulnerable bird
+18. Groups at varied speeds still score and never run into each other
+19. Size power-ups scale the bird until their timer runs out, split by the configured share
+20. Spawned pipe groups get the gaps previewed beforehand
+21. With gravity_on_first_flap the bird hangs in place until it flaps
+22. A smash charge is used up on a pipe hit, breaking the group instead of ending the run
+23. Pipes pass through the bird during the start grace, not after it
+24. Resuming a finished run restores the checkpointed score and bird position, once
+25. Passing the pipe limit wins the run: it ends with the bird alive and the world stopped
+26. Forcing a game over ends the round from any phase with the given score
+27. The risen ground is part of a snapshot, stops at Ground::MAX_RISE and drops back on reset
+
+*/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn test_bird_waits_for_first_flap() {
+        let mut sim = Simulation::headless(1);
+        let start_y = sim.bird.position.y;
+
+        for _ in 0..30 {
+            sim.step(false);
+        }
+
+        assert_eq!(sim.phase(), GamePhase::Instructions);
+        assert_float_eq!(sim.bird.position.y, start_y, abs <= 0.001);
+
+        let events = sim.step(true);
+        assert_eq!(sim.phase(), GamePhase::Playing);
+        assert_eq!(events[..2], [SimEvent::Started, SimEvent::Flapped]);
+    }
+
+    #[test]
+    fn test_pipes_spawn_after_interval() {
+        let mut sim = Simulation::headless(1);
+        sim.start();
+        sim.bird.allow_gravity = false;
+
+        for _ in 0..79 {
+            sim.update_world();
+        }
+        assert!(sim.pipes.is_empty());
+
+        sim.update_world();
+        assert_eq!(sim.pipes.len(), 1);
+    }
+
+    #[test]
+    fn test_ground_hit_ends_game() {
+        let mut sim = Simulation::headless(1);
+        sim.step(true);
+
+        let mut events = Vec::new();
+        for _ in 0..300 {
+            events.extend(sim.step(false));
+        }
+
+        assert_eq!(sim.phase(), GamePhase::GameOver);
+        assert!(!sim.bird.alive);
+        assert!(events.contains(&SimEvent::GroundHit));
+        assert!(!sim.step(true).contains(&SimEvent::Flapped));
+        assert_eq!(sim.last_collision.map(|collision| collision.kind), Some(CollisionKind::Ground));
+    }
+
+    #[test]
+    fn test_top_pipe_hit_is_recorded() {
+        let mut sim = Simulation::headless(1);
+        sim.config.start_grace = 0.0;
+        sim.start();
+
+        let mut pipe_group = PipeGroup::new();
+        pipe_group.reset(sim.bird.position.x - Pipe::HALF_WIDTH, PipeGroup::gap_top(0.5, sim.ground_y, PipeGroup::GAP_SIZE), PipeGroup::GAP_SIZE, Speeds::SCROLL..=Speeds::SCROLL, 0.0, &mut StdRng::seed_from_u64(1));
+        // Bird pokes 5px into the bottom of the top pipe
+        let top_pipe = pipe_group.hitboxes()[0];
+        sim.bird.position.y = top_pipe.bottom() - 5.0;
+        sim.pipes.push(pipe_group);
+
+        let events = sim.update_world();
+
+        assert!(events.contains(&SimEvent::PipeHit));
+        let collision = sim.last_collision.unwrap();
+        assert_eq!(collision.kind, CollisionKind::TopPipe);
+        assert_float_eq!(collision.overlap.h, 5.0, abs <= 0.001);
+        assert_float_eq!(collision.overlap.y, top_pipe.bottom() - 5.0, abs <= 0.001);
+
+        sim.reset(2);
+        assert_eq!(sim.last_collision, None);
+    }
+
+    #[test]
+    fn test_reset_starts_fresh_round() {
+        let mut sim = Simulation::headless(1);
+        sim.step(true);
+        for _ in 0..300 {
+            sim.step(false);
+        }
+
+        sim.reset(2);
+
+        assert_eq!(sim.seed(), 2);
+        assert_eq!(sim.score, 0);
+        assert_eq!(sim.phase(), GamePhase::Instructions);
+        assert!(sim.bird.alive);
+        assert!(sim.pipes.is_empty());
+    }
+
+    #[test]
+    fn test_same_seed_is_deterministic() {
+        let run = |seed: u64| {
+            let mut sim = Simulation::headless(seed);
+            for frame in 0..400 {
+                sim.step(frame % 20 == 0);
+            }
+            let gaps: Vec<f32> = sim.pipes.iter().map(|p| p.gap_center()).collect();
+            (sim.score, gaps)
+        };
+
+        assert_eq!(run(42), run(42));
+    }
+
+    #[test]
+    fn test_needle_bonus_by_pass_height() {
+        let gap_center = 250.0;
+
+        assert_eq!(needle_bonus(gap_center, gap_center), NEEDLE_BONUS);
+        assert_eq!(needle_bonus(gap_center - NEEDLE_BAND, gap_center), NEEDLE_BONUS);
+        assert_eq!(needle_bonus(gap_center + NEEDLE_BAND, gap_center), NEEDLE_BONUS);
+        assert_eq!(needle_bonus(gap_center + NEEDLE_BAND + 1.0, gap_center), 0);
+        assert_eq!(needle_bonus(gap_center - 60.0, gap_center), 0);
+    }
+
+    #[test]
+    fn test_phase_transitions() {
+        let mut sim = Simulation::headless(1);
+        assert_eq!(sim.phase(), GamePhase::Instructions);
+
+        sim.start();
+        assert_eq!(sim.phase(), GamePhase::Playing);
+        assert!(sim.bird.allow_gravity);
+
+        for _ in 0..300 {
+            sim.step(false);
+        }
+        assert_eq!(sim.phase(), GamePhase::GameOver);
+        assert!(sim.is_game_over());
+
+        sim.reset(2);
+        assert_eq!(sim.phase(), GamePhase::Instructions);
+    }
+
+    #[test]
+    fn test_countdown_before_playing() {
+        let mut sim = Simulation::headless(1);
+        sim.config.countdown_duration = 0.5;
+        let start_y = sim.bird.position.y;
+
+        sim.start();
+        assert_eq!(sim.phase(), GamePhase::Countdown);
+
+        for _ in 0..20 {
+            sim.step(false);
+        }
+        assert_eq!(sim.phase(), GamePhase::Countdown);
+        assert_float_eq!(sim.bird.position.y, start_y, abs <= 0.001);
+        assert!(sim.pipes.is_empty());
+
+        for _ in 0..20 {
+            sim.step(false);
+        }
+        assert_eq!(sim.phase(), GamePhase::Playing);
+    }
+
+    #[test]
+    fn test_zen_mode_has_no_death_or_score() {
+        let mut sim = Simulation::headless(1);
+        sim.config.mode = GameMode::Zen;
+        sim.start();
+
+        // Pipe group right on top of the bird, already past the scoring line
+        let mut pipe_group = PipeGroup::new();
+        pipe_group.reset(sim.bird.position.x - Pipe::HALF_WIDTH, PipeGroup::gap_top(0.5, sim.ground_y, PipeGroup::GAP_SIZE), PipeGroup::GAP_SIZE, Speeds::SCROLL..=Speeds::SCROLL, 0.0, &mut StdRng::seed_from_u64(1));
+        sim.pipes.push(pipe_group);
+
+        let mut events = Vec::new();
+        for _ in 0..600 {
+            events.extend(sim.step(false));
+        }
+
+        assert!(sim.bird.alive);
+        assert_eq!(sim.phase(), GamePhase::Playing);
+        assert_eq!(sim.score, 0);
+        // Flying close by still whooshes, but nothing hits or scores
+        assert!(events.iter().all(|event| *event == SimEvent::Whoosh));
+        assert!(sim.bird.position.y < sim.ground_y);
+    }
+
+    #[test]
+    fn test_spawn_uses_real_ground_height() {
+        // A ground strip much taller than the 112px the gaps used to assume
+        let ground_height = 200.0;
+        let mut sim = Simulation::headless(5);
+        sim.ground_y = sim.screen.y - ground_height;
+
+        for _ in 0..50 {
+            sim.spawn_pipe();
+            sim.pipes[0].alive = false;
+
+            // reset() keeps 100px between the bottom of the gap and the ground
+            let gap_bottom = sim.pipes[0].gap_center() + PipeGroup::GAP_SIZE / 2.0;
+            assert!(gap_bottom <= sim.ground_y - 100.0 + 0.001, "gap reaches {} with ground at {}", gap_bottom, sim.ground_y);
+        }
+    }
+
+    #[test]
+    fn test_scrolling_camera_follows_bird() {
+        let mut sim = Simulation::headless(3);
+        sim.config.scrolling_camera = true;
+        let start_x = sim.bird.position.x;
+
+        let mut frames = 0;
+        while sim.pipes.is_empty() {
+            sim.step(frames == 0 || sim.bird.position.y > sim.screen.y / 2.0);
+            frames += 1;
+        }
+        let pipe_x = sim.pipes[0].position.x;
+
+        for _ in 0..30 {
+            sim.step(sim.bird.position.y > sim.screen.y / 2.0);
+        }
+
+        assert!(sim.bird.alive);
+        assert!(sim.bird.position.x > start_x + 100.0);
+        // Pipes keep their world position
+        assert_float_eq!(sim.pipes[0].position.x, pipe_x, abs <= 0.001);
+        // Camera keeps the bird close to its usual spot on screen
+        let screen_x = sim.bird.position.x - sim.camera.x;
+        assert!((screen_x - sim.camera.offset).abs() < 20.0, "bird drawn at {}", screen_x);
+    }
+
+    #[test]
+    fn test_gravity_flip_powerup_and_restore() {
+        let mut sim = Simulation::headless(4);
+        sim.config.mode = GameMode::Zen;
+        sim.config.gravity_flip_duration = 1.0;
+        sim.start();
+
+        let bird_center = sim.bird.get_collision_rect().center();
+        sim.powerups.push(PowerUp::new(bird_center + vec2(Speeds::distance(Speeds::SCROLL, FIXED_FRAME_TIME), 0.0), PowerUpKind::GravityFlip));
+
+        let events = sim.step(false);
+        assert!(events.contains(&SimEvent::GravityFlipped));
+        assert!(sim.powerups.is_empty());
+        assert_float_eq!(sim.bird.gravity_sign, -1.0, abs <= 0.001);
+        assert_float_eq!(sim.gravity_flip_remaining(), 1.0, abs <= 0.001);
+
+        // One second at 60 FPS, plus a frame to spare
+        for _ in 0..61 {
+            sim.step(false);
+        }
+        assert_float_eq!(sim.bird.gravity_sign, 1.0, abs <= 0.001);
+        assert_float_eq!(sim.gravity_flip_remaining(), 0.0, abs <= 0.001);
+    }
+
+    #[test]
+    fn test_size_powerup_resizes_until_timer_runs_out() {
+        let mut sim = Simulation::headless(4);
+        sim.config.mode = GameMode::Zen;
+        sim.config.size_powerup_duration = 1.0;
+        sim.start();
+
+        let bird_center = sim.bird.get_collision_rect().center();
+        let center = bird_center + vec2(Speeds::distance(Speeds::SCROLL, FIXED_FRAME_TIME), 0.0);
+        sim.powerups.push(PowerUp::new(center, PowerUpKind::Shrink));
+
+        let events = sim.step(false);
+        assert!(events.contains(&SimEvent::Resized(0.6)));
+        assert_float_eq!(sim.bird.get_collision_rect().w, Bird::FRAME_WIDTH * 0.6, abs <= 0.001);
+        assert_float_eq!(sim.bird.gravity_sign, 1.0, abs <= 0.001);
+
+        for _ in 0..61 {
+            sim.step(false);
+        }
+        assert_float_eq!(sim.bird.get_collision_rect().w, Bird::FRAME_WIDTH, abs <= 0.001);
+
+        assert_eq!(Simulation::powerup_kind(0.1, 0.3, 0.0), PowerUpKind::Grow);
+        assert_eq!(Simulation::powerup_kind(0.2, 0.3, 0.0), PowerUpKind::Shrink);
+        assert_eq!(Simulation::powerup_kind(0.5, 0.3, 0.0), PowerUpKind::GravityFlip);
+        assert_eq!(Simulation::powerup_kind(0.0, 0.0, 0.0), PowerUpKind::GravityFlip);
+        assert_eq!(Simulation::powerup_kind(0.35, 0.3, 0.1), PowerUpKind::Smash);
+        assert_eq!(Simulation::powerup_kind(0.45, 0.3, 0.1), PowerUpKind::GravityFlip);
+    }
+
+    #[test]
+    fn test_distance_accumulates_while_flying() {
+        let mut sim = Simulation::headless(6);
+        sim.config.mode = GameMode::Zen;
+        sim.start();
+
+        for _ in 0..100 {
+            sim.step(false);
+        }
+        // 3 pixels a frame
+        assert_float_eq!(sim.distance, 300.0, abs <= 0.01);
+        assert_eq!(sim.meters(), (300.0 / PIXELS_PER_METER) as i32);
+
+        sim.bird.kill(false);
+        sim.step(false);
+        assert_float_eq!(sim.distance, 300.0, abs <= 0.01);
+
+        sim.reset(7);
+        assert_float_eq!(sim.distance, 0.0, abs <= 0.001);
+    }
+
+    #[test]
+    fn test_restore_snapshot_reproduces_state() {
+        let mut sim = Simulation::headless(3);
+        sim.step(true);
+        for i in 0..200 {
+            sim.step(i % 20 == 0);
+        }
+        let snapshot = sim.snapshot();
+        let bird = sim.bird.state();
+        let pipes: Vec<Vec2> = sim.pipes.iter().map(|pipe_group| pipe_group.position).collect();
+        let score = sim.score;
+
+        // Play on until the bird dies
+        for _ in 0..400 {
+            sim.step(false);
+        }
+        assert!(!sim.bird.alive);
+
+        sim.restore(&snapshot);
+        assert_eq!(sim.bird.state(), bird);
+        assert_eq!(sim.pipes.iter().map(|pipe_group| pipe_group.position).collect::<Vec<_>>(), pipes);
+        assert_eq!(sim.score, score);
+        assert_eq!(sim.phase(), GamePhase::Playing);
+
+        // Replaying the same inputs from the snapshot gives the same result
+        let mut replay = Simulation::headless(3);
+        replay.restore(&snapshot);
+        for _ in 0..100 {
+            sim.step(false);
+            replay.step(false);
+        }
+        assert_eq!(sim.bird.state(), replay.bird.state());
+        assert_eq!(sim.pipes.len(), replay.pipes.len());
+    }
+
+    #[test]
+    fn test_scoring_is_logged() {
+        let mut sim = Simulation::headless(1);
+        sim.start();
+        let mut pipe_group = PipeGroup::new();
+        pipe_group.reset(sim.bird.position.x - Pipe::WIDTH, PipeGroup::gap_top(0.5, sim.ground_y, PipeGroup::GAP_SIZE), PipeGroup::GAP_SIZE, Speeds::SCROLL..=Speeds::SCROLL, 0.0, &mut StdRng::seed_from_u64(1));
+        pipe_group.enabled = false;
+        sim.pipes.push(pipe_group);
+
+        let logs = crate::systems::logging::capture::logs_of(|| {
+            sim.update_world();
+        });
+
+        assert_eq!(sim.score, 1);
+        assert!(logs.contains(&"INFO scored, score is now 1".to_string()), "{:?}", logs);
+    }
+
+    #[test]
+    fn test_invulnerable_bird_passes_through_pipes() {
+        let mut sim = Simulation::headless(1);
+        sim.start();
+        let mut pipe_group = PipeGroup::new();
+        pipe_group.reset(sim.bird.position.x - Pipe::HALF_WIDTH, PipeGroup::gap_top(0.5, sim.ground_y, PipeGroup::GAP_SIZE), PipeGroup::GAP_SIZE, Speeds::SCROLL..=Speeds::SCROLL, 0.0, &mut StdRng::seed_from_u64(1));
+        sim.bird.position.y = pipe_group.hitboxes()[0].bottom() - 5.0;
+        sim.pipes.push(pipe_group);
+
+        sim.bird.invulnerable_timer = 1.0;
+        assert!(!sim.update_world().contains(&SimEvent::PipeHit));
+        assert!(sim.bird.alive);
+
+        sim.bird.invulnerable_timer = 0.0;
+        assert!(sim.update_world().contains(&SimEvent::PipeHit));
+    }
+
+    #[test]
+    fn test_varied_pipe_speeds_score_and_keep_apart() {
+        let mut sim = Simulation::headless(9);
+        sim.config.pipe_speed_variation = 0.3;
+        sim.step(true);
+        // Full variation from the start, and pipes can't end the run
+        sim.score = PipeGroup::FULL_VARIATION_SCORE;
+        sim.bird.invulnerable_timer = f32::MAX;
+
+        let mut speeds = Vec::new();
+        for _ in 0..3000 {
+            let flap = sim.bird.position.y > sim.screen.y / 2.0;
+            sim.step(flap);
+            assert!(sim.bird.alive);
+
+            let mut groups: Vec<&PipeGroup> = sim.pipes.iter().filter(|pipe_group| pipe_group.alive).collect();
+            groups.sort_by(|a, b| a.position.x.total_cmp(&b.position.x));
+            for pair in groups.windows(2) {
+                assert!(pair[1].position.x - pair[0].position.x >= Pipe::WIDTH);
+            }
+            speeds.extend(groups.iter().map(|pipe_group| pipe_group.scroll_speed));
+        }
+
+        assert!(sim.score > PipeGroup::FULL_VARIATION_SCORE + 10);
+        assert!(speeds.iter().any(|speed| (speed - Speeds::SCROLL).abs() > 0.1));
+    }
+
+    #[test]
+    fn test_spawned_pipes_use_previewed_gaps() {
+        let mut sim = Simulation::headless(12);
+        sim.config.mode = GameMode::Zen;
+        sim.start();
+
+        let previewed = sim.upcoming_gaps();
+        assert_eq!(previewed.len(), PipeGenerator::QUEUED_GAPS);
+
+        let mut spawned = Vec::new();
+        while spawned.len() < previewed.len() {
+            sim.step(false);
+            for pipe_group in &sim.pipes {
+                let gap_top = pipe_group.gap_center() - PipeGroup::GAP_SIZE / 2.0;
+                if !spawned.iter().any(|top: &f32| (top - gap_top).abs() < 0.001) {
+                    spawned.push(gap_top);
+                }
+            }
+        }
+
+        for ((gap_top, _), spawned_top) in previewed.iter().zip(&spawned) {
+            assert_float_eq!(*gap_top, *spawned_top, abs <= 0.001);
+        }
+    }
+
+    #[test]
+    fn test_gravity_waits_for_first_flap() {
+        let mut sim = Simulation::headless(1);
+        sim.config.gravity_on_first_flap = true;
+        sim.start();
+        let start_y = sim.bird.position.y;
+
+        for _ in 0..60 {
+            sim.step(false);
+        }
+        assert!(sim.bird.alive);
+        assert_float_eq!(sim.bird.position.y, start_y, abs <= 0.001);
+
+        sim.step(true);
+        assert!(sim.bird.allow_gravity);
+        for _ in 0..120 {
+            sim.step(false);
+        }
+        assert!(sim.bird.position.y > start_y);
+    }
+
+    #[test]
+    fn test_smash_charge_survives_pipe_hit() {
+        let mut sim = Simulation::headless(1);
+        sim.config.start_grace = 0.0;
+        sim.start();
+        let place_pipe = |sim: &mut Simulation| {
+            let mut pipe_group = PipeGroup::new();
+            pipe_group.reset(sim.bird.position.x - Pipe::HALF_WIDTH, PipeGroup::gap_top(0.5, sim.ground_y, PipeGroup::GAP_SIZE), PipeGroup::GAP_SIZE, Speeds::SCROLL..=Speeds::SCROLL, 0.0, &mut StdRng::seed_from_u64(1));
+            sim.bird.position.y = pipe_group.hitboxes()[0].bottom() - 5.0;
+            sim.pipes.push(pipe_group);
+        };
+
+        sim.smash_charges = 1;
+        place_pipe(&mut sim);
+        let events = sim.update_world();
+        assert!(!events.contains(&SimEvent::PipeHit));
+        assert!(events.iter().any(|event| matches!(event, SimEvent::Smashed(_))));
+        assert_eq!(sim.smash_charges, 0);
+        assert!(sim.bird.alive);
+        assert!(!sim.is_game_over());
+        assert!(!sim.pipes[0].alive);
+
+        // Out of charges, the next pipe kills as usual
+        place_pipe(&mut sim);
+        assert!(sim.update_world().contains(&SimEvent::PipeHit));
+        assert!(!sim.bird.alive);
+    }
+
+    #[test]
+    fn test_start_grace_skips_pipe_hits() {
+        let mut sim = Simulation::headless(1);
+        sim.config.start_grace = 0.5;
+        sim.step(true);
+        sim.bird.allow_gravity = false;
+        let mut pipe_group = PipeGroup::new();
+        pipe_group.reset(sim.bird.position.x - Pipe::HALF_WIDTH, PipeGroup::gap_top(0.5, sim.ground_y, PipeGroup::GAP_SIZE), PipeGroup::GAP_SIZE, 0.0..=0.0, 0.0, &mut StdRng::seed_from_u64(1));
+        sim.bird.position.y = pipe_group.hitboxes()[0].bottom() - 5.0;
+        sim.pipes.push(pipe_group);
+
+        // Still inside the grace after 0.4s
+        for _ in 0..24 {
+            assert!(!sim.step(false).contains(&SimEvent::PipeHit));
+        }
+        assert!(sim.bird.alive);
+
+        let mut events = Vec::new();
+        for _ in 0..12 {
+            events.extend(sim.step(false));
+        }
+        assert!(events.contains(&SimEvent::PipeHit));
+        assert!(!sim.bird.alive);
+    }
+
+    #[test]
+    fn test_resume_from_checkpoint() {
+        let mut sim = Simulation::headless(1);
+        sim.config.checkpoints = true;
+        sim.step(true);
+        sim.score = Simulation::CHECKPOINT_INTERVAL - 1;
+        let mut pipe_group = PipeGroup::new();
+        pipe_group.reset(sim.bird.position.x - Pipe::WIDTH, PipeGroup::gap_top(0.5, sim.ground_y, PipeGroup::GAP_SIZE), PipeGroup::GAP_SIZE, Speeds::SCROLL..=Speeds::SCROLL, 0.0, &mut StdRng::seed_from_u64(1));
+        pipe_group.enabled = false;
+        sim.pipes.push(pipe_group);
+
+        assert!(sim.update_world().contains(&SimEvent::Checkpoint));
+        assert_eq!(sim.checkpoint_score(), Some(Simulation::CHECKPOINT_INTERVAL));
+        let checkpoint_position = sim.bird.position;
+        assert!(!sim.resume_from_checkpoint(), "only a finished run resumes");
+
+        while !sim.is_game_over() {
+            sim.step(false);
+        }
+        assert!(sim.can_resume());
+        assert!(sim.resume_from_checkpoint());
+        assert_eq!(sim.score, Simulation::CHECKPOINT_INTERVAL);
+        assert_eq!(sim.bird.position, checkpoint_position);
+        assert_eq!(sim.phase(), GamePhase::Playing);
+        assert!(sim.bird.alive);
+
+        // Only once per run
+        while !sim.is_game_over() {
+            sim.step(false);
+        }
+        assert!(!sim.resume_from_checkpoint());
+        sim.reset(2);
+        assert_eq!(sim.checkpoint_score(), None);
+    }
+
+    #[test]
+    fn test_pipe_limit_wins_the_run() {
+        let mut sim = Simulation::headless(1);
+        sim.config.pipe_limit = 2;
+        sim.step(true);
+        let gap_top = PipeGroup::gap_top(0.5, sim.ground_y, PipeGroup::GAP_SIZE);
+        let add_passed_pipe = |sim: &mut Simulation| {
+            let mut pipe_group = PipeGroup::new();
+            pipe_group.reset(sim.bird.position.x - Pipe::WIDTH, gap_top, PipeGroup::GAP_SIZE, Speeds::SCROLL..=Speeds::SCROLL, 0.0, &mut StdRng::seed_from_u64(1));
+            pipe_group.enabled = false;
+            sim.pipes.push(pipe_group);
+        };
+
+        add_passed_pipe(&mut sim);
+        let events = sim.update_world();
+        assert!(!events.contains(&SimEvent::Won));
+        assert_eq!(sim.phase(), GamePhase::Playing);
+
+        add_passed_pipe(&mut sim);
+        let events = sim.update_world();
+        assert!(events.contains(&SimEvent::Won));
+        assert_eq!(sim.pipes_passed, 2);
+        assert!(sim.has_won());
+        assert!(sim.is_game_over());
+        assert!(sim.bird.alive);
+        assert!(!sim.flap());
+
+        // Nothing moves on once won
+        let position = sim.bird.position;
+        let score = sim.score;
+        for _ in 0..120 {
+            assert!(sim.step(false).is_empty());
+        }
+        assert_eq!(sim.bird.position, position);
+        assert_eq!(sim.score, score);
+
+        sim.reset(2);
+        assert!(!sim.has_won());
+        assert_eq!(sim.pipes_passed, 0);
+    }
+
+    #[test]
+    fn test_force_game_over_sets_score() {
+        let mut sim = Simulation::headless(1);
+        sim.force_game_over(42);
+        assert!(sim.is_game_over());
+        assert_eq!(sim.score, 42);
+        assert!(!sim.bird.alive);
+        assert!(!sim.flap());
+
+        // Also mid-run, with nothing moving on afterwards
+        sim.reset(1);
+        for _ in 0..30 {
+            sim.step(true);
+        }
+        sim.force_game_over(7);
+        assert_eq!(sim.phase(), GamePhase::GameOver);
+        assert!(sim.step(false).is_empty());
+        assert_eq!(sim.score, 7);
+    }
+
+    #[test]
+    fn test_ground_rise_is_snapshotted() {
+        let mut sim = Simulation::headless(3);
+        sim.rise_ground(40.0);
+        let snapshot = sim.snapshot();
+
+        sim.rise_ground(Ground::MAX_RISE * 2.0);
+        assert_float_eq!(sim.ground_rise, Ground::MAX_RISE, abs <= 0.001);
+
+        sim.restore(&snapshot);
+        assert_float_eq!(sim.ground_rise, 40.0, abs <= 0.001);
+
+        sim.reset(3);
+        assert_float_eq!(sim.ground_rise, 0.0, abs <= 0.001);
+    }
+}
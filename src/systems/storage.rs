@@ -1,39 +1,228 @@
+use std::fmt;
 use std::fs;
-use crate::FILE_NAME;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
 
-pub fn write(high_score: i32) -> std::io::Result<()> {
-    fs::write(FILE_NAME, high_score.to_string())?;
-    Ok(())
+// Names the folder to keep save files in, overriding the default
+pub const SAVE_PATH_ENV: &str = "FLAPPY_SAVE_PATH";
+
+// Highest score we accept as genuine. Anything above it is a bug or an edited file.
+pub const MAX_HIGH_SCORE: i32 = 9999;
+// Same for the best distance, in meters
+pub const MAX_DISTANCE: i32 = 1_000_000;
+// Total seconds played, i32::MAX is some 68 years
+pub const MAX_TIME_PLAYED: i32 = i32::MAX;
+// Longest file read back. A stored number is a few digits, anything
+// longer is corrupt and isn't worth reading into memory.
+pub const MAX_FILE_BYTES: u64 = 64;
+// A failed write is tried this many times before giving up
+const WRITE_ATTEMPTS: usize = 2;
+
+// Why a value couldn't be stored, so callers can tell the player
+#[derive(Debug)]
+pub enum StorageError {
+    Negative(i32),
+    Io(io::Error),
+}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StorageError::Negative(value) => write!(f, "refusing to store negative value {}", value),
+            StorageError::Io(err) => write!(f, "could not save: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for StorageError {}
+
+impl From<io::Error> for StorageError {
+    fn from(err: io::Error) -> Self {
+        StorageError::Io(err)
+    }
+}
+
+// Where a save file lives: in FLAPPY_SAVE_PATH if set, otherwise in the
+// OS data directory (e.g. ~/.local/share/flappy-bird), otherwise in the
+// working directory. Saves already in the working directory stay there.
+pub fn save_path(file_name: &str) -> String {
+    let env_dir = std::env::var_os(SAVE_PATH_ENV).filter(|dir| !dir.is_empty()).map(PathBuf::from);
+    let data_dir = directories::ProjectDirs::from("", "", "flappy-bird").map(|dirs| dirs.data_dir().to_path_buf());
+    resolve_save_path(file_name, env_dir, Path::new(""), data_dir).to_string_lossy().into_owned()
+}
+
+// `legacy_dir` is where older versions saved, the working directory
+pub fn resolve_save_path(file_name: &str, env_dir: Option<PathBuf>, legacy_dir: &Path, data_dir: Option<PathBuf>) -> PathBuf {
+    if let Some(dir) = env_dir {
+        return dir.join(file_name);
+    }
+    // Records saved before the data directory was used stay where they are
+    let legacy = legacy_dir.join(file_name);
+    if legacy.exists() {
+        return legacy;
+    }
+    data_dir.map_or(legacy, |dir| dir.join(file_name))
+}
+
+// Negative scores are rejected, very large ones are clamped to MAX_HIGH_SCORE
+pub fn write_to(path: &str, high_score: i32) -> Result<(), StorageError> {
+    write_number(path, high_score, MAX_HIGH_SCORE)
+}
+
+// Out of range values read as 0, as if no high score was stored yet
+pub fn read_from(path: &str) -> io::Result<i32> {
+    read_number(path, MAX_HIGH_SCORE)
+}
+
+pub fn write_best_distance_to(path: &str, meters: i32) -> Result<(), StorageError> {
+    write_number(path, meters, MAX_DISTANCE)
 }
 
-pub fn read() -> std::io::Result<i32> {
-    match fs::read_to_string(FILE_NAME) {
-        Ok(content) => content.trim().parse().map_err(|e| {
-            std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                format!("Failed to parse high score: {}", e)
+pub fn read_best_distance_from(path: &str) -> io::Result<i32> {
+    read_number(path, MAX_DISTANCE)
+}
+
+pub fn read_time_played_from(path: &str) -> io::Result<i32> {
+    read_number(path, MAX_TIME_PLAYED)
+}
+
+// Summary - SessionStats :
+// Time played since the last save. Saving adds the whole seconds to the
+// stored total and forgets them, so saving at game over and again when
+// leaving the run doesn't count anything twice.
+#[derive(Debug, Default)]
+pub struct SessionStats {
+    unsaved_seconds: f32,
+}
+
+impl SessionStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_time(&mut self, dt: f32) {
+        self.unsaved_seconds += dt.max(0.0);
+    }
+
+    // Nothing is written until there's at least a whole second to add.
+    // The unsaved time is kept when the write fails, to try again later.
+    pub fn save_to(&mut self, path: &str) -> Result<(), StorageError> {
+        let seconds = self.unsaved_seconds.floor();
+        if seconds < 1.0 {
+            return Ok(());
+        }
+        let total = read_time_played_from(path).unwrap_or(0).saturating_add(seconds as i32);
+        write_number(path, total, MAX_TIME_PLAYED)?;
+        self.unsaved_seconds -= seconds;
+        Ok(())
+    }
+}
+
+// The previous value is kept next to the file, e.g. highscore.txt.bak
+pub fn backup_path(path: &str) -> String {
+    format!("{}.bak", path)
+}
+
+fn write_number(path: &str, value: i32, max: i32) -> Result<(), StorageError> {
+    if value < 0 {
+        return Err(StorageError::Negative(value));
+    }
+
+    let content = value.min(max).to_string();
+    let mut result = Ok(());
+    for _ in 0..WRITE_ATTEMPTS {
+        result = write_atomic(path, &content);
+        if result.is_ok() {
+            break;
+        }
+    }
+    Ok(result?)
+}
+
+// Writes to a temp file and renames it over the original, so a failed
+// write never leaves a half written file behind
+fn write_atomic(path: &str, content: &str) -> io::Result<()> {
+    // The data directory doesn't exist before the first save
+    if let Some(dir) = Path::new(path).parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let temp_path = format!("{}.tmp", path);
+    fs::write(&temp_path, content)?;
+
+    let result = (|| {
+        if fs::metadata(path).is_ok() {
+            fs::copy(path, backup_path(path))?;
+        }
+        fs::rename(&temp_path, path)
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+// Corrupt files fall back to the backup before giving up
+fn read_number(path: &str, max: i32) -> io::Result<i32> {
+    match read_number_file(path, max) {
+        Err(err) if err.kind() == io::ErrorKind::InvalidData => {
+            read_number_file(&backup_path(path), max).map_err(|_| err)
+        }
+        result => result,
+    }
+}
+
+fn read_number_file(path: &str, max: i32) -> io::Result<i32> {
+    match read_capped(path) {
+        Ok(content) => content.trim().parse().map(|value| sanitize(value, max)).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Failed to parse stored value: {}", e)
             )
         }),
-        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(0),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(0),
         Err(err) => Err(err),
     }
 }
 
-/*
+// Reads at most MAX_FILE_BYTES, longer or non-UTF-8 files are InvalidData
+fn read_capped(path: &str) -> io::Result<String> {
+    let mut bytes = Vec::new();
+    fs::File::open(path)?.take(MAX_FILE_BYTES + 1).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 > MAX_FILE_BYTES {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "stored value is too long"));
+    }
+    String::from_utf8(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
 
-Using tempfile crate to test files safely in an isolated environment
+fn sanitize(value: i32, max: i32) -> i32 {
+    if (0..=max).contains(&value) {
+        value
+    } else {
+        0
+    }
+}
 
-We can refactor production code to pass a file path:
+/*
 
-   pub fn write_to(path: &str, score: i32) -> std::io::Result<()> { ... }
-   pub fn read_from(path: &str) -> std::io::Result<i32> { ... }
+Using tempfile crate to test files safely in an isolated environment
 
-Then test the real logic with tempfile, fully isolated. 
+write_to/read_from take a file path, so the real logic is tested with
+tempfile, fully isolated.
 
 The tests validate :
 1. Proper functioning of writing and reading from file
 2. Return zero when file not found
 3. Parse fails on invalid data
+4. Negative scores are rejected and huge ones clamped when writing
+5. Out of range values read back as 0
+6. Best distance is stored separately from the high score
+7. Writes replace the file through a rename and keep the previous value as a backup
+8. A corrupt file reads back the backup, and fails when there is none
+9. Write failures come back as typed errors
+10. Oversized and binary files read back as 0, small ones still load
+11. Session time is saved once, repeated saves don't add it again
+12. FLAPPY_SAVE_PATH overrides the save folder, then older saves are kept where they are, then the OS data folder is used
+13. Saving into a folder that doesn't exist yet creates it
 
 */
 
@@ -77,4 +266,162 @@ mod storemanagement_tests {
 
         assert!(parsed.is_err());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_write_rejects_negative_and_clamps_huge() {
+        let tmp = NamedTempFile::new().unwrap();
+        let path = tmp.path().to_str().unwrap();
+
+        assert!(super::write_to(path, -5).is_err());
+
+        super::write_to(path, i32::MAX).unwrap();
+        assert_eq!(super::read_from(path).unwrap(), super::MAX_HIGH_SCORE);
+
+        super::write_to(path, 42).unwrap();
+        assert_eq!(super::read_from(path).unwrap(), 42);
+    }
+
+    #[test]
+    fn test_read_sanitizes_out_of_range_values() {
+        let tmp = NamedTempFile::new().unwrap();
+        let path = tmp.path().to_str().unwrap();
+
+        std::fs::write(path, "-12").unwrap();
+        assert_eq!(super::read_from(path).unwrap(), 0);
+
+        std::fs::write(path, "2000000000").unwrap();
+        assert_eq!(super::read_from(path).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_best_distance_persists_separately() {
+        let score_file = NamedTempFile::new().unwrap();
+        let distance_file = NamedTempFile::new().unwrap();
+        let score_path = score_file.path().to_str().unwrap();
+        let distance_path = distance_file.path().to_str().unwrap();
+
+        super::write_to(score_path, 12).unwrap();
+        super::write_best_distance_to(distance_path, 25_000).unwrap();
+
+        assert_eq!(super::read_from(score_path).unwrap(), 12);
+        assert_eq!(super::read_best_distance_from(distance_path).unwrap(), 25_000);
+    }
+
+    #[test]
+    fn test_write_is_atomic_and_keeps_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("highscore.txt");
+        let path = path.to_str().unwrap();
+
+        super::write_to(path, 10).unwrap();
+        super::write_to(path, 20).unwrap();
+
+        assert_eq!(std::fs::read_to_string(path).unwrap(), "20");
+        assert_eq!(std::fs::read_to_string(super::backup_path(path)).unwrap(), "10");
+        // No temp file is left behind
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 2);
+    }
+
+    #[test]
+    fn test_corrupt_file_falls_back_to_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("highscore.txt");
+        let path = path.to_str().unwrap();
+
+        super::write_to(path, 10).unwrap();
+        super::write_to(path, 20).unwrap();
+        std::fs::write(path, "").unwrap();
+        assert_eq!(super::read_from(path).unwrap(), 10);
+
+        // Without a usable backup the parse error is reported
+        std::fs::write(super::backup_path(path), "junk").unwrap();
+        let err = super::read_from(path).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_write_errors_are_typed() {
+        let dir = tempfile::tempdir().unwrap();
+
+        // A file where the folder should be can't be saved into
+        let blocker = dir.path().join("blocker");
+        std::fs::write(&blocker, "").unwrap();
+        let blocked = blocker.join("highscore.txt");
+        let err = super::write_to(blocked.to_str().unwrap(), 5).unwrap_err();
+        assert!(matches!(err, super::StorageError::Io(_)));
+        assert!(err.to_string().starts_with("could not save"));
+
+        assert!(matches!(super::write_to("unused.txt", -1), Err(super::StorageError::Negative(-1))));
+    }
+
+    #[test]
+    fn test_read_caps_file_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("highscore.txt");
+        let path = path.to_str().unwrap();
+
+        std::fs::write(path, "57\n").unwrap();
+        assert_eq!(super::read_from(path).unwrap(), 57);
+
+        // Corrupt with no backup reads as no high score yet.
+        // Padding a valid number past the cap still counts as corrupt.
+        let oversized = format!("{}57", " ".repeat(super::MAX_FILE_BYTES as usize));
+        std::fs::write(path, oversized).unwrap();
+        assert_eq!(super::read_from(path).unwrap(), 0);
+
+        std::fs::write(path, vec![b'7'; 1 << 20]).unwrap();
+        assert_eq!(super::read_from(path).unwrap(), 0);
+
+        std::fs::write(path, [0xff, 0xfe, 0x00, 0x31]).unwrap();
+        assert_eq!(super::read_from(path).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_session_saves_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("time_played.txt");
+        let path = path.to_str().unwrap();
+
+        // Quitting mid-run saves at game over and again on the way out
+        let mut session = super::SessionStats::new();
+        session.add_time(12.5);
+        session.save_to(path).unwrap();
+        assert_eq!(super::read_time_played_from(path).unwrap(), 12);
+        session.save_to(path).unwrap();
+        assert_eq!(super::read_time_played_from(path).unwrap(), 12);
+
+        // The leftover half second carries into the next save
+        session.add_time(2.6);
+        session.save_to(path).unwrap();
+        assert_eq!(super::read_time_played_from(path).unwrap(), 15);
+    }
+
+    #[test]
+    fn test_save_path_resolution() {
+        use super::resolve_save_path;
+        use std::path::{Path, PathBuf};
+
+        let env_dir = Some(PathBuf::from("/saves"));
+        let data_dir = Some(PathBuf::from("/data"));
+        let no_legacy = Path::new("/no/such/dir");
+        assert_eq!(resolve_save_path("save.txt", env_dir.clone(), no_legacy, data_dir.clone()), PathBuf::from("/saves/save.txt"));
+        assert_eq!(resolve_save_path("save.txt", None, no_legacy, data_dir.clone()), PathBuf::from("/data/save.txt"));
+        assert_eq!(resolve_save_path("save.txt", None, no_legacy, None), PathBuf::from("/no/such/dir/save.txt"));
+
+        // A record left by an older version is kept, unless FLAPPY_SAVE_PATH is set
+        let legacy_dir = tempfile::tempdir().unwrap();
+        std::fs::write(legacy_dir.path().join("save.txt"), "12").unwrap();
+        assert_eq!(resolve_save_path("save.txt", None, legacy_dir.path(), data_dir.clone()), legacy_dir.path().join("save.txt"));
+        assert_eq!(resolve_save_path("save.txt", env_dir, legacy_dir.path(), data_dir), PathBuf::from("/saves/save.txt"));
+    }
+
+    #[test]
+    fn test_write_creates_save_folder() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("flappy-bird/highscore.txt");
+        let path = path.to_str().unwrap();
+
+        super::write_to(path, 12).unwrap();
+        assert_eq!(super::read_from(path).unwrap(), 12);
+    }
+}
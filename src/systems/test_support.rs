@@ -0,0 +1,140 @@
+use std::iter;
+use crate::scenes::game::GameResult;
+use crate::systems::benchmark;
+use crate::systems::simulation::{SimEvent, Simulation, FIXED_FRAME_TIME};
+
+// Frames the bird is left to fall after the script runs out, far more than
+// it takes to reach the ground from anywhere on screen
+const MAX_TAIL_FRAMES: usize = 600;
+
+// One entry of a recorded input script
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FlapInput {
+    // Flap on this frame
+    Flap,
+    // Let this many frames pass without flapping
+    Wait(u32),
+}
+
+impl FlapInput {
+    fn frames(self) -> impl Iterator<Item = bool> {
+        let (flap, count) = match self {
+            FlapInput::Flap => (true, 1),
+            FlapInput::Wait(frames) => (false, frames as usize),
+        };
+        iter::repeat_n(flap, count)
+    }
+}
+
+// How a scripted run ended
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SimulatedRun {
+    // Against no previous high score, the duration runs from the first flap
+    pub result: GameResult,
+    // Frame the bird died on, counted from the first frame of the script.
+    // None if the script never started the run.
+    pub death_frame: Option<u32>,
+}
+
+// Script of what the benchmark's autopilot does over `frames` frames of
+// `seed`, to record a run that gets somewhere
+pub fn record_autopilot(seed: u64, frames: u32) -> Vec<FlapInput> {
+    let mut sim = Simulation::headless(seed);
+    let mut inputs = Vec::new();
+    let mut waited = 0;
+    for _ in 0..frames {
+        let flap = benchmark::scripted_flap(&sim);
+        sim.step(flap);
+        if flap {
+            if waited > 0 {
+                inputs.push(FlapInput::Wait(waited));
+                waited = 0;
+            }
+            inputs.push(FlapInput::Flap);
+        } else {
+            waited += 1;
+        }
+    }
+    inputs
+}
+
+// Summary - simulate() :
+// Plays `inputs` frame by frame through the headless step API on a fresh
+// simulation, then leaves the bird to fall until the run ends. Seed and
+// script alone decide the outcome, so golden values catch gameplay and
+// balance changes.
+pub fn simulate(seed: u64, inputs: &[FlapInput]) -> SimulatedRun {
+    let mut sim = Simulation::headless(seed);
+    let script = inputs.iter().flat_map(|input| input.frames());
+
+    let mut start_frame = None;
+    let mut death_frame = None;
+    for (frame, flap) in (0..).zip(script.chain(iter::repeat_n(false, MAX_TAIL_FRAMES))) {
+        let events = sim.step(flap);
+        if events.contains(&SimEvent::Started) {
+            start_frame = Some(frame);
+        }
+        if start_frame.is_some() && !sim.bird.alive {
+            death_frame = Some(frame);
+            break;
+        }
+    }
+
+    let flown_frames = death_frame.zip(start_frame).map_or(0, |(death, start)| death - start);
+    SimulatedRun {
+        result: GameResult::new(&sim, 0, flown_frames as f32 * FIXED_FRAME_TIME),
+        death_frame,
+    }
+}
+
+/*
+
+The tests validate :
+1. Golden runs: fixed seeds and scripts end on the recorded score and death frame,
+   for a single flap, steady flapping and a script recorded from the autopilot
+2. A script that never flaps never starts the run
+3. The same seed and script always give the same outcome
+
+*/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use FlapInput::{Flap, Wait};
+
+    // Flap every `interval` frames, `count` times
+    fn steady(interval: u32, count: usize) -> Vec<FlapInput> {
+        [Flap, Wait(interval - 1)].repeat(count)
+    }
+
+    #[test]
+    fn test_golden_runs() {
+        let single_flap = simulate(1, &[Flap]);
+        assert_eq!(single_flap.result.score, 0);
+        assert_eq!(single_flap.death_frame, Some(60));
+
+        // Steady flapping stays level and flies into the first pipe
+        let steady_run = simulate(1234, &steady(22, 40));
+        assert_eq!(steady_run.result.score, 0);
+        assert_eq!(steady_run.death_frame, Some(229));
+
+        // The autopilot survives its 1500 frames, then the bird drops
+        let autopilot = simulate(1234, &record_autopilot(1234, 1500));
+        assert_eq!(autopilot.result.score, 23);
+        assert_eq!(autopilot.death_frame, Some(1590));
+    }
+
+    #[test]
+    fn test_no_flap_never_starts() {
+        let run = simulate(1, &[Wait(120)]);
+        assert_eq!(run.death_frame, None);
+        assert_eq!(run.result.score, 0);
+        assert_eq!(run.result.duration, 0.0);
+    }
+
+    #[test]
+    fn test_same_script_same_outcome() {
+        let script = steady(20, 30);
+        assert_eq!(simulate(7, &script), simulate(7, &script));
+    }
+}
@@ -0,0 +1,39 @@
+use macroquad::prelude::*;
+
+// Summary - Theme :
+// Colours shared by the scenes. There is a single daytime theme for now.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    pub sky_top: Color,
+    pub sky_bottom: Color,
+
+    // Draw the procedural gradient even when sky.png is available
+    pub gradient_sky: bool,
+}
+
+impl Theme {
+    pub fn new() -> Self {
+        Theme {
+            // Roughly the colours at the top and bottom of sky.png
+            sky_top: Color::new(0.31, 0.75, 0.79, 1.0),
+            sky_bottom: Color::new(0.68, 0.89, 0.85, 1.0),
+            gradient_sky: false,
+        }
+    }
+}
+
+// Parses "#rrggbb" (the # is optional)
+pub fn parse_hex_color(value: &str) -> Option<Color> {
+    let hex = value.trim().trim_start_matches('#');
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+    let channel = |index: usize| u8::from_str_radix(&hex[index..index + 2], 16).ok();
+    Some(Color::from_rgba(channel(0)?, channel(2)?, channel(4)?, 255))
+}
+
+// "#rrggbb" for a colour, alpha is dropped
+pub fn to_hex_color(color: Color) -> String {
+    let [r, g, b, _]: [u8; 4] = color.into();
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
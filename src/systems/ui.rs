@@ -0,0 +1,94 @@
+use macroquad::prelude::*;
+
+// Offset of the outline copies from the text, in pixels
+pub const OUTLINE_WIDTH: f32 = 1.0;
+
+// High contrast mode: text this much bigger, on a near-opaque dark panel
+pub const HIGH_CONTRAST_SCALE: f32 = 1.5;
+const BACKING_COLOR: Color = Color::new(0.0, 0.0, 0.0, 0.85);
+const BACKING_PADDING: f32 = 6.0;
+
+pub fn font_size(base: u16, high_contrast: bool) -> u16 {
+    if high_contrast {
+        (base as f32 * HIGH_CONTRAST_SCALE).round() as u16
+    } else {
+        base
+    }
+}
+
+// Panel around text drawn with its baseline at (x, y)
+pub fn backing_rect(x: f32, y: f32, dims: TextDimensions) -> Rect {
+    Rect::new(
+        x - BACKING_PADDING,
+        y - dims.offset_y - BACKING_PADDING,
+        dims.width + BACKING_PADDING * 2.0,
+        dims.height + BACKING_PADDING * 2.0,
+    )
+}
+
+pub fn draw_backing(rect: Rect) {
+    draw_rectangle(rect.x, rect.y, rect.w, rect.h, BACKING_COLOR);
+}
+
+// Positions of the outline copies: left, right, up and down of the text
+pub fn outline_offsets(x: f32, y: f32, width: f32) -> [Vec2; 4] {
+    [
+        vec2(x - width, y),
+        vec2(x + width, y),
+        vec2(x, y - width),
+        vec2(x, y + width),
+    ]
+}
+
+// Draws `text` in `outline` shifted around it, then the text itself on top,
+// so it stays readable over light and dark backgrounds alike
+pub fn draw_text_outlined(text: &str, x: f32, y: f32, params: TextParams, outline: Color) {
+    for offset in outline_offsets(x, y, OUTLINE_WIDTH) {
+        draw_text_ex(text, offset.x, offset.y, TextParams { color: outline, ..params.clone() });
+    }
+    draw_text_ex(text, x, y, params);
+}
+
+/*
+
+The tests validate :
+1. The outline is drawn once in each direction around the text
+2. High contrast text is bigger, normal text keeps its size
+3. The backing panel covers the text with padding on every side
+
+*/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn test_outline_offsets() {
+        let offsets = outline_offsets(100.0, 50.0, OUTLINE_WIDTH);
+        let expected = [vec2(99.0, 50.0), vec2(101.0, 50.0), vec2(100.0, 49.0), vec2(100.0, 51.0)];
+
+        for (offset, expected) in offsets.iter().zip(expected) {
+            assert_float_eq!(offset.x, expected.x, abs <= 0.001);
+            assert_float_eq!(offset.y, expected.y, abs <= 0.001);
+        }
+    }
+
+    #[test]
+    fn test_font_size_for_high_contrast() {
+        assert_eq!(font_size(32, false), 32);
+        assert_eq!(font_size(32, true), 48);
+        assert_eq!(font_size(15, true), 23);
+    }
+
+    #[test]
+    fn test_backing_rect_covers_text() {
+        let dims = TextDimensions { width: 40.0, height: 20.0, offset_y: 16.0 };
+        let rect = backing_rect(100.0, 50.0, dims);
+
+        // Text spans x 100..140 and y 34..54
+        assert!(rect.x < 100.0 && rect.right() > 140.0);
+        assert!(rect.y < 34.0 && rect.bottom() > 54.0);
+        assert_float_eq!(rect.w - 40.0, rect.h - 20.0, abs <= 0.001);
+    }
+}
@@ -0,0 +1,98 @@
+use macroquad::miniquad::conf::Icon;
+use macroquad::prelude::*;
+use crate::prefabs::bird::Bird;
+
+// Summary - WindowIcon :
+// Builds the taskbar/title bar icon from the bird sprite sheet. The first
+// frame is centred on a transparent square and sampled down to the three
+// sizes the window system asks for. Runs before the window exists, so it
+// works on the raw file rather than a texture.
+
+// None when the file is missing or isn't an image, the default icon is used then
+pub fn load_icon(path: &str) -> Option<Icon> {
+    let bytes = std::fs::read(path).ok()?;
+    let image = Image::from_file_with_format(&bytes, None).ok()?;
+    icon_from_image(&image)
+}
+
+pub fn icon_from_image(image: &Image) -> Option<Icon> {
+    let (frame_count, frame_size) = Bird::frame_layout(image.width as f32, image.height as f32);
+    if frame_count == 0 {
+        return None;
+    }
+    let frame = image.sub_image(Bird::frame_source(0, frame_size));
+
+    let mut icon = Icon { small: [0; 16 * 16 * 4], medium: [0; 32 * 32 * 4], big: [0; 64 * 64 * 4] };
+    sample_square(&frame, 16, &mut icon.small);
+    sample_square(&frame, 32, &mut icon.medium);
+    sample_square(&frame, 64, &mut icon.big);
+    Some(icon)
+}
+
+// Nearest neighbour scale of `image` into a `size` x `size` RGBA buffer,
+// keeping its proportions and leaving the unused strip transparent
+fn sample_square(image: &Image, size: usize, out: &mut [u8]) {
+    let (width, height) = (image.width as usize, image.height as usize);
+    let side = width.max(height);
+    let (left, top) = ((side - width) / 2, (side - height) / 2);
+
+    for y in 0..size {
+        for x in 0..size {
+            let source_x = (x * side / size).checked_sub(left).filter(|x| *x < width);
+            let source_y = (y * side / size).checked_sub(top).filter(|y| *y < height);
+            if let (Some(source_x), Some(source_y)) = (source_x, source_y) {
+                let from = (source_y * width + source_x) * 4;
+                let to = (y * size + x) * 4;
+                out[to..to + 4].copy_from_slice(&image.bytes[from..from + 4]);
+            }
+        }
+    }
+}
+
+/*
+
+The tests validate :
+1. A missing or broken file gives no icon
+2. A valid sheet gives an icon made from its first frame, padded to a square
+
+*/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_or_broken_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("missing.png");
+        assert!(load_icon(missing.to_str().unwrap()).is_none());
+
+        let broken = dir.path().join("broken.png");
+        std::fs::write(&broken, b"not a png").unwrap();
+        assert!(load_icon(broken.to_str().unwrap()).is_none());
+    }
+
+    #[test]
+    fn test_icon_from_first_frame() {
+        // Two 34 x 24 frames, red then blue
+        let mut sheet = Image::gen_image_color(68, 24, Color::from_rgba(0, 0, 255, 255));
+        for y in 0..24 {
+            for x in 0..34 {
+                sheet.set_pixel(x, y, Color::from_rgba(255, 0, 0, 255));
+            }
+        }
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bird.png");
+        sheet.export_png(path.to_str().unwrap());
+
+        let icon = load_icon(path.to_str().unwrap()).unwrap();
+        let pixel = |x: usize, y: usize| &icon.big[(y * 64 + x) * 4..(y * 64 + x) * 4 + 4];
+        // The frame is wider than tall, so the top and bottom are padded
+        assert_eq!(pixel(32, 32), &[255, 0, 0, 255]);
+        assert_eq!(pixel(0, 32), &[255, 0, 0, 255]);
+        assert_eq!(pixel(32, 0), &[0, 0, 0, 0]);
+        assert_eq!(pixel(32, 63), &[0, 0, 0, 0]);
+        assert!(icon.small.chunks(4).any(|pixel| pixel == [255, 0, 0, 255]));
+        assert!(!icon.medium.chunks(4).any(|pixel| pixel[2] == 255));
+    }
+}
@@ -0,0 +1,11 @@
+// Lets the game ask whether its tab is hidden, see src/systems/platform.rs.
+// Required by the web build: load after mq_js_bundle.js and before the wasm starts.
+miniquad_add_plugin({
+    register_plugin: function (importObject) {
+        importObject.env.flappy_document_hidden = function () {
+            return document.hidden ? 1 : 0;
+        };
+    },
+    name: "flappy_visibility",
+    version: 1,
+});